@@ -1,6 +1,8 @@
 use std::convert::Infallible;
 
-use crate::{CreatePoolError, Manager, Pool, PoolBuilder, PoolConfig, Runtime};
+use deadpool::managed::EnvVars;
+
+use crate::{CreatePoolError, FromEnvError, Manager, Pool, PoolBuilder, PoolConfig, Runtime};
 
 /// Configuration object.
 ///
@@ -29,6 +31,12 @@ use crate::{CreatePoolError, Manager, Pool, PoolBuilder, PoolConfig, Runtime};
 ///     }
 /// }
 /// ```
+///
+/// Without the `serde` feature the [`Config::from_env()`] method can be used
+/// instead:
+/// ```rust
+/// let cfg = deadpool_lapin::Config::from_env("AMQP").unwrap();
+/// ```
 #[derive(Clone, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Config {
@@ -67,6 +75,26 @@ impl std::fmt::Debug for Config {
 }
 
 impl Config {
+    /// Reads a [`Config`] from environment variables starting with the given
+    /// `prefix`.
+    ///
+    /// The AMQP server URL is read from `<PREFIX>__URL` and the
+    /// [`PoolConfig`] using [`PoolConfig::from_env()`] from variables
+    /// starting with `<PREFIX>__POOL`. The connection properties keep their
+    /// default value.
+    ///
+    /// # Errors
+    ///
+    /// See [`FromEnvError`] for details.
+    pub fn from_env(prefix: &str) -> Result<Self, FromEnvError> {
+        let vars = EnvVars::new(prefix);
+        Ok(Self {
+            url: vars.get("URL")?,
+            pool: vars.pool_config("POOL")?,
+            connection_properties: lapin::ConnectionProperties::default(),
+        })
+    }
+
     /// Creates a new [`Pool`] using this [`Config`].
     ///
     /// # Errors
@@ -96,8 +124,9 @@ impl Config {
             Some(Runtime::AsyncStd1) => conn_props
                 .with_executor(async_executor_trait::AsyncStd)
                 .with_reactor(async_reactor_trait::AsyncIo),
+            // Other runtimes use the default executor and reactor of `lapin`.
             #[allow(unreachable_patterns)]
-            _ => unreachable!(),
+            _ => conn_props,
         };
 
         let mut builder = Pool::builder(Manager::new(url, conn_props)).config(pool_config);
@@ -1,19 +1,21 @@
 //! This module contains all the configuration structures
 
+#[cfg(any(feature = "core", feature = "replication"))]
+use std::collections::BTreeMap;
 #[cfg(any(feature = "core", feature = "replication", feature = "sync"))]
 use std::path::PathBuf;
 #[cfg(any(feature = "replication", feature = "sync"))]
 use std::time::Duration;
 
 use deadpool::{
-    managed::{CreatePoolError, PoolConfig},
+    managed::{CreatePoolError, EnvVars, PoolConfig},
     Runtime,
 };
 use libsql::Builder;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::{Manager, Pool, PoolBuilder};
+use crate::{FromEnvError, Manager, Pool, PoolBuilder};
 
 /// Configuration object.
 ///
@@ -39,6 +41,12 @@ use crate::{Manager, Pool, PoolBuilder};
 ///     }
 /// }
 /// ```
+///
+/// Without the `serde` feature the [`Config::from_env()`] method can be used
+/// instead:
+/// ```rust,no_run
+/// let cfg = deadpool_libsql::Config::from_env("LIBSQL").unwrap();
+/// ```
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Config {
@@ -48,6 +56,9 @@ pub struct Config {
     /// Pool configuration.
     #[cfg_attr(feature = "serde", serde(default))]
     pub pool: PoolConfig,
+    /// Manager configuration.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub manager: Option<ManagerConfig>,
 }
 
 impl Config {
@@ -57,9 +68,40 @@ impl Config {
         Self {
             database,
             pool: PoolConfig::default(),
+            manager: None,
         }
     }
 
+    /// Reads a [`Config`] from environment variables starting with the given
+    /// `prefix`.
+    ///
+    /// The variables are named like the ones read by the `config` crate:
+    /// The kind of [`Database`] is read from `<PREFIX>__DATABASE`, e.g.
+    /// `Local`, and its fields from variables named like them in upper
+    /// case, e.g. `<PREFIX>__PATH`. Every `<PREFIX>__PRAGMAS__<NAME>`
+    /// variable adds a pragma. The [`PoolConfig`] is read using
+    /// [`PoolConfig::from_env()`] from variables starting with
+    /// `<PREFIX>__POOL` and the [`ManagerConfig`] from variables starting
+    /// with `<PREFIX>__MANAGER`.
+    ///
+    /// # Errors
+    ///
+    /// See [`FromEnvError`] for details. The kind of database and its
+    /// required fields must be set.
+    pub fn from_env(prefix: &str) -> Result<Self, FromEnvError> {
+        let vars = EnvVars::new(prefix);
+        let manager = vars.nested("MANAGER");
+        Ok(Self {
+            database: Database::from_env_vars(&vars)?,
+            pool: vars.pool_config("POOL")?.unwrap_or_default(),
+            manager: if manager.is_present() {
+                Some(ManagerConfig::from_env_vars(&manager)?)
+            } else {
+                None
+            },
+        })
+    }
+
     /// Create a new [`Pool`] using this [`Config`].
     ///
     /// # Errors
@@ -86,6 +128,92 @@ impl Config {
         let manager = Manager::from_config(self).await?;
         Ok(Pool::builder(manager).config(config))
     }
+
+    /// Returns [`ManagerConfig`] which can be used to construct a
+    /// [`Manager`] instance.
+    #[must_use]
+    pub fn get_manager_config(&self) -> ManagerConfig {
+        self.manager.clone().unwrap_or_default()
+    }
+}
+
+/// Possible methods of how a connection is recycled.
+///
+/// The default is [`Verified`] which runs a test query every time a
+/// connection is recycled.
+///
+/// [`Verified`]: RecyclingMethod::Verified
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum RecyclingMethod {
+    /// Don't check the connection when recycling it.
+    ///
+    /// This avoids a round-trip to the database. A broken connection is only
+    /// noticed by the first query run on it.
+    Fast,
+
+    /// Execute a test query when recycling a connection.
+    ///
+    /// This is slower, but guarantees that the database connection is ready to
+    /// be used.
+    #[default]
+    Verified,
+
+    /// Like [`Verified`] but rolls back any transaction which was left open
+    /// and resets the connection state before executing the test query.
+    ///
+    /// [`Verified`]: RecyclingMethod::Verified
+    Clean,
+}
+
+/// Configuration object for a [`Manager`].
+///
+/// This makes it possible to specify which [`RecyclingMethod`] should be
+/// used when retrieving existing objects from the [`Pool`] and which query is
+/// used to check the health of a connection.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ManagerConfig {
+    /// Method of how a connection is recycled. See [`RecyclingMethod`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub recycling_method: RecyclingMethod,
+    /// SQL executed to check that a connection is usable, e.g.
+    /// `PRAGMA user_version` or a statement touching a table which is
+    /// required by the application. The check succeeds if the query
+    /// doesn't fail.
+    ///
+    /// By default `SELECT ?` is executed with a counter and the returned
+    /// value is compared to it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub test_query: Option<String>,
+    /// Don't execute the test query when creating a connection.
+    ///
+    /// As libSQL connects lazily a broken connection is then only noticed by
+    /// the first query run on it or when it is recycled. This saves a
+    /// round-trip when creating connections to remote databases.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub skip_test_query_on_create: bool,
+}
+
+impl ManagerConfig {
+    fn from_env_vars(vars: &EnvVars) -> Result<Self, FromEnvError> {
+        Ok(Self {
+            recycling_method: vars
+                .parse_with("RECYCLING_METHOD", |v| {
+                    parse_variant(
+                        v,
+                        &[
+                            ("fast", RecyclingMethod::Fast),
+                            ("verified", RecyclingMethod::Verified),
+                            ("clean", RecyclingMethod::Clean),
+                        ],
+                    )
+                })?
+                .unwrap_or_default(),
+            test_query: vars.get("TEST_QUERY")?,
+            skip_test_query_on_create: vars.bool("SKIP_TEST_QUERY_ON_CREATE")?.unwrap_or(false),
+        })
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -112,6 +240,75 @@ pub enum Database {
 }
 
 impl Database {
+    fn from_env_vars(vars: &EnvVars) -> Result<Self, FromEnvError> {
+        let database = vars.require("DATABASE")?;
+        match database.to_ascii_lowercase().as_str() {
+            #[cfg(feature = "core")]
+            "local" => Ok(Self::Local(Local {
+                path: vars.require("PATH")?.into(),
+                encryption_config: EncryptionConfig::from_env_vars(
+                    &vars.nested("ENCRYPTION_CONFIG"),
+                )?,
+                flags: OpenFlags::from_env_vars(&vars.nested("FLAGS"))?,
+                pragmas: pragmas_from_env_vars(&vars.nested("PRAGMAS"))?,
+            })),
+            #[cfg(feature = "replication")]
+            "localreplica" => Ok(Self::LocalReplica(LocalReplica {
+                path: vars.require("PATH")?.into(),
+                encryption_config: EncryptionConfig::from_env_vars(
+                    &vars.nested("ENCRYPTION_CONFIG"),
+                )?,
+                flags: OpenFlags::from_env_vars(&vars.nested("FLAGS"))?,
+                pragmas: pragmas_from_env_vars(&vars.nested("PRAGMAS"))?,
+            })),
+            #[cfg(feature = "remote")]
+            "remote" => Ok(Self::Remote(Remote {
+                url: vars.require("URL")?,
+                auth_token: vars.require("AUTH_TOKEN")?,
+                namespace: vars.get("NAMESPACE")?,
+                remote_encryption: EncryptionContext::from_env_vars(
+                    &vars.nested("REMOTE_ENCRYPTION"),
+                )?,
+            })),
+            #[cfg(feature = "replication")]
+            "remotereplica" => Ok(Self::RemoteReplica(RemoteReplica {
+                path: vars.require("PATH")?.into(),
+                url: vars.require("URL")?,
+                auth_token: vars.require("AUTH_TOKEN")?,
+                encryption_config: EncryptionConfig::from_env_vars(
+                    &vars.nested("ENCRYPTION_CONFIG"),
+                )?,
+                namespace: vars.get("NAMESPACE")?,
+                read_your_writes: vars.bool("READ_YOUR_WRITES")?,
+                remote_encryption: EncryptionContext::from_env_vars(
+                    &vars.nested("REMOTE_ENCRYPTION"),
+                )?,
+                sync_interval: vars.duration("SYNC_INTERVAL")?,
+                sync_protocol: vars.parse_with("SYNC_PROTOCOL", |v| {
+                    parse_variant(v, &[("v1", SyncProtocol::V1), ("v2", SyncProtocol::V2)])
+                })?,
+            })),
+            #[cfg(feature = "sync")]
+            "synceddatabase" => Ok(Self::SyncedDatabase(SyncedDatabase {
+                path: vars.require("PATH")?.into(),
+                url: vars.require("URL")?,
+                auth_token: vars.require("AUTH_TOKEN")?,
+                read_your_writes: vars.bool("READ_YOUR_WRITES")?,
+                remote_encryption: EncryptionContext::from_env_vars(
+                    &vars.nested("REMOTE_ENCRYPTION"),
+                )?,
+                remote_writes: vars.bool("REMOTE_WRITES")?,
+                set_push_batch_size: vars.parse("SET_PUSH_BATCH_SIZE")?,
+                sync_interval: vars.duration("SYNC_INTERVAL")?,
+            })),
+            _ => Err(FromEnvError::Invalid {
+                name: vars.name("DATABASE"),
+                value: database,
+                reason: "unknown database or its feature is disabled".to_owned(),
+            }),
+        }
+    }
+
     pub(crate) async fn libsql_database(&self) -> Result<libsql::Database, libsql::Error> {
         match self {
             #[cfg(feature = "core")]
@@ -133,6 +330,18 @@ impl Database {
             _ => compile_error!("At least one of the following features must be enabled: core, replication, remote, sync"),
         }
     }
+
+    /// Returns the `PRAGMA` statements to execute on every new connection.
+    pub(crate) fn pragmas(&self) -> Vec<(String, String)> {
+        match self {
+            #[cfg(feature = "core")]
+            Self::Local(x) => x.pragmas.clone().into_iter().collect(),
+            #[cfg(feature = "replication")]
+            Self::LocalReplica(x) => x.pragmas.clone().into_iter().collect(),
+            #[allow(unreachable_patterns)]
+            _ => Vec::new(),
+        }
+    }
 }
 
 #[cfg(feature = "core")]
@@ -143,6 +352,13 @@ pub struct Local {
     pub path: PathBuf,
     pub encryption_config: Option<EncryptionConfig>,
     pub flags: Option<OpenFlags>,
+    /// `PRAGMA` statements executed right after a connection has been
+    /// created, e.g. `journal_mode = "WAL"` or `foreign_keys = "ON"`.
+    /// See [`Manager::with_pragmas()`] for how they are validated.
+    ///
+    /// [`Manager::with_pragmas()`]: crate::Manager::with_pragmas
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pragmas: BTreeMap<String, String>,
 }
 
 #[cfg(feature = "core")]
@@ -168,6 +384,23 @@ pub struct EncryptionConfig {
     pub encryption_key: bytes::Bytes,
 }
 
+#[cfg(any(feature = "core", feature = "replication"))]
+impl EncryptionConfig {
+    fn from_env_vars(vars: &EnvVars) -> Result<Option<Self>, FromEnvError> {
+        if !vars.is_present() {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            cipher: vars
+                .parse_with("CIPHER", |v| {
+                    parse_variant(v, &[("aes256cbc", Cipher::Aes256Cbc)])
+                })?
+                .unwrap_or_default(),
+            encryption_key: vars.require("ENCRYPTION_KEY")?.into(),
+        }))
+    }
+}
+
 #[cfg(feature = "core")]
 impl EncryptionConfig {
     fn to_libsql(&self) -> libsql::EncryptionConfig {
@@ -210,6 +443,17 @@ pub struct OpenFlags {
 
 #[cfg(any(feature = "core", feature = "replication"))]
 impl OpenFlags {
+    fn from_env_vars(vars: &EnvVars) -> Result<Option<Self>, FromEnvError> {
+        if !vars.is_present() {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            read_only: vars.bool("READ_ONLY")?.unwrap_or(false),
+            read_write: vars.bool("READ_WRITE")?.unwrap_or(false),
+            create: vars.bool("CREATE")?.unwrap_or(false),
+        }))
+    }
+
     fn to_libsql(self) -> libsql::OpenFlags {
         (if self.read_only {
             libsql::OpenFlags::SQLITE_OPEN_READ_ONLY
@@ -235,6 +479,13 @@ pub struct LocalReplica {
     pub path: PathBuf,
     pub encryption_config: Option<EncryptionConfig>,
     pub flags: Option<OpenFlags>,
+    /// `PRAGMA` statements executed right after a connection has been
+    /// created, e.g. `journal_mode = "WAL"` or `foreign_keys = "ON"`.
+    /// See [`Manager::with_pragmas()`] for how they are validated.
+    ///
+    /// [`Manager::with_pragmas()`]: crate::Manager::with_pragmas
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pragmas: BTreeMap<String, String>,
 }
 
 #[cfg(feature = "replication")]
@@ -361,6 +612,16 @@ pub struct EncryptionContext {
     pub key: EncryptionKey,
 }
 
+#[cfg(any(feature = "remote", feature = "replication", feature = "sync"))]
+impl EncryptionContext {
+    /// The key is read as a base64-encoded string from `<PREFIX>__KEY`.
+    fn from_env_vars(vars: &EnvVars) -> Result<Option<Self>, FromEnvError> {
+        Ok(vars.get("KEY")?.map(|key| Self {
+            key: EncryptionKey::Base64Encoded(key),
+        }))
+    }
+}
+
 #[cfg(feature = "sync")]
 impl EncryptionContext {
     #[cfg(feature = "sync")]
@@ -456,5 +717,28 @@ impl SyncedDatabase {
     }
 }
 
+/// Reads the pragmas from all variables starting with the prefix of the
+/// given `vars`. The names of the pragmas are converted to lower case.
+#[cfg(any(feature = "core", feature = "replication"))]
+fn pragmas_from_env_vars(vars: &EnvVars) -> Result<BTreeMap<String, String>, FromEnvError> {
+    let prefix = vars.name("");
+    std::env::vars_os()
+        .filter_map(|(name, _)| Some(name.to_str()?.strip_prefix(&prefix)?.to_owned()))
+        .map(|key| Ok((key.to_ascii_lowercase(), vars.require(&key)?)))
+        .collect()
+}
+
+/// Parses one of the given `variants` ignoring the case.
+fn parse_variant<T: Clone>(value: &str, variants: &[(&str, T)]) -> Result<T, String> {
+    variants
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(value))
+        .map(|(_, variant)| variant.clone())
+        .ok_or_else(|| {
+            let names = variants.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+            format!("expected one of: {}", names.join(", "))
+        })
+}
+
 /// This error is returned if there is something wrong with the libSQL configuration.
 pub type ConfigError = libsql::Error;
@@ -26,7 +26,7 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use deadpool::managed::{self, RecycleError};
 
 pub mod config;
-pub use config::Config;
+pub use config::{Config, ManagerConfig, RecyclingMethod};
 mod errors;
 
 pub use libsql;
@@ -50,28 +50,79 @@ pub type Connection = managed::Object<Manager>;
 #[derive(Debug)]
 pub struct Manager {
     database: libsql::Database,
+    config: ManagerConfig,
+    /// `PRAGMA` statements executed on every new connection.
+    pragmas: Vec<String>,
     test_query_count: AtomicU64,
 }
 
 impl Manager {
     /// Creates a new [`Manager`] using the given [`libsql::Database`].
     pub fn from_libsql_database(database: libsql::Database) -> Self {
+        Self::from_libsql_database_with_config(database, ManagerConfig::default())
+    }
+
+    /// Creates a new [`Manager`] using the given [`libsql::Database`] and
+    /// [`ManagerConfig`].
+    pub fn from_libsql_database_with_config(
+        database: libsql::Database,
+        config: ManagerConfig,
+    ) -> Self {
         Self {
             database,
+            config,
+            pragmas: Vec::new(),
             test_query_count: AtomicU64::new(0),
         }
     }
 
     /// Creates a new [`Manager`] using the given [`config::Config`].
     pub async fn from_config(config: Config) -> Result<Self, libsql::Error> {
-        config
-            .database
-            .libsql_database()
-            .await
-            .map(Self::from_libsql_database)
+        let manager_config = config.get_manager_config();
+        let database = config.database.libsql_database().await?;
+        Self::from_libsql_database_with_config(database, manager_config)
+            .with_pragmas(config.database.pragmas())
+    }
+
+    /// Sets the pragmas which are set right after a connection has been
+    /// created, e.g. `journal_mode` to `WAL`.
+    ///
+    /// Numbers, identifiers and quoted strings are used as values as they
+    /// are. Any other value is quoted as a string literal.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`libsql::Error::Misuse`] if the name of a pragma isn't
+    /// an identifier optionally prefixed by a schema name.
+    pub fn with_pragmas<K, V>(
+        mut self,
+        pragmas: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<Self, libsql::Error>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.pragmas = pragmas
+            .into_iter()
+            .map(|(name, value)| pragma_statement(name.as_ref(), value.as_ref()))
+            .collect::<Result<_, _>>()?;
+        Ok(self)
+    }
+
+    async fn apply_pragmas(&self, conn: &libsql::Connection) -> Result<(), ConnectionError> {
+        for statement in &self.pragmas {
+            // Some pragmas return the new value, so `query` is used as
+            // `execute` fails for statements returning rows.
+            let _ = conn.query(statement, ()).await?.next().await?;
+        }
+        Ok(())
     }
 
     async fn run_test_query(&self, conn: &libsql::Connection) -> Result<(), ConnectionError> {
+        if let Some(test_query) = &self.config.test_query {
+            let _ = conn.query(test_query, ()).await?.next().await?;
+            return Ok(());
+        }
         let test_query_count = self.test_query_count.fetch_add(1, Ordering::Relaxed);
         // A call to the database to check that it is accessible
         let row = conn
@@ -100,10 +151,13 @@ impl managed::Manager for Manager {
 
     async fn create(&self) -> Result<Self::Type, Self::Error> {
         let conn = self.database.connect()?;
+        self.apply_pragmas(&conn).await?;
         // Libsql establishes the database connection lazily. Thus the
         // only way to check if the connection is in a useable state is
         // to run a test query.
-        self.run_test_query(&conn).await?;
+        if !self.config.skip_test_query_on_create {
+            self.run_test_query(&conn).await?;
+        }
         Ok(conn)
     }
 
@@ -112,8 +166,67 @@ impl managed::Manager for Manager {
         conn: &mut Self::Type,
         _: &Metrics,
     ) -> managed::RecycleResult<Self::Error> {
+        match self.config.recycling_method {
+            RecyclingMethod::Fast => return Ok(()),
+            RecyclingMethod::Verified => {}
+            RecyclingMethod::Clean => {
+                if !conn.is_autocommit() {
+                    let _ = conn
+                        .execute("ROLLBACK", ())
+                        .await
+                        .map_err(|e| RecycleError::Backend(e.into()))?;
+                }
+                conn.reset().await;
+            }
+        }
         self.run_test_query(conn)
             .await
             .map_err(RecycleError::Backend)
     }
 }
+
+/// Builds the statement setting the pragma `name` to the given `value`.
+fn pragma_statement(name: &str, value: &str) -> Result<String, libsql::Error> {
+    let mut parts = name.split('.');
+    let valid_name = parts.next().is_some_and(is_identifier)
+        && parts.next().map_or(true, is_identifier)
+        && parts.next().is_none();
+    if !valid_name {
+        return Err(libsql::Error::Misuse(format!(
+            "Invalid pragma name: {:?}",
+            name
+        )));
+    }
+    let value = value.trim();
+    if is_identifier(value) || is_integer(value) || is_quoted(value) {
+        Ok(format!("PRAGMA {} = {}", name, value))
+    } else {
+        Ok(format!("PRAGMA {} = '{}'", name, value.replace('\'', "''")))
+    }
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn is_integer(s: &str) -> bool {
+    let digits = s.strip_prefix(['-', '+']).unwrap_or(s);
+    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Indicates whether `s` is a single string literal or quoted identifier
+/// in which every quote is escaped by doubling it.
+fn is_quoted(s: &str) -> bool {
+    ['\'', '"'].into_iter().any(|quote| {
+        let Some(inner) = s.strip_prefix(quote).and_then(|s| s.strip_suffix(quote)) else {
+            return false;
+        };
+        !inner
+            .replace(&format!("{}{}", quote, quote), "")
+            .contains(quote)
+    })
+}
@@ -39,8 +39,10 @@ async fn fail_at_connect_to_local() {
                 read_only: false,
                 read_write: true,
             }),
+            pragmas: Default::default(),
         }),
         pool: deadpool_libsql::PoolConfig::default(),
+        manager: None,
     };
     let pool = config.create_pool(None).await.unwrap();
     let result = pool.get().await;
@@ -64,6 +66,7 @@ async fn fail_at_connect_to_remote() {
             remote_encryption: None,
         }),
         pool: deadpool_libsql::PoolConfig::default(),
+        manager: None,
     };
     let pool = config.create_pool(None).await.unwrap();
     let result = pool.get().await;
@@ -73,3 +76,198 @@ async fn fail_at_connect_to_remote() {
         result.unwrap()
     );
 }
+
+#[cfg(feature = "core")]
+async fn create_pool_with_recycling_method(
+    recycling_method: deadpool_libsql::RecyclingMethod,
+) -> deadpool_libsql::Pool {
+    let database = deadpool_libsql::libsql::Builder::new_local(":memory:")
+        .build()
+        .await
+        .unwrap();
+    let manager = deadpool_libsql::Manager::from_libsql_database_with_config(
+        database,
+        deadpool_libsql::ManagerConfig {
+            recycling_method,
+            ..Default::default()
+        },
+    );
+    deadpool_libsql::Pool::builder(manager)
+        .max_size(1)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+#[cfg(feature = "core")]
+async fn recycling_method_fast() {
+    let pool = create_pool_with_recycling_method(deadpool_libsql::RecyclingMethod::Fast).await;
+    let conn = pool.get().await.unwrap();
+    let _ = conn.execute("BEGIN", ()).await.unwrap();
+    drop(conn);
+    let conn = pool.get().await.unwrap();
+    assert!(!conn.is_autocommit());
+}
+
+#[tokio::test]
+#[cfg(feature = "core")]
+async fn recycling_method_clean() {
+    let pool = create_pool_with_recycling_method(deadpool_libsql::RecyclingMethod::Clean).await;
+    let conn = pool.get().await.unwrap();
+    let _ = conn.execute("BEGIN", ()).await.unwrap();
+    drop(conn);
+    let conn = pool.get().await.unwrap();
+    assert!(conn.is_autocommit());
+}
+
+#[cfg(feature = "core")]
+async fn create_pool_with_test_query(test_query: &str) -> deadpool_libsql::Pool {
+    let database = deadpool_libsql::libsql::Builder::new_local(":memory:")
+        .build()
+        .await
+        .unwrap();
+    let manager = deadpool_libsql::Manager::from_libsql_database_with_config(
+        database,
+        deadpool_libsql::ManagerConfig {
+            test_query: Some(test_query.into()),
+            ..Default::default()
+        },
+    );
+    deadpool_libsql::Pool::builder(manager).build().unwrap()
+}
+
+#[tokio::test]
+#[cfg(feature = "core")]
+async fn test_query() {
+    let pool = create_pool_with_test_query("PRAGMA user_version").await;
+    assert!(pool.get().await.is_ok());
+}
+
+#[tokio::test]
+#[cfg(feature = "core")]
+async fn test_query_failed() {
+    let pool = create_pool_with_test_query("SELECT * FROM does_not_exist").await;
+    assert!(matches!(
+        pool.get().await,
+        Err(deadpool_libsql::PoolError::Backend(
+            deadpool_libsql::ConnectionError::Libsql(_)
+        ))
+    ));
+}
+
+/// This test makes sure that no connection is established when the test
+/// query is skipped on create.
+#[cfg(feature = "remote")]
+#[tokio::test]
+async fn skip_test_query_on_create() {
+    let config = deadpool_libsql::config::Config {
+        database: deadpool_libsql::config::Database::Remote(deadpool_libsql::config::Remote {
+            url: "http://invalid-hostname.example.com:1337".into(),
+            auth_token: "nothing here".into(),
+            namespace: None,
+            remote_encryption: None,
+        }),
+        pool: deadpool_libsql::PoolConfig::default(),
+        manager: Some(deadpool_libsql::ManagerConfig {
+            skip_test_query_on_create: true,
+            ..Default::default()
+        }),
+    };
+    let pool = config.create_pool(None).await.unwrap();
+    let conn = pool.get().await.unwrap();
+    assert!(conn.query("SELECT 1", ()).await.is_err());
+}
+
+#[tokio::test]
+#[cfg(feature = "core")]
+async fn pragmas() {
+    let config = deadpool_libsql::config::Config::new(deadpool_libsql::config::Database::Local(
+        deadpool_libsql::config::Local {
+            path: ":memory:".into(),
+            encryption_config: None,
+            flags: None,
+            pragmas: [
+                ("foreign_keys".into(), "ON".into()),
+                ("journal_mode".into(), "\"MEMORY\"".into()),
+                ("user_version".into(), "42".into()),
+            ]
+            .into(),
+        },
+    ));
+    let pool = config.create_pool(None).await.unwrap();
+    let conn = pool.get().await.unwrap();
+    for (pragma, expected) in [("foreign_keys", "1"), ("user_version", "42")] {
+        let row = conn
+            .query(&format!("PRAGMA {}", pragma), ())
+            .await
+            .unwrap()
+            .next()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(row.get::<i64>(0).unwrap().to_string(), expected);
+    }
+}
+
+#[tokio::test]
+#[cfg(feature = "core")]
+async fn pragmas_invalid_name() {
+    let config = deadpool_libsql::config::Config::new(deadpool_libsql::config::Database::Local(
+        deadpool_libsql::config::Local {
+            path: ":memory:".into(),
+            encryption_config: None,
+            flags: None,
+            pragmas: [("user_version = 42; --".into(), "0".into())].into(),
+        },
+    ));
+    assert!(config.create_pool(None).await.is_err());
+}
+
+#[tokio::test]
+#[cfg(feature = "core")]
+async fn pragmas_quoted_value() {
+    let database = deadpool_libsql::libsql::Builder::new_local(":memory:")
+        .build()
+        .await
+        .unwrap();
+    let manager = deadpool_libsql::Manager::from_libsql_database(database)
+        .with_pragmas([("journal_mode", "MEMORY"), ("user_version", "'; invalid")])
+        .unwrap();
+    let pool = deadpool_libsql::Pool::builder(manager).build().unwrap();
+    // The value is quoted instead of being spliced into the statement.
+    let conn = pool.get().await.unwrap();
+    let row = conn
+        .query("PRAGMA user_version", ())
+        .await
+        .unwrap()
+        .next()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(row.get::<i64>(0).unwrap(), 0);
+}
+
+#[test]
+#[cfg(feature = "core")]
+fn config_from_env() {
+    use deadpool_libsql::{config::Database, RecyclingMethod};
+    std::env::set_var("ENV_VARS_TEST__LIBSQL__DATABASE", "Local");
+    std::env::set_var("ENV_VARS_TEST__LIBSQL__PATH", "db.sqlite3");
+    std::env::set_var("ENV_VARS_TEST__LIBSQL__PRAGMAS__JOURNAL_MODE", "WAL");
+    std::env::set_var("ENV_VARS_TEST__LIBSQL__POOL__MAX_SIZE", "4");
+    std::env::set_var("ENV_VARS_TEST__LIBSQL__MANAGER__RECYCLING_METHOD", "fast");
+    let cfg = deadpool_libsql::Config::from_env("ENV_VARS_TEST__LIBSQL").unwrap();
+    let Database::Local(local) = &cfg.database else {
+        panic!("unexpected database: {:?}", cfg.database);
+    };
+    assert_eq!(local.path, std::path::Path::new("db.sqlite3"));
+    assert!(local.flags.is_none());
+    assert_eq!(local.pragmas.get("journal_mode").unwrap(), "WAL");
+    assert_eq!(cfg.pool.max_size, 4);
+    assert_eq!(
+        cfg.get_manager_config().recycling_method,
+        RecyclingMethod::Fast
+    );
+    std::env::set_var("ENV_VARS_TEST__LIBSQL_INVALID__DATABASE", "Unknown");
+    assert!(deadpool_libsql::Config::from_env("ENV_VARS_TEST__LIBSQL_INVALID").is_err());
+}
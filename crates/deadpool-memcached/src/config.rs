@@ -0,0 +1,81 @@
+use deadpool::managed::EnvVars;
+
+use crate::{CreatePoolError, FromEnvError, Manager, Pool, PoolBuilder, PoolConfig, Runtime};
+
+/// Configuration object.
+///
+/// # Example (from environment)
+///
+/// The configuration can be read from environment variables using
+/// [`Config::from_env()`]:
+/// ```env
+/// MEMCACHED__ADDR=localhost:11211
+/// MEMCACHED__POOL__MAX_SIZE=16
+/// MEMCACHED__POOL__TIMEOUTS__WAIT=5s
+/// ```
+/// ```rust,no_run
+/// let cfg = deadpool_memcached::Config::from_env("MEMCACHED").unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    /// Address of the memcached server, e.g. `localhost:11211`.
+    pub addr: String,
+
+    /// [`Pool`] configuration.
+    pub pool: Option<PoolConfig>,
+}
+
+impl Config {
+    /// Create a new [`Config`] with the given `addr` of the memcached
+    /// server.
+    #[must_use]
+    pub fn new(addr: impl Into<String>) -> Self {
+        Self {
+            addr: addr.into(),
+            pool: None,
+        }
+    }
+
+    /// Reads a [`Config`] from environment variables starting with the given
+    /// `prefix`.
+    ///
+    /// The address of the memcached server is read from `<PREFIX>__ADDR`
+    /// and the [`PoolConfig`] using [`PoolConfig::from_env()`] from
+    /// variables starting with `<PREFIX>__POOL`.
+    ///
+    /// # Errors
+    ///
+    /// See [`FromEnvError`] for details. The address is required.
+    pub fn from_env(prefix: &str) -> Result<Self, FromEnvError> {
+        let vars = EnvVars::new(prefix);
+        Ok(Self {
+            addr: vars.require("ADDR")?,
+            pool: vars.pool_config("POOL")?,
+        })
+    }
+
+    /// Creates a new [`Pool`] using this [`Config`].
+    ///
+    /// # Errors
+    ///
+    /// See [`CreatePoolError`] for details.
+    pub fn create_pool(&self, runtime: Option<Runtime>) -> Result<Pool, CreatePoolError> {
+        let mut builder = self.builder();
+        if let Some(runtime) = runtime {
+            builder = builder.runtime(runtime);
+        }
+        builder.build().map_err(CreatePoolError::Build)
+    }
+
+    /// Creates a new [`PoolBuilder`] using this [`Config`].
+    pub fn builder(&self) -> PoolBuilder {
+        Pool::builder(Manager::new(self.addr.clone())).config(self.get_pool_config())
+    }
+
+    /// Returns [`deadpool::managed::PoolConfig`] which can be used to construct
+    /// a [`deadpool::managed::Pool`] instance.
+    #[must_use]
+    pub fn get_pool_config(&self) -> PoolConfig {
+        self.pool.unwrap_or_default()
+    }
+}
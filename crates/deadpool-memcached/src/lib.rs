@@ -11,13 +11,22 @@ use std::convert::Infallible;
 
 use async_memcached::{Client, Error};
 
+mod config;
+pub use config::Config;
+
 /// Type alias for using [`deadpool::managed::RecycleResult`] with [`redis`].
 type RecycleResult = deadpool::managed::RecycleResult<Error>;
 
 type ConfigError = Infallible;
 
 pub use deadpool::managed::reexports::*;
-deadpool::managed_reexports!("memcached", Manager, Client, Error, ConfigError);
+deadpool::managed_reexports!(
+    "memcached",
+    Manager,
+    deadpool::managed::Object<Manager>,
+    Error,
+    ConfigError
+);
 
 /// The manager for creating and recyling memcache connections
 pub struct Manager {
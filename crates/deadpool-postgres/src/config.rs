@@ -17,7 +17,9 @@ use tokio_postgres::{
     Socket,
 };
 
-use super::PoolConfig;
+use deadpool::managed::EnvVars;
+
+use super::{FromEnvError, PoolConfig};
 
 /// Configuration object.
 ///
@@ -48,6 +50,13 @@ use super::PoolConfig;
 ///     }
 /// }
 /// ```
+///
+/// If the configuration is only read from the environment the
+/// [`Config::from_env()`] method can be used instead which doesn't require
+/// the `serde` feature nor the `config` crate:
+/// ```rust
+/// let cfg = deadpool_postgres::Config::from_env("PG").unwrap();
+/// ```
 #[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Config {
@@ -151,6 +160,87 @@ impl Config {
         Self::default()
     }
 
+    /// Reads a [`Config`] from environment variables starting with the given
+    /// `prefix`.
+    ///
+    /// Every field is read from the variable named like the field in upper
+    /// case, e.g. `PG__DBNAME` for the `prefix` `PG`. Nested fields are
+    /// separated by `__`. The `hosts`, `hostaddrs` and `ports` lists are
+    /// comma separated and durations can be given in a human readable form
+    /// like `5s`. The [`PoolConfig`] is read using
+    /// [`PoolConfig::from_env()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`FromEnvError`] for details.
+    pub fn from_env(prefix: &str) -> Result<Self, FromEnvError> {
+        let vars = EnvVars::new(prefix);
+        let manager = vars.nested("MANAGER");
+        Ok(Self {
+            url: vars.get("URL")?,
+            user: vars.get("USER")?,
+            password: vars.get("PASSWORD")?,
+            dbname: vars.get("DBNAME")?,
+            options: vars.get("OPTIONS")?,
+            application_name: vars.get("APPLICATION_NAME")?,
+            ssl_mode: vars.parse_with("SSL_MODE", |v| {
+                parse_variant(
+                    v,
+                    &[
+                        ("disable", SslMode::Disable),
+                        ("prefer", SslMode::Prefer),
+                        ("require", SslMode::Require),
+                    ],
+                )
+            })?,
+            host: vars.get("HOST")?,
+            hosts: vars.parse_with("HOSTS", parse_list)?,
+            hostaddr: vars.parse("HOSTADDR")?,
+            hostaddrs: vars.parse_with("HOSTADDRS", parse_list)?,
+            port: vars.parse("PORT")?,
+            ports: vars.parse_with("PORTS", parse_list)?,
+            connect_timeout: vars.duration("CONNECT_TIMEOUT")?,
+            keepalives: vars.bool("KEEPALIVES")?,
+            #[cfg(not(target_arch = "wasm32"))]
+            keepalives_idle: vars.duration("KEEPALIVES_IDLE")?,
+            target_session_attrs: vars.parse_with("TARGET_SESSION_ATTRS", |v| {
+                parse_variant(
+                    v,
+                    &[
+                        ("any", TargetSessionAttrs::Any),
+                        ("read_write", TargetSessionAttrs::ReadWrite),
+                        ("readwrite", TargetSessionAttrs::ReadWrite),
+                    ],
+                )
+            })?,
+            channel_binding: vars.parse_with("CHANNEL_BINDING", |v| {
+                parse_variant(
+                    v,
+                    &[
+                        ("disable", ChannelBinding::Disable),
+                        ("prefer", ChannelBinding::Prefer),
+                        ("require", ChannelBinding::Require),
+                    ],
+                )
+            })?,
+            load_balance_hosts: vars.parse_with("LOAD_BALANCE_HOSTS", |v| {
+                parse_variant(
+                    v,
+                    &[
+                        ("disable", LoadBalanceHosts::Disable),
+                        ("random", LoadBalanceHosts::Random),
+                    ],
+                )
+            })?,
+            manager: if manager.is_present() {
+                Some(ManagerConfig::from_env_vars(&manager)?)
+            } else {
+                None
+            },
+            pool: vars.pool_config("POOL")?,
+        })
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     /// Creates a new [`Pool`] using this [`Config`].
     ///
@@ -296,6 +386,30 @@ impl Config {
     }
 }
 
+/// Parses a comma separated list of values.
+fn parse_list<T>(value: &str) -> Result<Vec<T>, String>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    value
+        .split(',')
+        .map(|item| item.trim().parse().map_err(|e: T::Err| e.to_string()))
+        .collect()
+}
+
+/// Parses one of the given `variants` ignoring the case.
+fn parse_variant<T: Clone>(value: &str, variants: &[(&str, T)]) -> Result<T, String> {
+    variants
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(value))
+        .map(|(_, variant)| variant.clone())
+        .ok_or_else(|| {
+            let names = variants.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+            format!("expected one of: {}", names.join(", "))
+        })
+}
+
 /// Possible methods of how a connection is recycled.
 ///
 /// The default is [`Fast`] which does not check the connection health or
@@ -386,6 +500,29 @@ pub struct ManagerConfig {
     pub recycling_method: RecyclingMethod,
 }
 
+impl ManagerConfig {
+    /// Reads the `RECYCLING_METHOD` being one of `fast`, `verified` or
+    /// `clean`. A custom SQL is read from `RECYCLING_METHOD__CUSTOM`.
+    fn from_env_vars(vars: &EnvVars) -> Result<Self, FromEnvError> {
+        let recycling_method = match vars.nested("RECYCLING_METHOD").get("CUSTOM")? {
+            Some(sql) => RecyclingMethod::Custom(sql),
+            None => vars
+                .parse_with("RECYCLING_METHOD", |v| {
+                    parse_variant(
+                        v,
+                        &[
+                            ("fast", RecyclingMethod::Fast),
+                            ("verified", RecyclingMethod::Verified),
+                            ("clean", RecyclingMethod::Clean),
+                        ],
+                    )
+                })?
+                .unwrap_or_default(),
+        };
+        Ok(Self { recycling_method })
+    }
+}
+
 /// Properties required of a session.
 ///
 /// This is a 1:1 copy of the [`PgTargetSessionAttrs`] enumeration.
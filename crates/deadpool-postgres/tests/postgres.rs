@@ -274,6 +274,41 @@ fn config_from_env() {
     assert_eq!(pool_cfg.timeouts.recycle, Some(Duration::from_secs(3)));
 }
 
+#[test]
+fn config_from_env_vars() {
+    let mut env = Env::new();
+    env.set(
+        "ENV_VARS_TEST__PG__HOSTS",
+        "pg1.example.com, pg2.example.com",
+    );
+    env.set("ENV_VARS_TEST__PG__PORT", "5433");
+    env.set("ENV_VARS_TEST__PG__DBNAME", "example");
+    env.set("ENV_VARS_TEST__PG__SSL_MODE", "require");
+    env.set("ENV_VARS_TEST__PG__CONNECT_TIMEOUT", "5s");
+    env.set("ENV_VARS_TEST__PG__MANAGER__RECYCLING_METHOD", "verified");
+    env.set("ENV_VARS_TEST__PG__POOL__MAX_SIZE", "42");
+    env.set("ENV_VARS_TEST__PG__POOL__TIMEOUTS__WAIT", "1s");
+    let cfg = deadpool_postgres::Config::from_env("ENV_VARS_TEST__PG").unwrap();
+    assert_eq!(
+        cfg.hosts,
+        Some(vec!["pg1.example.com".into(), "pg2.example.com".into()])
+    );
+    assert_eq!(cfg.port, Some(5433));
+    assert_eq!(cfg.dbname, Some("example".to_string()));
+    assert_eq!(cfg.ssl_mode, Some(deadpool_postgres::SslMode::Require));
+    assert_eq!(cfg.connect_timeout, Some(Duration::from_secs(5)));
+    assert_eq!(
+        cfg.get_manager_config().recycling_method,
+        RecyclingMethod::Verified
+    );
+    let pool_cfg = cfg.get_pool_config();
+    assert_eq!(pool_cfg.max_size, 42);
+    assert_eq!(pool_cfg.timeouts.wait, Some(Duration::from_secs(1)));
+
+    env.set("ENV_VARS_TEST__PG__PORT", "postgres");
+    assert!(deadpool_postgres::Config::from_env("ENV_VARS_TEST__PG").is_err());
+}
+
 #[test]
 fn config_url() {
     let mut cfg = deadpool_postgres::Config {
@@ -1,5 +1,7 @@
+use deadpool::managed::EnvVars;
+
 pub use crate::config::ConfigError;
-use crate::ConnectionInfo;
+use crate::{ConnectionInfo, FromEnvError};
 
 use super::{CreatePoolError, Pool, PoolBuilder, PoolConfig, Runtime};
 
@@ -36,6 +38,12 @@ use super::{CreatePoolError, Pool, PoolBuilder, PoolConfig, Runtime};
 ///     }
 /// }
 /// ```
+///
+/// Without the `serde` feature the [`Config::from_env()`] method can be used
+/// instead:
+/// ```rust
+/// let cfg = deadpool_redis::cluster::Config::from_env("REDIS_CLUSTER").unwrap();
+/// ```
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Config {
@@ -64,6 +72,34 @@ pub struct Config {
 }
 
 impl Config {
+    /// Reads a [`Config`] from environment variables starting with the given
+    /// `prefix`.
+    ///
+    /// The comma separated Redis URLs are read from `<PREFIX>__URLS` and the
+    /// [`PoolConfig`] using [`PoolConfig::from_env()`] from variables
+    /// starting with `<PREFIX>__POOL`. The [`ConnectionInfo`] structures
+    /// can't be read from the environment. Use URLs instead.
+    ///
+    /// # Errors
+    ///
+    /// See [`FromEnvError`] for details.
+    pub fn from_env(prefix: &str) -> Result<Self, FromEnvError> {
+        let vars = EnvVars::new(prefix);
+        let mut cfg = match vars.get("URLS")? {
+            Some(urls) => Self::from_urls(
+                urls.split(',')
+                    .map(|url| url.trim().to_owned())
+                    .collect::<Vec<_>>(),
+            ),
+            None => Self::default(),
+        };
+        if let Some(read_from_replicas) = vars.bool("READ_FROM_REPLICAS")? {
+            cfg.read_from_replicas = read_from_replicas;
+        }
+        cfg.pool = vars.pool_config("POOL")?;
+        Ok(cfg)
+    }
+
     /// Creates a new [`Pool`] using this [`Config`].
     ///
     /// # Errors
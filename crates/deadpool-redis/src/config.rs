@@ -4,7 +4,9 @@ use redis::RedisError;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::{CreatePoolError, Pool, PoolBuilder, PoolConfig, RedisResult, Runtime};
+use deadpool::managed::EnvVars;
+
+use crate::{CreatePoolError, FromEnvError, Pool, PoolBuilder, PoolConfig, RedisResult, Runtime};
 
 /// Configuration object.
 ///
@@ -33,6 +35,12 @@ use crate::{CreatePoolError, Pool, PoolBuilder, PoolConfig, RedisResult, Runtime
 ///     }
 /// }
 /// ```
+///
+/// Without the `serde` feature the [`Config::from_env()`] method can be used
+/// instead:
+/// ```rust
+/// let cfg = deadpool_redis::Config::from_env("REDIS").unwrap();
+/// ```
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde"))]
@@ -50,6 +58,27 @@ pub struct Config {
 }
 
 impl Config {
+    /// Reads a [`Config`] from environment variables starting with the given
+    /// `prefix`.
+    ///
+    /// The Redis URL is read from `<PREFIX>__URL` and the [`PoolConfig`]
+    /// using [`PoolConfig::from_env()`] from variables starting with
+    /// `<PREFIX>__POOL`. The [`ConnectionInfo`] structure can't be read from
+    /// the environment. Use an URL instead.
+    ///
+    /// # Errors
+    ///
+    /// See [`FromEnvError`] for details.
+    pub fn from_env(prefix: &str) -> Result<Self, FromEnvError> {
+        let vars = EnvVars::new(prefix);
+        let mut cfg = match vars.get("URL")? {
+            Some(url) => Self::from_url(url),
+            None => Self::default(),
+        };
+        cfg.pool = vars.pool_config("POOL")?;
+        Ok(cfg)
+    }
+
     /// Creates a new [`Pool`] using this [`Config`].
     ///
     /// # Errors
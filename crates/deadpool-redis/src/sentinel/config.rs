@@ -1,5 +1,7 @@
+use deadpool::managed::EnvVars;
+
 pub use crate::config::ConfigError;
-use crate::{ConnectionAddr, ConnectionInfo, RedisConnectionInfo};
+use crate::{ConnectionAddr, ConnectionInfo, FromEnvError, RedisConnectionInfo};
 
 use super::{CreatePoolError, Pool, PoolBuilder, PoolConfig, Runtime};
 
@@ -37,6 +39,12 @@ use super::{CreatePoolError, Pool, PoolBuilder, PoolConfig, Runtime};
 ///     }
 /// }
 /// ```
+///
+/// Without the `serde` feature the [`Config::from_env()`] method can be used
+/// instead:
+/// ```rust
+/// let cfg = deadpool_redis::sentinel::Config::from_env("REDIS_SENTINEL").unwrap();
+/// ```
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Config {
@@ -61,6 +69,45 @@ pub struct Config {
 }
 
 impl Config {
+    /// Reads a [`Config`] from environment variables starting with the given
+    /// `prefix`.
+    ///
+    /// The comma separated Redis URLs are read from `<PREFIX>__URLS`, the
+    /// `SERVER_TYPE` being either `master` or `replica` and the
+    /// `MASTER_NAME` from the corresponding variables. The [`PoolConfig`] is
+    /// read using [`PoolConfig::from_env()`] from variables starting with
+    /// `<PREFIX>__POOL`. The [`ConnectionInfo`] and
+    /// [`SentinelNodeConnectionInfo`] structures can't be read from the
+    /// environment.
+    ///
+    /// # Errors
+    ///
+    /// See [`FromEnvError`] for details.
+    pub fn from_env(prefix: &str) -> Result<Self, FromEnvError> {
+        let vars = EnvVars::new(prefix);
+        let mut cfg = Self::default();
+        if let Some(urls) = vars.get("URLS")? {
+            cfg.urls = Some(urls.split(',').map(|url| url.trim().to_owned()).collect());
+            cfg.connections = None;
+        }
+        if let Some(server_type) = vars.parse_with("SERVER_TYPE", |v| {
+            if v.eq_ignore_ascii_case("master") {
+                Ok(SentinelServerType::Master)
+            } else if v.eq_ignore_ascii_case("replica") {
+                Ok(SentinelServerType::Replica)
+            } else {
+                Err("expected `master` or `replica`".to_owned())
+            }
+        })? {
+            cfg.server_type = server_type;
+        }
+        if let Some(master_name) = vars.get("MASTER_NAME")? {
+            cfg.master_name = master_name;
+        }
+        cfg.pool = vars.pool_config("POOL")?;
+        Ok(cfg)
+    }
+
     /// Creates a new [`Pool`] using this [`Config`].
     ///
     /// # Errors
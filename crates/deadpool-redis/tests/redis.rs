@@ -191,3 +191,19 @@ async fn test_recycled_with_watch() {
         );
     }
 }
+
+#[test]
+fn config_from_env() {
+    std::env::set_var("ENV_VARS_TEST__REDIS__URL", "redis://redis.example.com");
+    std::env::set_var("ENV_VARS_TEST__REDIS__POOL__MAX_SIZE", "16");
+    std::env::set_var("ENV_VARS_TEST__REDIS__POOL__TIMEOUTS__WAIT", "2s");
+    let cfg = deadpool_redis::Config::from_env("ENV_VARS_TEST__REDIS").unwrap();
+    assert_eq!(cfg.url.as_deref(), Some("redis://redis.example.com"));
+    assert!(cfg.connection.is_none());
+    let pool_cfg = cfg.get_pool_config();
+    assert_eq!(pool_cfg.max_size, 16);
+    assert_eq!(
+        pool_cfg.timeouts.wait,
+        Some(std::time::Duration::from_secs(2))
+    );
+}
@@ -21,10 +21,22 @@
 )]
 #![allow(clippy::uninlined_format_args)]
 
-use std::{any::Any, fmt, future::Future, time::Duration};
+use std::{
+    any::Any,
+    fmt,
+    future::{poll_fn, Future},
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::{pin, Pin},
+    sync::{Arc, Mutex},
+    task::{Poll, Waker},
+    time::Duration,
+};
+
+/// Boxed [`Future`] as used by [`CustomRuntime`].
+pub type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send + 'static>>;
 
 /// Enumeration for picking a runtime implementation.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug)]
 pub enum Runtime {
     #[cfg(feature = "tokio_1")]
     #[cfg_attr(docsrs, doc(cfg(feature = "tokio_1")))]
@@ -35,9 +47,95 @@ pub enum Runtime {
     #[cfg_attr(docsrs, doc(cfg(feature = "async-std_1")))]
     /// [`async-std` 1.0](async_std_1) runtime.
     AsyncStd1,
+
+    #[cfg(feature = "smol_2")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "smol_2")))]
+    /// [`smol` 2.0](smol_2) runtime.
+    ///
+    /// Tasks are spawned on the global executor of [`smol`](smol_2) and
+    /// timers are driven by [`async-io`](smol_2::Timer).
+    Smol2,
+
+    /// Runtime provided by a [`CustomRuntime`] implementation. This makes
+    /// it possible to use executors other than the built-in ones.
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use deadpool_runtime::{BoxFuture, CustomRuntime, Runtime};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyRuntime;
+    ///
+    /// impl CustomRuntime for MyRuntime {
+    ///     fn sleep(&self, duration: Duration) -> BoxFuture<()> {
+    ///         // Use the timer of your executor here.
+    ///         Box::pin(async move { std::thread::sleep(duration) })
+    ///     }
+    ///     fn spawn(&self, future: BoxFuture<()>) {
+    ///         // Use the spawn function of your executor here.
+    ///         drop(future);
+    ///     }
+    /// }
+    ///
+    /// static MY_RUNTIME: MyRuntime = MyRuntime;
+    ///
+    /// let runtime = Runtime::Custom(&MY_RUNTIME);
+    /// ```
+    Custom(&'static dyn CustomRuntime),
 }
 
+/// Runtime implementation used by [`Runtime::Custom`].
+///
+/// Implement this for executors which aren't supported by the [`Runtime`]
+/// enumeration out of the box, e.g. `smol` or `embassy`.
+pub trait CustomRuntime: fmt::Debug + Send + Sync {
+    /// Returns a [`Future`] which completes after the given `duration` has
+    /// elapsed. This is used for implementing timeouts, too.
+    fn sleep(&self, duration: Duration) -> BoxFuture<()>;
+
+    /// Spawns the given `future` as a new detached task running in the
+    /// background.
+    fn spawn(&self, future: BoxFuture<()>);
+
+    /// Runs the given closure on a thread where blocking is acceptable.
+    ///
+    /// The default implementation spawns a new thread for every call.
+    fn spawn_blocking(&self, f: Box<dyn FnOnce() + Send + 'static>) {
+        drop(std::thread::spawn(f));
+    }
+}
+
+impl PartialEq for Runtime {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Custom(a), Self::Custom(b)) => std::ptr::eq(
+                *a as *const dyn CustomRuntime as *const (),
+                *b as *const dyn CustomRuntime as *const (),
+            ),
+            #[allow(unreachable_patterns)]
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl Eq for Runtime {}
+
 impl Runtime {
+    /// Detects the runtime the current thread is running in.
+    ///
+    /// Only [`Runtime::Tokio1`] can be detected. This returns `None` if the
+    /// `tokio_1` feature is disabled or the current thread isn't running in
+    /// the context of a Tokio runtime.
+    #[must_use]
+    pub fn detect() -> Option<Self> {
+        #[cfg(feature = "tokio_1")]
+        if tokio_1::runtime::Handle::try_current().is_ok() {
+            return Some(Self::Tokio1);
+        }
+        None
+    }
+
     /// Requires a [`Future`] to complete before the specified `duration` has
     /// elapsed.
     ///
@@ -54,8 +152,64 @@ impl Runtime {
             Self::Tokio1 => tokio_1::time::timeout(duration, future).await.ok(),
             #[cfg(feature = "async-std_1")]
             Self::AsyncStd1 => async_std_1::future::timeout(duration, future).await.ok(),
-            #[allow(unreachable_patterns)]
-            _ => unreachable!(),
+            #[cfg(feature = "smol_2")]
+            Self::Smol2 => {
+                smol_2::future::or(async { Some(future.await) }, async {
+                    let _ = smol_2::Timer::after(duration).await;
+                    None
+                })
+                .await
+            }
+            Self::Custom(rt) => {
+                let mut future = pin!(future);
+                let mut sleep = rt.sleep(duration);
+                poll_fn(|cx| {
+                    if let Poll::Ready(output) = future.as_mut().poll(cx) {
+                        return Poll::Ready(Some(output));
+                    }
+                    sleep.as_mut().poll(cx).map(|()| None)
+                })
+                .await
+            }
+        }
+    }
+
+    /// Waits until the specified `duration` has elapsed.
+    #[allow(unused_variables)]
+    pub async fn sleep(&self, duration: Duration) {
+        match self {
+            #[cfg(feature = "tokio_1")]
+            Self::Tokio1 => tokio_1::time::sleep(duration).await,
+            #[cfg(feature = "async-std_1")]
+            Self::AsyncStd1 => async_std_1::task::sleep(duration).await,
+            #[cfg(feature = "smol_2")]
+            Self::Smol2 => drop(smol_2::Timer::after(duration).await),
+            Self::Custom(rt) => rt.sleep(duration).await,
+        }
+    }
+
+    /// Spawns the given `future` as a new task running in the background.
+    ///
+    /// The task is detached. It keeps running until the `future` completes
+    /// or the runtime shuts down.
+    ///
+    /// # Panics
+    ///
+    /// When using [`Runtime::Tokio1`] this must be called from the context of
+    /// a Tokio runtime.
+    #[allow(unused_variables)]
+    pub fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        match self {
+            #[cfg(feature = "tokio_1")]
+            Self::Tokio1 => drop(tokio_1::spawn(future)),
+            #[cfg(feature = "async-std_1")]
+            Self::AsyncStd1 => drop(async_std_1::task::spawn(future)),
+            #[cfg(feature = "smol_2")]
+            Self::Smol2 => smol_2::spawn(future).detach(),
+            Self::Custom(rt) => rt.spawn(Box::pin(future)),
         }
     }
 
@@ -77,8 +231,18 @@ impl Runtime {
                 .map_err(|e| SpawnBlockingError::Panic(e.into_panic())),
             #[cfg(feature = "async-std_1")]
             Self::AsyncStd1 => Ok(async_std_1::task::spawn_blocking(f).await),
-            #[allow(unreachable_patterns)]
-            _ => unreachable!(),
+            #[cfg(feature = "smol_2")]
+            Self::Smol2 => smol_2::unblock(move || catch_unwind(AssertUnwindSafe(f)))
+                .await
+                .map_err(SpawnBlockingError::Panic),
+            Self::Custom(rt) => {
+                let slot = Arc::new(Mutex::new(Slot::<R>::default()));
+                let sender = Slot::sender(&slot);
+                rt.spawn_blocking(Box::new(move || {
+                    sender(catch_unwind(AssertUnwindSafe(f)));
+                }));
+                Slot::receive(slot).await.map_err(SpawnBlockingError::Panic)
+            }
         }
     }
 
@@ -106,12 +270,62 @@ impl Runtime {
                 drop(async_std_1::task::spawn_blocking(f));
                 Ok(())
             }
-            #[allow(unreachable_patterns)]
-            _ => unreachable!(),
+            #[cfg(feature = "smol_2")]
+            Self::Smol2 => {
+                smol_2::unblock(f).detach();
+                Ok(())
+            }
+            Self::Custom(rt) => {
+                rt.spawn_blocking(Box::new(f));
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Result of a closure passed to [`CustomRuntime::spawn_blocking()`]
+/// along with the [`Waker`] of the task waiting for it.
+struct Slot<R> {
+    result: Option<std::thread::Result<R>>,
+    waker: Option<Waker>,
+}
+
+impl<R> Default for Slot<R> {
+    fn default() -> Self {
+        Self {
+            result: None,
+            waker: None,
         }
     }
 }
 
+impl<R> Slot<R> {
+    fn sender(slot: &Arc<Mutex<Self>>) -> impl FnOnce(std::thread::Result<R>) {
+        let slot = slot.clone();
+        move |result| {
+            let mut slot = slot.lock().unwrap_or_else(|e| e.into_inner());
+            slot.result = Some(result);
+            if let Some(waker) = slot.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+
+    async fn receive(slot: Arc<Mutex<Self>>) -> std::thread::Result<R> {
+        poll_fn(|cx| {
+            let mut slot = slot.lock().unwrap_or_else(|e| e.into_inner());
+            match slot.result.take() {
+                Some(result) => Poll::Ready(result),
+                None => {
+                    slot.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        })
+        .await
+    }
+}
+
 /// Error of spawning a task on a thread where blocking is acceptable.
 #[derive(Debug)]
 pub enum SpawnBlockingError {
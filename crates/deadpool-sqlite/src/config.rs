@@ -1,6 +1,8 @@
 use std::{convert::Infallible, path::PathBuf};
 
-use crate::{CreatePoolError, Manager, Pool, PoolBuilder, PoolConfig, Runtime};
+use deadpool::managed::EnvVars;
+
+use crate::{CreatePoolError, FromEnvError, Manager, Pool, PoolBuilder, PoolConfig, Runtime};
 
 /// Configuration object.
 ///
@@ -28,6 +30,12 @@ use crate::{CreatePoolError, Manager, Pool, PoolBuilder, PoolConfig, Runtime};
 ///     }
 /// }
 /// ```
+///
+/// Without the `serde` feature the [`Config::from_env()`] method can be used
+/// instead:
+/// ```rust,no_run
+/// let cfg = deadpool_sqlite::Config::from_env("SQLITE").unwrap();
+/// ```
 #[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Config {
@@ -48,6 +56,24 @@ impl Config {
         }
     }
 
+    /// Reads a [`Config`] from environment variables starting with the given
+    /// `prefix`.
+    ///
+    /// The path of the SQLite database file is read from `<PREFIX>__PATH`
+    /// and the [`PoolConfig`] using [`PoolConfig::from_env()`] from
+    /// variables starting with `<PREFIX>__POOL`.
+    ///
+    /// # Errors
+    ///
+    /// See [`FromEnvError`] for details. The path is required.
+    pub fn from_env(prefix: &str) -> Result<Self, FromEnvError> {
+        let vars = EnvVars::new(prefix);
+        Ok(Self {
+            path: vars.require("PATH")?.into(),
+            pool: vars.pool_config("POOL")?,
+        })
+    }
+
     /// Creates a new [`Pool`] using this [`Config`].
     ///
     /// # Errors
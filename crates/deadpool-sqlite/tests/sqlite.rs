@@ -51,3 +51,13 @@ async fn panic() {
         .unwrap();
     assert_eq!(result, 1);
 }
+
+#[test]
+fn config_from_env() {
+    std::env::set_var("ENV_VARS_TEST__SQLITE__PATH", "db.sqlite3");
+    std::env::set_var("ENV_VARS_TEST__SQLITE__POOL__MAX_SIZE", "4");
+    let cfg = Config::from_env("ENV_VARS_TEST__SQLITE").unwrap();
+    assert_eq!(cfg.path, std::path::Path::new("db.sqlite3"));
+    assert_eq!(cfg.get_pool_config().max_size, 4);
+    assert!(Config::from_env("ENV_VARS_TEST__MISSING").is_err());
+}
@@ -1,10 +1,38 @@
-use std::{convert::TryInto, fmt::Display};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    convert::TryInto,
+    fmt::Display,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 
-use deadpool::managed::Metrics;
+use deadpool::managed::{
+    Metrics,
+    QueueMode::{self, Fifo, Lifo},
+};
 use tokio::task::JoinHandle;
 
+/// Global allocator counting the allocations so the number of allocations
+/// per `get` can be reported along with the timings.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
 //const ITERATIONS: usize = 1_048_576;
 const ITERATIONS: usize = 1 << 15;
 
@@ -12,11 +40,20 @@ const ITERATIONS: usize = 1 << 15;
 struct Config {
     pool_size: usize,
     workers: usize,
+    shards: usize,
+    queue_mode: QueueMode,
 }
 
 impl Display for Config {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "w{}s{}", self.workers, self.pool_size)
+        write!(f, "w{}s{}", self.workers, self.pool_size)?;
+        if self.shards > 1 {
+            write!(f, "h{}", self.shards)?;
+        }
+        if let QueueMode::Lifo = self.queue_mode {
+            write!(f, "lifo")?;
+        }
+        Ok(())
     }
 }
 
@@ -29,17 +66,27 @@ impl Config {
 #[rustfmt::skip]
 const CONFIGS: &[Config] = &[
     // 8 workers
-    Config { workers:  8, pool_size:  2 },
-    Config { workers:  8, pool_size:  4 },
-    Config { workers:  8, pool_size:  8 },
+    Config { workers:  8, pool_size:  2, shards: 1, queue_mode: Fifo },
+    Config { workers:  8, pool_size:  4, shards: 1, queue_mode: Fifo },
+    Config { workers:  8, pool_size:  8, shards: 1, queue_mode: Fifo },
     // 16 workers
-    Config { workers: 16, pool_size:  4 },
-    Config { workers: 16, pool_size:  8 },
-    Config { workers: 16, pool_size: 16 },
+    Config { workers: 16, pool_size:  4, shards: 1, queue_mode: Fifo },
+    Config { workers: 16, pool_size:  8, shards: 1, queue_mode: Fifo },
+    Config { workers: 16, pool_size: 16, shards: 1, queue_mode: Fifo },
     // 32 workers
-    Config { workers: 32, pool_size:  8 },
-    Config { workers: 32, pool_size: 16 },
-    Config { workers: 32, pool_size: 32 },
+    Config { workers: 32, pool_size:  8, shards: 1, queue_mode: Fifo },
+    Config { workers: 32, pool_size: 16, shards: 1, queue_mode: Fifo },
+    Config { workers: 32, pool_size: 32, shards: 1, queue_mode: Fifo },
+    // 64 workers
+    Config { workers: 64, pool_size: 32, shards: 1, queue_mode: Fifo },
+    Config { workers: 64, pool_size: 32, shards: 8, queue_mode: Fifo },
+    Config { workers: 64, pool_size: 64, shards: 1, queue_mode: Fifo },
+    Config { workers: 64, pool_size: 64, shards: 8, queue_mode: Fifo },
+    // 64 workers using the mutex protected storage of the LIFO mode
+    Config { workers: 64, pool_size: 32, shards: 1, queue_mode: Lifo },
+    Config { workers: 64, pool_size: 32, shards: 8, queue_mode: Lifo },
+    Config { workers: 64, pool_size: 64, shards: 1, queue_mode: Lifo },
+    Config { workers: 64, pool_size: 64, shards: 8, queue_mode: Lifo },
 ];
 
 struct Manager {}
@@ -64,6 +111,8 @@ type Pool = deadpool::managed::Pool<Manager>;
 async fn bench_get(cfg: Config) {
     let pool = Pool::builder(Manager {})
         .max_size(cfg.pool_size)
+        .shards(cfg.shards)
+        .queue_mode(cfg.queue_mode)
         .build()
         .unwrap();
     let join_handles: Vec<JoinHandle<()>> = (0..cfg.workers)
@@ -88,6 +137,16 @@ fn criterion_benchmark(c: &mut Criterion) {
         ITERATIONS.try_into().expect("Can't convert u64 to usize"),
     ));
     for &config in CONFIGS {
+        // This includes the allocations for building the pool, creating the
+        // objects and spawning the workers.
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        runtime.block_on(bench_get(config));
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+        println!(
+            "managed/get/{}: {:.4} allocations per get",
+            config,
+            allocations as f64 / ITERATIONS as f64
+        );
         group.bench_with_input(BenchmarkId::new("get", config), &config, |b, &cfg| {
             b.to_async(&runtime).iter(|| bench_get(cfg))
         });
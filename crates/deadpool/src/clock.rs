@@ -0,0 +1,22 @@
+//! Source of the current time for lifetimes, statistics and timeouts.
+//!
+//! With the `rt_tokio_1` feature the clock of Tokio is used. It can be
+//! paused and advanced via `tokio::time::pause()` and
+//! `tokio::time::advance()` when the `test-util` feature of `tokio` is
+//! enabled, which makes the time seen by the pool deterministic in tests.
+//! Outside of a paused runtime it is the same as [`Instant::now()`].
+
+use std::time::{Duration, Instant};
+
+/// Returns the current instant.
+pub(crate) fn now() -> Instant {
+    #[cfg(feature = "rt_tokio_1")]
+    return tokio::time::Instant::now().into_std();
+    #[cfg(not(feature = "rt_tokio_1"))]
+    return Instant::now();
+}
+
+/// Returns the time elapsed since the given `instant`.
+pub(crate) fn elapsed(instant: Instant) -> Duration {
+    now().saturating_duration_since(instant)
+}
@@ -0,0 +1,71 @@
+//! Helpers shared by the configurations of the managed and the unmanaged
+//! pool.
+
+use std::{fmt, time::Duration};
+
+/// Deserializes an optional [`Duration`] either from a human readable
+/// string like `"500ms"` or `"30s"` or from the struct form `{ secs, nanos }`.
+///
+/// Formats which aren't human readable only support the struct form.
+pub(crate) fn deserialize_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::{
+        de::{self, value::MapAccessDeserializer, value::SeqAccessDeserializer, Visitor},
+        Deserialize,
+    };
+
+    struct DurationVisitor;
+
+    impl<'de> Visitor<'de> for DurationVisitor {
+        type Value = Duration;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a duration like \"30s\" or a struct with `secs` and `nanos`")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Duration, E> {
+            humantime::parse_duration(v).map_err(E::custom)
+        }
+
+        fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<Duration, A::Error> {
+            Duration::deserialize(MapAccessDeserializer::new(map))
+        }
+
+        fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<Duration, A::Error> {
+            Duration::deserialize(SeqAccessDeserializer::new(seq))
+        }
+    }
+
+    struct OptionVisitor;
+
+    impl<'de> Visitor<'de> for OptionVisitor {
+        type Value = Option<Duration>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("an optional duration")
+        }
+
+        fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D: serde::Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_any(DurationVisitor).map(Some)
+        }
+    }
+
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_option(OptionVisitor)
+    } else {
+        Option::<Duration>::deserialize(deserializer)
+    }
+}
@@ -0,0 +1,239 @@
+//! Reading configurations from environment variables without depending on
+//! the [`config`](https://crates.io/crates/config) crate.
+
+use std::{env, ffi::OsString, fmt, str::FromStr, time::Duration};
+
+/// Environment variables sharing a common prefix.
+///
+/// Nested names are separated by a double underscore (`__`) following the
+/// convention of the [`config`](https://crates.io/crates/config) crate.
+/// E.g. the `max_size` of [`EnvVars::new("PG__POOL")`](EnvVars::new) is read
+/// from the `PG__POOL__MAX_SIZE` variable.
+///
+/// Backend crates use this for implementing their `Config::from_env()`
+/// methods.
+#[derive(Clone, Debug)]
+pub struct EnvVars {
+    pub(crate) prefix: String,
+}
+
+impl EnvVars {
+    /// Creates a new [`EnvVars`] reading variables with the given `prefix`.
+    ///
+    /// With an empty `prefix` the variables are read without any prefix.
+    #[must_use]
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+
+    /// Returns the name of the variable holding the given `key`.
+    #[must_use]
+    pub fn name(&self, key: &str) -> String {
+        if self.prefix.is_empty() {
+            key.to_owned()
+        } else {
+            format!("{}__{}", self.prefix, key)
+        }
+    }
+
+    /// Returns the [`EnvVars`] of the nested section with the given `key`.
+    #[must_use]
+    pub fn nested(&self, key: &str) -> Self {
+        Self::new(self.name(key))
+    }
+
+    /// Indicates whether any variable of this section is set.
+    #[must_use]
+    pub fn is_present(&self) -> bool {
+        let prefix = self.name("");
+        env::vars_os().any(|(name, _)| name.to_str().is_some_and(|n| n.starts_with(&prefix)))
+    }
+
+    /// Reads the variable holding the given `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FromEnvError::NotUnicode`] if the variable isn't valid
+    /// unicode.
+    pub fn get(&self, key: &str) -> Result<Option<String>, FromEnvError> {
+        let name = self.name(key);
+        match env::var(&name) {
+            Ok(value) => Ok(Some(value)),
+            Err(env::VarError::NotPresent) => Ok(None),
+            Err(env::VarError::NotUnicode(value)) => Err(FromEnvError::NotUnicode { name, value }),
+        }
+    }
+
+    /// Reads the variable holding the given `key` and fails if it isn't
+    /// set.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FromEnvError::Missing`] if the variable isn't set.
+    pub fn require(&self, key: &str) -> Result<String, FromEnvError> {
+        self.get(key)?.ok_or_else(|| FromEnvError::Missing {
+            name: self.name(key),
+        })
+    }
+
+    /// Reads and parses the variable holding the given `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FromEnvError::Invalid`] if the value can't be parsed.
+    pub fn parse<T>(&self, key: &str) -> Result<Option<T>, FromEnvError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.parse_with(key, |value| value.parse::<T>().map_err(|e| e.to_string()))
+    }
+
+    /// Reads and parses the variable holding the given `key` and fails if
+    /// it isn't set.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FromEnvError::Missing`] if the variable isn't set.
+    pub fn require_parse<T>(&self, key: &str) -> Result<T, FromEnvError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.parse(key)?.ok_or_else(|| FromEnvError::Missing {
+            name: self.name(key),
+        })
+    }
+
+    /// Reads the variable holding the given `key` and parses it using the
+    /// given function.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FromEnvError::Invalid`] if the function fails.
+    pub fn parse_with<T>(
+        &self,
+        key: &str,
+        f: impl FnOnce(&str) -> Result<T, String>,
+    ) -> Result<Option<T>, FromEnvError> {
+        let Some(value) = self.get(key)? else {
+            return Ok(None);
+        };
+        match f(value.trim()) {
+            Ok(v) => Ok(Some(v)),
+            Err(reason) => Err(FromEnvError::Invalid {
+                name: self.name(key),
+                value,
+                reason,
+            }),
+        }
+    }
+
+    /// Reads a boolean. Besides `true` and `false` the values `1`, `0`,
+    /// `yes`, `no`, `on` and `off` are accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FromEnvError::Invalid`] if the value isn't a boolean.
+    pub fn bool(&self, key: &str) -> Result<Option<bool>, FromEnvError> {
+        self.parse_with(key, |value| match value.to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => Ok(true),
+            "false" | "0" | "no" | "off" => Ok(false),
+            _ => Err("expected a boolean".to_owned()),
+        })
+    }
+
+    /// Reads a [`Duration`] either from a human readable value like `500ms`
+    /// or `30s` or from the two variables `<KEY>__SECS` and `<KEY>__NANOS`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FromEnvError::Invalid`] if the value isn't a duration.
+    pub fn duration(&self, key: &str) -> Result<Option<Duration>, FromEnvError> {
+        if let Some(duration) = self.parse_with(key, |value| {
+            humantime::parse_duration(value).map_err(|e| e.to_string())
+        })? {
+            return Ok(Some(duration));
+        }
+        let nested = self.nested(key);
+        let secs = nested.parse::<u64>("SECS")?;
+        let nanos = nested.parse::<u32>("NANOS")?;
+        if secs.is_none() && nanos.is_none() {
+            return Ok(None);
+        }
+        Duration::from_secs(secs.unwrap_or(0))
+            .checked_add(Duration::from_nanos(nanos.unwrap_or(0).into()))
+            .map(Some)
+            .ok_or_else(|| FromEnvError::Invalid {
+                name: nested.name("SECS"),
+                value: secs.unwrap_or(0).to_string(),
+                reason: "duration overflow".to_owned(),
+            })
+    }
+
+    /// Reads a [`Duration`] like [`EnvVars::duration()`] and fails if it
+    /// isn't set.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FromEnvError::Missing`] if the variable isn't set.
+    pub fn require_duration(&self, key: &str) -> Result<Duration, FromEnvError> {
+        self.duration(key)?.ok_or_else(|| FromEnvError::Missing {
+            name: self.name(key),
+        })
+    }
+}
+
+/// Possible errors returned when reading a configuration from environment
+/// variables.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum FromEnvError {
+    /// A required variable isn't set.
+    Missing {
+        /// Name of the variable.
+        name: String,
+    },
+
+    /// A variable isn't valid unicode.
+    NotUnicode {
+        /// Name of the variable.
+        name: String,
+        /// Value of the variable.
+        value: OsString,
+    },
+
+    /// A variable couldn't be parsed.
+    Invalid {
+        /// Name of the variable.
+        name: String,
+        /// Value of the variable.
+        value: String,
+        /// Reason why the value is invalid.
+        reason: String,
+    },
+}
+
+impl fmt::Display for FromEnvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { name } => write!(f, "Environment variable {} is not set", name),
+            Self::NotUnicode { name, .. } => {
+                write!(f, "Environment variable {} is not valid unicode", name)
+            }
+            Self::Invalid {
+                name,
+                value,
+                reason,
+            } => write!(
+                f,
+                "Environment variable {} has an invalid value {:?}: {}",
+                name, value, reason
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FromEnvError {}
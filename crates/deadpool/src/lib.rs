@@ -21,6 +21,13 @@
 )]
 #![allow(clippy::uninlined_format_args)]
 
+#[cfg(any(feature = "managed", feature = "unmanaged"))]
+mod clock;
+#[cfg(all(feature = "serde", any(feature = "managed", feature = "unmanaged")))]
+mod config;
+#[cfg(all(feature = "env", any(feature = "managed", feature = "unmanaged")))]
+mod env;
+
 #[cfg(feature = "managed")]
 #[cfg_attr(docsrs, doc(cfg(feature = "managed")))]
 pub mod managed;
@@ -29,7 +36,15 @@ pub mod managed;
 #[cfg_attr(docsrs, doc(cfg(feature = "unmanaged")))]
 pub mod unmanaged;
 
-pub use deadpool_runtime::{Runtime, SpawnBlockingError};
+#[cfg(feature = "registry")]
+#[cfg_attr(docsrs, doc(cfg(feature = "registry")))]
+pub mod registry;
+
+#[cfg(feature = "test-utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-utils")))]
+pub mod test_utils;
+
+pub use deadpool_runtime::{BoxFuture, CustomRuntime, Runtime, SpawnBlockingError};
 
 /// The current pool status.
 ///
@@ -41,6 +56,7 @@ pub use deadpool_runtime::{Runtime, SpawnBlockingError};
 ///
 /// [1]: https://en.wikipedia.org/wiki/Eventual_consistency
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Status {
     /// The maximum size of the pool.
     pub max_size: usize,
@@ -53,4 +69,9 @@ pub struct Status {
 
     /// The number of futures waiting for an object.
     pub waiting: usize,
+
+    /// The number of objects which are currently being created. Unmanaged
+    /// pools only create objects if they have a factory, see
+    /// `unmanaged::Pool::with_factory()`.
+    pub creating: usize,
 }
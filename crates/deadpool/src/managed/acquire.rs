@@ -0,0 +1,64 @@
+//! Named future for retrieving an [`Object`] from a [`Pool`].
+
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use super::{Manager, Object, Pool, PoolError};
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Future returned by [`Pool::acquire()`].
+///
+/// It resolves to the same result as [`Pool::get()`] but can be named, e.g.
+/// for storing it in a struct or for implementing [`Future`] by hand.
+///
+/// # Cancel safety
+///
+/// Dropping an [`Acquire`] before it completes never leaks any capacity of
+/// the [`Pool`]:
+///
+/// - A task waiting for a free slot stops waiting. A slot which has been
+///   handed to it already is passed on to the next waiting task.
+/// - A slot which has been reserved but not yet filled with an [`Object`]
+///   is released again.
+/// - A call to [`Manager::create()`] or [`Manager::recycle()`] which is in
+///   flight is aborted by dropping its future. As the state of the affected
+///   [`Object`] is unknown, it is detached and destroyed. The same applies
+///   to an [`Object`] whose hooks didn't finish yet.
+///
+/// So it's safe to race an [`Acquire`] against other futures, e.g. in a
+/// `select!` or a timeout.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Acquire<'a, M: Manager, W: From<Object<M>> = Object<M>> {
+    future: BoxFuture<'a, Result<W, PoolError<M::Error>>>,
+}
+
+impl<'a, M, W> Acquire<'a, M, W>
+where
+    M: Manager,
+    W: From<Object<M>> + Send + 'a,
+{
+    pub(crate) fn new(pool: &'a Pool<M, W>) -> Self {
+        Self {
+            future: Box::pin(pool.get()),
+        }
+    }
+}
+
+impl<M: Manager, W: From<Object<M>>> Future for Acquire<'_, M, W> {
+    type Output = Result<W, PoolError<M::Error>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.future.as_mut().poll(cx)
+    }
+}
+
+impl<M: Manager, W: From<Object<M>>> fmt::Debug for Acquire<'_, M, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Acquire").finish_non_exhaustive()
+    }
+}
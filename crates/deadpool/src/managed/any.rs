@@ -0,0 +1,96 @@
+//! Type-erased access to pools of different [`Manager`]s.
+//!
+//! [`Manager`]: super::Manager
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::BoxFuture;
+use crate::Status;
+
+#[cfg(not(target_arch = "wasm32"))]
+use super::Health;
+use super::{Counters, Manager, Object, Pool};
+
+/// Object-safe subset of the [`Pool`] API which doesn't depend on the
+/// [`Manager`].
+///
+/// This is implemented by every [`Pool`] and allows keeping pools of
+/// different backends in a single registry, e.g. for admin endpoints:
+///
+/// ```rust,ignore
+/// let registry: Vec<DynPool> = vec![Box::new(pg_pool), Box::new(redis_pool)];
+/// for pool in &registry {
+///     println!("{:?}: {:?}", pool.name(), pool.status());
+/// }
+/// ```
+pub trait AnyPool: Send + Sync {
+    /// See [`Pool::name()`].
+    fn name(&self) -> Option<&str>;
+
+    /// See [`Pool::labels()`].
+    fn labels(&self) -> &[(String, String)];
+
+    /// See [`Pool::status()`].
+    fn status(&self) -> Status;
+
+    /// See [`Pool::counters()`].
+    fn counters(&self) -> Counters;
+
+    /// See [`Pool::resize()`].
+    fn resize(&self, max_size: usize);
+
+    /// See [`Pool::close()`].
+    fn close(&self);
+
+    /// See [`Pool::is_closed()`].
+    fn is_closed(&self) -> bool;
+
+    /// See [`Pool::health()`].
+    #[cfg(not(target_arch = "wasm32"))]
+    fn health(&self, timeout: Duration) -> BoxFuture<Health>;
+}
+
+/// Boxed [`AnyPool`] for keeping pools of different [`Manager`]s together.
+pub type DynPool = Box<dyn AnyPool>;
+
+impl<M, W> AnyPool for Pool<M, W>
+where
+    M: Manager + 'static,
+    W: From<Object<M>> + 'static,
+{
+    fn name(&self) -> Option<&str> {
+        Pool::name(self)
+    }
+
+    fn labels(&self) -> &[(String, String)] {
+        Pool::labels(self)
+    }
+
+    fn status(&self) -> Status {
+        Pool::status(self)
+    }
+
+    fn counters(&self) -> Counters {
+        Pool::counters(self)
+    }
+
+    fn resize(&self, max_size: usize) {
+        Pool::resize(self, max_size);
+    }
+
+    fn close(&self) {
+        Pool::close(self);
+    }
+
+    fn is_closed(&self) -> bool {
+        Pool::is_closed(self)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn health(&self, timeout: Duration) -> BoxFuture<Health> {
+        let pool = self.clone();
+        Box::pin(async move { pool.health(timeout).await })
+    }
+}
@@ -0,0 +1,115 @@
+//! Circuit breaker guarding [`Manager::create()`] calls.
+//!
+//! [`Manager::create()`]: super::Manager::create
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::{sync::Mutex, time::Instant};
+
+use super::CircuitBreakerConfig;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::clock;
+
+/// Circuit breaker configured via [`PoolConfig::circuit_breaker`].
+///
+/// The breaker is _closed_ as long as creating objects succeeds. After
+/// the configured number of consecutive failures it _opens_ and all
+/// attempts fail fast until the cool-down period has passed. Afterwards a
+/// single attempt is let through as probe. If it succeeds the breaker is
+/// closed again, otherwise it stays open for another cool-down period.
+///
+/// The breaker is disabled on `wasm32` targets.
+///
+/// [`PoolConfig::circuit_breaker`]: super::PoolConfig::circuit_breaker
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    config: Option<CircuitBreakerConfig>,
+    #[cfg(not(target_arch = "wasm32"))]
+    state: Mutex<State>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Default)]
+struct State {
+    failures: usize,
+    open_until: Option<Instant>,
+    probing: bool,
+}
+
+/// Permission to attempt creating an object. The outcome must be reported
+/// via [`Attempt::finish()`]. Dropping it without doing so doesn't change
+/// the state of the breaker.
+#[derive(Debug)]
+pub(crate) struct Attempt<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: bool,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: Option<CircuitBreakerConfig>) -> Self {
+        Self {
+            config,
+            #[cfg(not(target_arch = "wasm32"))]
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Returns `None` if the breaker is open.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn attempt(&self) -> Option<Attempt<'_>> {
+        let mut probe = false;
+        if self.config.is_some() {
+            let mut state = self.state.lock().unwrap();
+            if let Some(open_until) = state.open_until {
+                if state.probing || clock::now() < open_until {
+                    return None;
+                }
+                state.probing = true;
+                probe = true;
+            }
+        }
+        Some(Attempt {
+            breaker: self,
+            probe,
+        })
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn attempt(&self) -> Option<Attempt<'_>> {
+        Some(Attempt {
+            breaker: self,
+            probe: false,
+        })
+    }
+}
+
+impl Attempt<'_> {
+    /// Reports whether the attempt was successful.
+    pub(crate) fn finish(mut self, success: bool) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(config) = &self.breaker.config {
+            let mut state = self.breaker.state.lock().unwrap();
+            if success {
+                *state = State::default();
+            } else {
+                state.failures += 1;
+                state.probing = false;
+                if self.probe || state.failures >= config.failure_threshold {
+                    state.open_until = Some(clock::now() + config.cool_down);
+                }
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        let _ = success;
+        self.probe = false;
+    }
+}
+
+impl Drop for Attempt<'_> {
+    fn drop(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.probe {
+            // The probe has been cancelled. Let the next attempt probe.
+            self.breaker.state.lock().unwrap().probing = false;
+        }
+    }
+}
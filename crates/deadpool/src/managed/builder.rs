@@ -3,16 +3,22 @@ use std::{fmt, marker::PhantomData, time::Duration};
 use crate::Runtime;
 
 use super::{
-    hooks::{Hook, Hooks},
-    Manager, Object, Pool, PoolConfig, QueueMode, Timeouts,
+    hooks::{AcquireHook, Hook, HookResult, Hooks},
+    AutoscaleConfig, CircuitBreakerConfig, DetachMode, InvalidConfigError, Manager, Metrics,
+    Object, Pool, PoolConfig, PoolEvent, QueueMode, RecycleError, RecycleTimeoutMode, RetryConfig,
+    TimeoutError, Timeouts, DEFAULT_HISTOGRAM_BUCKETS, DEFAULT_STATS_WINDOW,
 };
 
 /// Possible errors returned when [`PoolBuilder::build()`] fails to build a
 /// [`Pool`].
 #[derive(Copy, Clone, Debug)]
 pub enum BuildError {
-    /// [`Runtime`] is required du to configured timeouts.
+    /// [`Runtime`] is required due to configured timeouts or background
+    /// tasks.
     NoRuntimeSpecified,
+
+    /// The [`PoolConfig`] is invalid. See [`PoolConfig::validate()`].
+    InvalidConfig(InvalidConfigError),
 }
 
 impl fmt::Display for BuildError {
@@ -20,8 +26,11 @@ impl fmt::Display for BuildError {
         match self {
             Self::NoRuntimeSpecified => write!(
                 f,
-                "Error occurred while building the pool: Timeouts require a runtime",
+                "Error occurred while building the pool: Timeouts and background tasks require a runtime",
             ),
+            Self::InvalidConfig(e) => {
+                write!(f, "Error occurred while building the pool: {}", e)
+            }
         }
     }
 }
@@ -30,10 +39,15 @@ impl std::error::Error for BuildError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::NoRuntimeSpecified => None,
+            Self::InvalidConfig(e) => Some(e),
         }
     }
 }
 
+/// Function signature for generating object ids. See
+/// [`PoolBuilder::id_generator()`].
+pub(crate) type IdGeneratorFn = dyn Fn() -> usize + Sync + Send;
+
 /// Builder for [`Pool`]s.
 ///
 /// Instances of this are created by calling the [`Pool::builder()`] method.
@@ -47,6 +61,14 @@ where
     pub(crate) config: PoolConfig,
     pub(crate) runtime: Option<Runtime>,
     pub(crate) hooks: Hooks<M>,
+    pub(crate) warm_up_on_create: bool,
+    pub(crate) histogram_buckets: Vec<Duration>,
+    pub(crate) stats_window: Duration,
+    pub(crate) id_generator: Option<Box<IdGeneratorFn>>,
+    pub(crate) name: Option<String>,
+    pub(crate) labels: Vec<(String, String)>,
+    #[cfg(feature = "registry")]
+    pub(crate) registry_name: Option<String>,
     _wrapper: PhantomData<fn() -> W>,
 }
 
@@ -57,13 +79,21 @@ where
     W: From<Object<M>>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("PoolBuilder")
+        let mut f = f.debug_struct("PoolBuilder");
+        let _ = f
             .field("manager", &self.manager)
             .field("config", &self.config)
             .field("runtime", &self.runtime)
             .field("hooks", &self.hooks)
-            .field("_wrapper", &self._wrapper)
-            .finish()
+            .field("warm_up_on_create", &self.warm_up_on_create)
+            .field("histogram_buckets", &self.histogram_buckets)
+            .field("stats_window", &self.stats_window)
+            .field("id_generator", &self.id_generator.is_some())
+            .field("name", &self.name)
+            .field("labels", &self.labels);
+        #[cfg(feature = "registry")]
+        let _ = f.field("registry_name", &self.registry_name);
+        f.field("_wrapper", &self._wrapper).finish()
     }
 }
 
@@ -78,6 +108,14 @@ where
             config: PoolConfig::default(),
             runtime: None,
             hooks: Hooks::default(),
+            warm_up_on_create: false,
+            histogram_buckets: DEFAULT_HISTOGRAM_BUCKETS.to_vec(),
+            stats_window: DEFAULT_STATS_WINDOW,
+            id_generator: None,
+            name: None,
+            labels: Vec::new(),
+            #[cfg(feature = "registry")]
+            registry_name: None,
             _wrapper: PhantomData,
         }
     }
@@ -87,14 +125,36 @@ where
     /// # Errors
     ///
     /// See [`BuildError`] for details.
-    pub fn build(self) -> Result<Pool<M, W>, BuildError> {
-        // Return an error if a timeout is configured without runtime.
-        let t = &self.config.timeouts;
-        if (t.wait.is_some() || t.create.is_some() || t.recycle.is_some()) && self.runtime.is_none()
-        {
+    pub fn build(mut self) -> Result<Pool<M, W>, BuildError>
+    where
+        M: 'static,
+    {
+        self.config.validate().map_err(BuildError::InvalidConfig)?;
+        if self.runtime.is_none() {
+            self.runtime = Runtime::detect();
+        }
+        // Return an error if a timeout or background task is configured
+        // without runtime.
+        if (self.config.requires_runtime() || self.warm_up_on_create) && self.runtime.is_none() {
             return Err(BuildError::NoRuntimeSpecified);
         }
-        Ok(Pool::from_builder(self))
+        #[cfg(feature = "registry")]
+        let registry_name = self.registry_name.take();
+        let pool = Pool::from_builder(self);
+        #[cfg(feature = "registry")]
+        if let Some(name) = registry_name {
+            crate::registry::register(name, &pool);
+        }
+        Ok(pool)
+    }
+
+    /// Returns the [`Manager`] the [`Pool`] is going to be built with.
+    ///
+    /// Once the [`Pool`] has been built the [`Manager`] can be accessed via
+    /// [`Pool::manager()`].
+    #[must_use]
+    pub fn manager(&self) -> &M {
+        &self.manager
     }
 
     /// Sets a [`PoolConfig`] to build the [`Pool`] with.
@@ -133,12 +193,205 @@ where
         self
     }
 
+    /// Sets the [`PoolConfig::recycle_timeout_mode`].
+    pub fn recycle_timeout_mode(mut self, value: RecycleTimeoutMode) -> Self {
+        self.config.recycle_timeout_mode = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::detach_mode`].
+    pub fn detach_mode(mut self, value: DetachMode) -> Self {
+        self.config.detach_mode = value;
+        self
+    }
+
     /// Sets the [`PoolConfig::queue_mode`].
     pub fn queue_mode(mut self, value: QueueMode) -> Self {
         self.config.queue_mode = value;
         self
     }
 
+    /// Sets the [`PoolConfig::max_lifetime`].
+    pub fn max_lifetime(mut self, value: Option<Duration>) -> Self {
+        self.config.max_lifetime = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::idle_timeout`].
+    pub fn idle_timeout(mut self, value: Option<Duration>) -> Self {
+        self.config.idle_timeout = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::max_uses`].
+    pub fn max_uses(mut self, value: Option<usize>) -> Self {
+        self.config.max_uses = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::min_idle`].
+    pub fn min_idle(mut self, value: usize) -> Self {
+        self.config.min_idle = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::maintenance_interval`].
+    pub fn maintenance_interval(mut self, value: Option<Duration>) -> Self {
+        self.config.maintenance_interval = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::health_check_interval`].
+    pub fn health_check_interval(mut self, value: Option<Duration>) -> Self {
+        self.config.health_check_interval = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::max_waiters`].
+    pub fn max_waiters(mut self, value: Option<usize>) -> Self {
+        self.config.max_waiters = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::max_overflow`].
+    pub fn max_overflow(mut self, value: usize) -> Self {
+        self.config.max_overflow = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::autoscale`].
+    pub fn autoscale(mut self, value: Option<AutoscaleConfig>) -> Self {
+        self.config.autoscale = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::shards`].
+    pub fn shards(mut self, value: usize) -> Self {
+        self.config.shards = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::create_retry`].
+    pub fn create_retry(mut self, value: Option<RetryConfig>) -> Self {
+        self.config.create_retry = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::circuit_breaker`].
+    pub fn circuit_breaker(mut self, value: Option<CircuitBreakerConfig>) -> Self {
+        self.config.circuit_breaker = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::recycle_on_return`].
+    pub fn recycle_on_return(mut self, value: bool) -> Self {
+        self.config.recycle_on_return = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::recycle_ahead`].
+    pub fn recycle_ahead(mut self, value: usize) -> Self {
+        self.config.recycle_ahead = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::max_holders`].
+    pub fn max_holders(mut self, value: usize) -> Self {
+        self.config.max_holders = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::lease_timeout`].
+    pub fn lease_timeout(mut self, value: Option<Duration>) -> Self {
+        self.config.lease_timeout = value;
+        self
+    }
+
+    /// Warms up the [`Pool`] right after it has been built.
+    ///
+    /// If enabled, [`PoolBuilder::build()`] spawns a task using the
+    /// configured [`Runtime`] which creates [`PoolConfig::max_size`]
+    /// [`Object`]s in the background. Use [`Pool::warm_up()`] for waiting
+    /// until a specific number of [`Object`]s has been created.
+    pub fn warm_up_on_create(mut self, value: bool) -> Self {
+        self.warm_up_on_create = value;
+        self
+    }
+
+    /// Sets the name of the [`Pool`].
+    ///
+    /// The name is used to tell multiple [`Pool`]s apart in diagnostics,
+    /// e.g. it is added to all spans and events if the `tracing` feature is
+    /// enabled and used as `pool` label of all metrics if the `metrics`
+    /// feature is enabled.
+    pub fn name(mut self, value: impl Into<String>) -> Self {
+        self.name = Some(value.into());
+        self
+    }
+
+    /// Adds a key/value label to the [`Pool`].
+    ///
+    /// Labels are added to the spans and events of the `tracing` feature
+    /// and to the metrics of the `metrics`, `prometheus` and `opentelemetry`
+    /// features next to the [name](PoolBuilder::name).
+    pub fn label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.push((key.into(), value.into()));
+        self
+    }
+
+    /// Registers the [`Pool`] under the given name in the global
+    /// [`registry`](crate::registry) once it has been built.
+    ///
+    /// The registry name is independent of [`PoolBuilder::name()`].
+    #[cfg(feature = "registry")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "registry")))]
+    pub fn register(mut self, name: impl Into<String>) -> Self {
+        self.registry_name = Some(name.into());
+        self
+    }
+
+    /// Sets the inclusive upper bounds of the buckets of the [`Histogram`]s
+    /// returned by [`Pool::stats()`]. A last bucket without upper bound is
+    /// always added.
+    ///
+    /// Default: [`DEFAULT_HISTOGRAM_BUCKETS`]
+    ///
+    /// [`Histogram`]: super::Histogram
+    pub fn histogram_buckets(mut self, value: impl Into<Vec<Duration>>) -> Self {
+        self.histogram_buckets = value.into();
+        self
+    }
+
+    /// Sets the function generating the ids returned by [`Object::id()`].
+    ///
+    /// By default every [`Pool`] counts up from `0`. A custom generator
+    /// allows correlating ids with external systems, e.g. by embedding a
+    /// node id, and keeping them unique across restarts of the [`Pool`].
+    /// The generated ids must be unique within the [`Pool`].
+    ///
+    /// ```rust,ignore
+    /// let next = AtomicUsize::new(0);
+    /// let pool = Pool::builder(manager)
+    ///     .id_generator(move || NODE_ID << 48 | next.fetch_add(1, Ordering::Relaxed))
+    ///     .build()?;
+    /// ```
+    pub fn id_generator(mut self, f: impl Fn() -> usize + Sync + Send + 'static) -> Self {
+        self.id_generator = Some(Box::new(f));
+        self
+    }
+
+    /// Sets the duration of the rolling window the [`Rates`] returned by
+    /// [`Pool::stats()`] are computed over. It is rounded up to whole
+    /// seconds.
+    ///
+    /// Default: [`DEFAULT_STATS_WINDOW`]
+    ///
+    /// [`Rates`]: super::Rates
+    pub fn stats_window(mut self, value: Duration) -> Self {
+        self.stats_window = value;
+        self
+    }
+
     /// Attaches a `post_create` hook.
     ///
     /// The given `hook` will be called each time right after a new [`Object`]
@@ -160,12 +413,119 @@ where
     /// Attaches a `post_recycle` hook.
     ///
     /// The given `hook` will be called each time right after an [`Object`] has
-    /// been recycled.
+    /// been recycled. It gets mutable access to the object and is therefore
+    /// a good place for resetting state like caches or session settings.
     pub fn post_recycle(mut self, hook: impl Into<Hook<M>>) -> Self {
         self.hooks.post_recycle.push(hook.into());
         self
     }
 
+    /// Attaches an `on_acquire` hook.
+    ///
+    /// The given `hook` will be called each time right before an [`Object`]
+    /// is handed out, after it has been created or recycled. It gets mutable
+    /// access to the object and its [`Metrics`]. If the `hook`
+    /// fails the [`Object`] is returned to the [`Pool`] and
+    /// [`PoolError::OnAcquireHook`] is returned. If it returns
+    /// [`HookError::Discard`] the [`Object`] is discarded and another one
+    /// is handed out instead.
+    ///
+    /// [`HookError::Discard`]: super::HookError::Discard
+    /// [`PoolError::OnAcquireHook`]: super::PoolError::OnAcquireHook
+    pub fn on_acquire(mut self, hook: impl Into<AcquireHook<M>>) -> Self {
+        self.hooks.on_acquire.push(hook.into());
+        self
+    }
+
+    /// Attaches a `pre_return` hook.
+    ///
+    /// The given `hook` will be called each time an [`Object`] is about to
+    /// be returned to the [`Pool`]. If it fails the [`Object`] is discarded
+    /// instead, e.g. because a connection is still in the middle of a
+    /// transaction. As [`Object`]s are returned when being dropped, only
+    /// sync functions are supported.
+    pub fn pre_return(
+        mut self,
+        hook: impl Fn(&mut M::Type, &Metrics) -> HookResult<M::Error> + Sync + Send + 'static,
+    ) -> Self {
+        self.hooks.pre_return.push(Box::new(hook));
+        self
+    }
+
+    /// Attaches an `on_detach` hook.
+    ///
+    /// The given `hook` will be called each time an [`Object`] is
+    /// permanently removed from the [`Pool`] via [`Object::take()`]. As this
+    /// happens synchronously only sync functions are supported.
+    pub fn on_detach(
+        mut self,
+        hook: impl Fn(&mut M::Type, &Metrics) + Sync + Send + 'static,
+    ) -> Self {
+        self.hooks.on_detach.push(Box::new(hook));
+        self
+    }
+
+    /// Attaches an `on_create_error` hook.
+    ///
+    /// The given `hook` will be called each time [`Manager::create()`]
+    /// fails. This is useful for logging and alerting about failing
+    /// connections without wrapping the [`Manager`].
+    pub fn on_create_error(mut self, hook: impl Fn(&M::Error) + Sync + Send + 'static) -> Self {
+        self.hooks.on_create_error.push(Box::new(hook));
+        self
+    }
+
+    /// Attaches a callback which is called for every [`PoolEvent`].
+    ///
+    /// This is useful for audit logging and custom metrics. The `callback`
+    /// is called synchronously and must not block.
+    pub fn on_event(mut self, callback: impl Fn(&PoolEvent) + Sync + Send + 'static) -> Self {
+        self.hooks.on_event.push(Box::new(callback));
+        self
+    }
+
+    /// Attaches a callback which is called whenever retrieving an
+    /// [`Object`] times out.
+    ///
+    /// The `callback` receives the same [`TimeoutError`] which is returned
+    /// to the caller via [`PoolError::Timeout`]. This allows alerting and
+    /// triggering diagnostics in a central place. Timeouts of
+    /// [`Pool::close_graceful()`] aren't reported. The `callback` is called
+    /// synchronously and must not block.
+    ///
+    /// [`PoolError::Timeout`]: super::PoolError::Timeout
+    pub fn on_timeout(mut self, callback: impl Fn(&TimeoutError) + Sync + Send + 'static) -> Self {
+        self.hooks.on_timeout.push(Box::new(callback));
+        self
+    }
+
+    /// Attaches an `on_recycle_error` hook.
+    ///
+    /// The given `hook` will be called each time [`Manager::recycle()`]
+    /// fails while retrieving an [`Object`]. The [`Object`] is discarded
+    /// afterwards.
+    pub fn on_recycle_error(
+        mut self,
+        hook: impl Fn(&RecycleError<M::Error>, &Metrics) + Sync + Send + 'static,
+    ) -> Self {
+        self.hooks.on_recycle_error.push(Box::new(hook));
+        self
+    }
+
+    /// Sets the function describing errors of the [`Manager`] in the
+    /// [`LastError`] returned by [`Pool::last_error()`].
+    ///
+    /// Without it errors of the backend are reported without a message.
+    ///
+    /// [`LastError`]: super::LastError
+    pub fn describe_error(
+        mut self,
+        f: impl Fn(&M::Error) -> String + Sync + Send + 'static,
+    ) -> Self {
+        self.hooks.describe_error = Some(Box::new(f));
+        self
+    }
+
     /// Sets the [`Runtime`].
     ///
     /// # Important
@@ -175,9 +535,14 @@ where
     /// specified as you will otherwise get a [`PoolError::NoRuntimeSpecified`]
     /// when trying to use [`Pool::timeout_get()`].
     ///
+    /// If no [`Runtime`] is set [`PoolBuilder::build()`] uses
+    /// [`Runtime::detect()`] which picks up the Tokio runtime it is called
+    /// from if the `rt_tokio_1` feature is enabled. That runtime must have
+    /// its time driver enabled for timeouts to work.
+    ///
     /// [`PoolBuilder::build()`] will fail with a
     /// [`BuildError::NoRuntimeSpecified`] if you try to build a
-    /// [`Pool`] with timeouts and no [`Runtime`] specified.
+    /// [`Pool`] with timeouts and no [`Runtime`] specified or detected.
     ///
     /// [`PoolError::NoRuntimeSpecified`]: super::PoolError::NoRuntimeSpecified
     pub fn runtime(mut self, value: Runtime) -> Self {
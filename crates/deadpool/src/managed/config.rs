@@ -1,6 +1,8 @@
-use std::{fmt, time::Duration};
+use std::{collections::hash_map::RandomState, fmt, hash::BuildHasher, time::Duration};
 
 use super::BuildError;
+#[cfg(feature = "serde")]
+use crate::config::deserialize_duration;
 
 /// [`Pool`] configuration.
 ///
@@ -32,6 +34,315 @@ pub struct PoolConfig {
     /// [`Pool`]: super::Pool
     #[cfg_attr(feature = "serde", serde(default))]
     pub queue_mode: QueueMode,
+
+    /// Maximum lifetime of the [`Object`]s in the [`Pool`].
+    ///
+    /// [`Object`]s older than this are discarded instead of being recycled
+    /// or returned to the [`Pool`] regardless of their health.
+    ///
+    /// Default: No maximum lifetime
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "deserialize_duration")
+    )]
+    pub max_lifetime: Option<Duration>,
+
+    /// Maximum time an [`Object`] may stay idle in the [`Pool`].
+    ///
+    /// Idle [`Object`]s which haven't been used for longer than this (see
+    /// [`Metrics::last_used()`]) are discarded instead of being handed out
+    /// again. The background maintenance task removes them as well, keeping
+    /// at least [`PoolConfig::min_idle`] of them. Without a
+    /// [`PoolConfig::maintenance_interval`] this task runs every
+    /// `idle_timeout`, which requires a [`Runtime`].
+    ///
+    /// Default: No idle timeout
+    ///
+    /// [`Metrics::last_used()`]: super::Metrics::last_used
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    /// [`Runtime`]: crate::Runtime
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "deserialize_duration")
+    )]
+    pub idle_timeout: Option<Duration>,
+
+    /// Maximum number of times an [`Object`] is handed out by the [`Pool`].
+    ///
+    /// [`Object`]s which reached this number of uses (see
+    /// [`Metrics::use_count`]) are discarded when being returned.
+    ///
+    /// Default: No limit
+    ///
+    /// [`Metrics::use_count`]: super::Metrics::use_count
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_uses: Option<usize>,
+
+    /// Minimum number of idle [`Object`]s kept in the [`Pool`].
+    ///
+    /// The background maintenance task creates new [`Object`]s whenever
+    /// the number of idle [`Object`]s drops below this value. This
+    /// requires the [`PoolConfig::maintenance_interval`] or the
+    /// [`PoolConfig::idle_timeout`] to be set.
+    ///
+    /// Default: `0`
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min_idle: usize,
+
+    /// Interval of the background maintenance task.
+    ///
+    /// If set, a task is spawned using the configured [`Runtime`] which
+    /// periodically removes idle [`Object`]s exceeding the
+    /// [`PoolConfig::max_lifetime`], [`PoolConfig::idle_timeout`] and
+    /// [`PoolConfig::max_uses`] limits and creates new ones until the
+    /// [`PoolConfig::min_idle`] is reached. The task stops as soon as the
+    /// [`Pool`] is closed or dropped.
+    ///
+    /// Default: No background maintenance
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    /// [`Runtime`]: crate::Runtime
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "deserialize_duration")
+    )]
+    pub maintenance_interval: Option<Duration>,
+
+    /// Interval of the background health check.
+    ///
+    /// If set, a task is spawned using the configured [`Runtime`] which
+    /// periodically runs [`Manager::recycle()`] on all idle [`Object`]s and
+    /// discards the broken ones. Idle [`Object`]s are only checked while
+    /// there are free slots in the [`Pool`] so the health check never
+    /// delays callers of [`Pool::get()`]. The task stops as soon as the
+    /// [`Pool`] is closed or dropped.
+    ///
+    /// Default: No background health check
+    ///
+    /// [`Manager::recycle()`]: super::Manager::recycle
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    /// [`Pool::get()`]: super::Pool::get
+    /// [`Runtime`]: crate::Runtime
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "deserialize_duration")
+    )]
+    pub health_check_interval: Option<Duration>,
+
+    /// Maximum number of tasks waiting for an [`Object`].
+    ///
+    /// If this many tasks are waiting already [`Pool::get()`] fails right
+    /// away with a [`PoolError::QueueFull`] instead of waiting, too. This
+    /// allows shedding load instead of queueing up ever more tasks when the
+    /// [`Pool`] is overloaded.
+    ///
+    /// Default: No limit
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    /// [`Pool::get()`]: super::Pool::get
+    /// [`PoolError::QueueFull`]: super::PoolError::QueueFull
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_waiters: Option<usize>,
+
+    /// Maximum number of [`Object`]s created on top of the
+    /// [`PoolConfig::max_size`] during spikes.
+    ///
+    /// If no slot is available [`Pool::get()`] creates an overflow
+    /// [`Object`] right away instead of waiting as long as fewer than this
+    /// many overflow [`Object`]s are in use. No overflow [`Object`] is
+    /// created while other tasks are waiting for a slot so they can't be
+    /// overtaken by tasks arriving later. Overflow [`Object`]s are never
+    /// put back into the [`Pool`] but dropped as soon as they are returned.
+    /// They aren't included in the [`Status::size`] of the [`Pool`].
+    ///
+    /// Default: `0`
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    /// [`Pool::get()`]: super::Pool::get
+    /// [`Status::size`]: crate::Status::size
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_overflow: usize,
+
+    /// Configuration of the autoscaler adjusting the `max_size` of the
+    /// [`Pool`] to the current load.
+    ///
+    /// If set, a task is spawned using the configured [`Runtime`] which
+    /// periodically resizes the [`Pool`]. See [`AutoscaleConfig`] for
+    /// details.
+    ///
+    /// Default: No autoscaling
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`Runtime`]: crate::Runtime
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub autoscale: Option<AutoscaleConfig>,
+
+    /// Number of shards the idle [`Object`]s are split into.
+    ///
+    /// With [`QueueMode::Fifo`] the shards are lock-free queues. With
+    /// [`QueueMode::Lifo`] every shard is protected by its own lock and
+    /// using more shards reduces contention when many tasks use the
+    /// [`Pool`] concurrently. The counters of the tasks using the [`Pool`]
+    /// and creating [`Object`]s are split into the same number of shards
+    /// and summed up by [`Pool::status()`](super::Pool::status). The
+    /// [`PoolConfig::queue_mode`] is applied per shard, so with more than
+    /// one shard the order in which [`Object`]s are handed out is only
+    /// approximately FIFO or LIFO.
+    ///
+    /// Default: `1`
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    #[cfg_attr(feature = "serde", serde(default = "default_shards"))]
+    pub shards: usize,
+
+    /// Policy for retrying [`Manager::create()`] after it failed.
+    ///
+    /// If set, [`Pool::get()`] retries creating an [`Object`] with an
+    /// exponentially growing delay before returning a
+    /// [`PoolError::Backend`]. The [`Timeouts::create`] applies to all
+    /// attempts combined. This requires a [`Runtime`] to be configured.
+    ///
+    /// Default: No retries
+    ///
+    /// [`Manager::create()`]: super::Manager::create
+    /// [`Object`]: super::Object
+    /// [`Pool::get()`]: super::Pool::get
+    /// [`PoolError::Backend`]: super::PoolError::Backend
+    /// [`Runtime`]: crate::Runtime
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub create_retry: Option<RetryConfig>,
+
+    /// Configuration of the circuit breaker guarding
+    /// [`Manager::create()`].
+    ///
+    /// If set, the [`Pool`] stops calling [`Manager::create()`] after a
+    /// number of consecutive failures and returns
+    /// [`PoolError::CircuitOpen`] right away until a cool-down period has
+    /// passed. See [`CircuitBreakerConfig`] for details.
+    ///
+    /// Default: No circuit breaker
+    ///
+    /// [`Manager::create()`]: super::Manager::create
+    /// [`Pool`]: super::Pool
+    /// [`PoolError::CircuitOpen`]: super::PoolError::CircuitOpen
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+
+    /// Recycles [`Object`]s when they are returned to the [`Pool`] instead
+    /// of when they are handed out again.
+    ///
+    /// If enabled, [`Manager::recycle()`] and the recycle hooks are run by
+    /// a task spawned using the configured [`Runtime`] once an [`Object`]
+    /// is returned. [`Pool::get()`] then hands out idle [`Object`]s without
+    /// recycling them, taking the recycling off the critical path. The
+    /// [`Object`] only becomes available again after it has been recycled.
+    ///
+    /// Keep in mind that an [`Object`] might become unusable while being
+    /// idle. This isn't noticed before it is used.
+    ///
+    /// Default: `false`
+    ///
+    /// [`Manager::recycle()`]: super::Manager::recycle
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    /// [`Pool::get()`]: super::Pool::get
+    /// [`Runtime`]: crate::Runtime
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub recycle_on_return: bool,
+
+    /// Number of idle [`Object`]s which are recycled ahead of time.
+    ///
+    /// If set, a task spawned using the configured [`Runtime`] keeps up to
+    /// this many idle [`Object`]s recycled in a buffer. [`Pool::get()`]
+    /// hands out [`Object`]s from that buffer without recycling them and
+    /// the task refills the buffer in the background. This is useful for
+    /// backends with expensive health checks as it separates the time
+    /// spent recycling [`Object`]s from the latency of [`Pool::get()`].
+    ///
+    /// Just like with [`PoolConfig::recycle_on_return`] an [`Object`]
+    /// might become unusable while waiting in the buffer.
+    ///
+    /// Default: `0` (disabled)
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool::get()`]: super::Pool::get
+    /// [`Runtime`]: crate::Runtime
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub recycle_ahead: usize,
+
+    /// Maximum number of holders sharing an [`Object`] handed out by
+    /// [`Pool::get_shared()`].
+    ///
+    /// Default: `1`
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool::get_shared()`]: super::Pool::get_shared
+    #[cfg_attr(feature = "serde", serde(default = "default_max_holders"))]
+    pub max_holders: usize,
+
+    /// Maximum duration an [`Object`] is expected to be held.
+    ///
+    /// If set, a task spawned using the configured [`Runtime`] periodically
+    /// checks for [`Object`]s which have been held longer than this. Every
+    /// such [`Object`] is reported once via a [`PoolEvent::LeaseExceeded`]
+    /// event and counted in [`Counters::leases_exceeded`]. This helps
+    /// spotting [`Object`]s which are leaked or held for too long. The
+    /// [`Object`]s are not taken away from their holders.
+    ///
+    /// Default: No lease timeout
+    ///
+    /// [`Counters::leases_exceeded`]: super::Counters::leases_exceeded
+    /// [`Object`]: super::Object
+    /// [`PoolEvent::LeaseExceeded`]: super::PoolEvent::LeaseExceeded
+    /// [`Runtime`]: crate::Runtime
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "deserialize_duration")
+    )]
+    pub lease_timeout: Option<Duration>,
+
+    /// Determines what happens to an [`Object`] whose recycling exceeded
+    /// the [`Timeouts::recycle`].
+    ///
+    /// Default: [`RecycleTimeoutMode::Discard`]
+    ///
+    /// [`Object`]: super::Object
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub recycle_timeout_mode: RecycleTimeoutMode,
+
+    /// Determines what happens to the slot of an [`Object`] which is
+    /// detached via [`Object::take()`].
+    ///
+    /// Default: [`DetachMode::Release`]
+    ///
+    /// [`Object`]: super::Object
+    /// [`Object::take()`]: super::Object::take
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub detach_mode: DetachMode,
+}
+
+#[cfg(feature = "serde")]
+fn default_shards() -> usize {
+    1
+}
+
+#[cfg(feature = "serde")]
+fn default_max_holders() -> usize {
+    1
 }
 
 impl PoolConfig {
@@ -43,8 +354,87 @@ impl PoolConfig {
             max_size,
             timeouts: Timeouts::default(),
             queue_mode: QueueMode::default(),
+            max_lifetime: None,
+            idle_timeout: None,
+            max_uses: None,
+            min_idle: 0,
+            maintenance_interval: None,
+            health_check_interval: None,
+            max_waiters: None,
+            max_overflow: 0,
+            autoscale: None,
+            shards: 1,
+            create_retry: None,
+            circuit_breaker: None,
+            recycle_on_return: false,
+            recycle_ahead: 0,
+            max_holders: 1,
+            lease_timeout: None,
+            recycle_timeout_mode: RecycleTimeoutMode::default(),
+            detach_mode: DetachMode::default(),
         }
     }
+
+    /// Checks this configuration for settings which would render the
+    /// [`Pool`] unusable, e.g. a pool which can never hand out an object.
+    ///
+    /// This is called by [`PoolBuilder::build()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`InvalidConfigError`] for details.
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`PoolBuilder::build()`]: super::PoolBuilder::build
+    pub fn validate(&self) -> Result<(), InvalidConfigError> {
+        if self.max_size == 0 {
+            return Err(InvalidConfigError::ZeroMaxSize);
+        }
+        if self.timeouts.wait == Some(Duration::ZERO) {
+            return Err(InvalidConfigError::ZeroWaitTimeout);
+        }
+        if self.min_idle > self.max_size {
+            return Err(InvalidConfigError::MinIdleExceedsMaxSize {
+                min_idle: self.min_idle,
+                max_size: self.max_size,
+            });
+        }
+        if let Some(retry) = &self.create_retry {
+            if retry.max_attempts == 0 {
+                return Err(InvalidConfigError::ZeroRetryAttempts);
+            }
+            if retry.base_delay > retry.max_delay {
+                return Err(InvalidConfigError::RetryBaseDelayExceedsMaxDelay);
+            }
+        }
+        Ok(())
+    }
+
+    /// Indicates whether this configuration contains timeouts or background
+    /// tasks which require a [`Runtime`](crate::Runtime).
+    pub(crate) fn requires_runtime(&self) -> bool {
+        let t = &self.timeouts;
+        t.wait.is_some()
+            || t.create.is_some()
+            || t.recycle.is_some()
+            || self.sweep_interval().is_some()
+            || self.health_check_interval.is_some()
+            || self.autoscale.is_some()
+            || self.create_retry.is_some()
+            || self.recycle_on_return
+            || self.recycle_ahead > 0
+            || self.lease_timeout.is_some()
+    }
+
+    /// Returns the interval of the background maintenance task. Without a
+    /// [`PoolConfig::maintenance_interval`] the task still runs every
+    /// [`PoolConfig::idle_timeout`] so idle [`Object`]s are removed
+    /// regardless of the [`QueueMode`].
+    ///
+    /// [`Object`]: super::Object
+    pub(crate) fn sweep_interval(&self) -> Option<Duration> {
+        self.maintenance_interval.or(self.idle_timeout)
+    }
 }
 
 impl Default for PoolConfig {
@@ -57,18 +447,35 @@ impl Default for PoolConfig {
 
 /// Timeouts when getting [`Object`]s from a [`Pool`].
 ///
+/// With the `serde` feature enabled the timeouts can be deserialized from
+/// human readable strings like `"500ms"` or `"30s"` in addition to the
+/// `{ secs, nanos }` struct form. The same goes for the other optional
+/// durations of the [`PoolConfig`].
+///
 /// [`Object`]: super::Object
 /// [`Pool`]: super::Pool
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Timeouts {
     /// Timeout when waiting for a slot to become available.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "deserialize_duration")
+    )]
     pub wait: Option<Duration>,
 
     /// Timeout when creating a new object.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "deserialize_duration")
+    )]
     pub create: Option<Duration>,
 
     /// Timeout when recycling an object.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "deserialize_duration")
+    )]
     pub recycle: Option<Duration>,
 }
 
@@ -103,6 +510,196 @@ impl Default for Timeouts {
     }
 }
 
+/// Configuration of the autoscaler of a [`Pool`].
+///
+/// Every `interval` the autoscaler samples the [`Pool`] and adjusts its
+/// `max_size` within the configured bounds:
+///
+/// - If tasks had to wait for an [`Object`] since the last sample and the
+///   `wait_quantile` of the [`Stats::wait_time`] recorded since then
+///   exceeds the `target_wait` the `max_size` is increased by the number of
+///   those tasks.
+/// - Otherwise, if no task had to wait and less than `shrink_utilization`
+///   of the `max_size` is in use, the `max_size` is decreased by one.
+///
+/// [`Object`]: super::Object
+/// [`Pool`]: super::Pool
+/// [`Stats::wait_time`]: super::Stats::wait_time
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct AutoscaleConfig {
+    /// Lower bound of the `max_size` of the [`Pool`].
+    ///
+    /// [`Pool`]: super::Pool
+    pub min_size: usize,
+
+    /// Upper bound of the `max_size` of the [`Pool`].
+    ///
+    /// [`Pool`]: super::Pool
+    pub max_size: usize,
+
+    /// Interval between two samples.
+    pub interval: Duration,
+
+    /// Wait time the autoscaler aims for.
+    ///
+    /// If set, waiting tasks only make the autoscaler grow the [`Pool`] if
+    /// the `wait_quantile` of the wait times recorded since the last sample
+    /// exceeds this duration. Otherwise every waiting task does.
+    ///
+    /// Default: `None`
+    ///
+    /// [`Pool`]: super::Pool
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "deserialize_duration")
+    )]
+    pub target_wait: Option<Duration>,
+
+    /// Quantile of the wait times which is compared to the `target_wait`.
+    ///
+    /// Default: `0.99`
+    #[cfg_attr(feature = "serde", serde(default = "default_wait_quantile"))]
+    pub wait_quantile: f64,
+
+    /// Fraction of the `max_size` which needs to be in use to keep the
+    /// autoscaler from shrinking the [`Pool`].
+    ///
+    /// Default: `0.5`
+    ///
+    /// [`Pool`]: super::Pool
+    #[cfg_attr(feature = "serde", serde(default = "default_shrink_utilization"))]
+    pub shrink_utilization: f64,
+}
+
+#[cfg(feature = "serde")]
+fn default_wait_quantile() -> f64 {
+    0.99
+}
+
+#[cfg(feature = "serde")]
+fn default_shrink_utilization() -> f64 {
+    0.5
+}
+
+impl AutoscaleConfig {
+    /// Creates a new [`AutoscaleConfig`] scaling the [`Pool`] between
+    /// `min_size` and `max_size`.
+    ///
+    /// [`Pool`]: super::Pool
+    #[must_use]
+    pub const fn new(min_size: usize, max_size: usize, interval: Duration) -> Self {
+        Self {
+            min_size,
+            max_size,
+            interval,
+            target_wait: None,
+            wait_quantile: 0.99,
+            shrink_utilization: 0.5,
+        }
+    }
+}
+
+/// Policy for retrying a failed operation with exponential backoff.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RetryConfig {
+    /// Maximum number of attempts including the first one.
+    pub max_attempts: usize,
+
+    /// Delay before the first retry. The delay is doubled for every further
+    /// retry.
+    pub base_delay: Duration,
+
+    /// Upper bound of the delay between two attempts.
+    ///
+    /// Default: 30 seconds
+    #[cfg_attr(feature = "serde", serde(default = "default_max_delay"))]
+    pub max_delay: Duration,
+
+    /// Randomizes every delay to a value between zero and the computed
+    /// delay. This keeps many tasks from retrying at the same time.
+    pub jitter: bool,
+}
+
+#[cfg(feature = "serde")]
+fn default_max_delay() -> Duration {
+    RetryConfig::DEFAULT_MAX_DELAY
+}
+
+impl RetryConfig {
+    const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+    /// Creates a new [`RetryConfig`] with jitter being enabled and a
+    /// `max_delay` of 30 seconds.
+    #[must_use]
+    pub const fn new(max_attempts: usize, base_delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            base_delay,
+            max_delay: Self::DEFAULT_MAX_DELAY,
+            jitter: true,
+        }
+    }
+
+    /// Returns the delay before the given `retry` starting at `1`.
+    pub(crate) fn delay(&self, retry: usize) -> Duration {
+        let factor = u32::try_from(retry.saturating_sub(1))
+            .ok()
+            .and_then(|shift| 1u32.checked_shl(shift));
+        let delay = factor
+            .and_then(|factor| self.base_delay.checked_mul(factor))
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay));
+        if self.jitter {
+            Duration::try_from_secs_f64(delay.as_secs_f64() * random_fraction()).unwrap_or(delay)
+        } else {
+            delay
+        }
+    }
+}
+
+/// Returns a random number between `0.0` and `1.0` without depending on a
+/// random number generator.
+fn random_fraction() -> f64 {
+    // Every `RandomState` is seeded with different keys.
+    let random = RandomState::new().hash_one(());
+    random as f64 / u64::MAX as f64
+}
+
+/// Configuration of the circuit breaker of a [`Pool`].
+///
+/// The circuit breaker opens after `failure_threshold` consecutive
+/// failures of [`Manager::create()`]. While it is open creating new
+/// [`Object`]s fails right away. Once the `cool_down` period has passed a
+/// single create is attempted as probe. If it succeeds the circuit breaker
+/// closes again, otherwise it stays open for another `cool_down` period.
+///
+/// The circuit breaker is not supported on `wasm32` targets.
+///
+/// [`Manager::create()`]: super::Manager::create
+/// [`Object`]: super::Object
+/// [`Pool`]: super::Pool
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CircuitBreakerConfig {
+    /// Number of consecutive failures opening the circuit breaker.
+    pub failure_threshold: usize,
+
+    /// Time the circuit breaker stays open before probing the backend.
+    pub cool_down: Duration,
+}
+
+impl CircuitBreakerConfig {
+    /// Creates a new [`CircuitBreakerConfig`].
+    #[must_use]
+    pub const fn new(failure_threshold: usize, cool_down: Duration) -> Self {
+        Self {
+            failure_threshold,
+            cool_down,
+        }
+    }
+}
+
 /// Mode for dequeuing [`Object`]s from a [`Pool`].
 ///
 /// [`Object`]: super::Object
@@ -111,12 +708,135 @@ impl Default for Timeouts {
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum QueueMode {
     /// Dequeue the object that was least recently added (first in first out).
+    ///
+    /// This spreads the load across all objects and keeps all of them busy
+    /// which prevents them from hitting server side idle timeouts.
     #[default]
     Fifo,
     /// Dequeue the object that was most recently added (last in first out).
+    ///
+    /// This keeps a small working set of hot objects and lets the surplus
+    /// ones go idle so they can be removed via [`PoolConfig::idle_timeout`].
     Lifo,
 }
 
+/// Behavior of a [`Pool`] when [`Manager::recycle()`] exceeds the
+/// [`Timeouts::recycle`]. See [`PoolConfig::recycle_timeout_mode`].
+///
+/// [`Manager::recycle()`]: super::Manager::recycle
+/// [`Pool`]: super::Pool
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum RecycleTimeoutMode {
+    /// Discard the object. [`Pool::get()`] creates a new one instead.
+    ///
+    /// This is the safe choice for backends whose objects are left in an
+    /// undefined state when [`Manager::recycle()`] is aborted.
+    ///
+    /// [`Manager::recycle()`]: super::Manager::recycle
+    /// [`Pool::get()`]: super::Pool::get
+    #[default]
+    Discard,
+    /// Use the object anyway as if recycling it had succeeded. The
+    /// `post_recycle` hooks are not run in that case.
+    ///
+    /// This avoids creating new objects when the backend is merely slow
+    /// and aborting [`Manager::recycle()`] doesn't break the object.
+    /// Those objects are counted in [`Counters::recycle_timeouts_returned`].
+    ///
+    /// [`Counters::recycle_timeouts_returned`]: super::Counters::recycle_timeouts_returned
+    /// [`Manager::recycle()`]: super::Manager::recycle
+    Return,
+}
+
+/// Behavior of a [`Pool`] when an [`Object`] is detached via
+/// [`Object::take()`]. See [`PoolConfig::detach_mode`].
+///
+/// [`Object`]: super::Object
+/// [`Object::take()`]: super::Object::take
+/// [`Pool`]: super::Pool
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum DetachMode {
+    /// Release the slot of the detached object right away so the [`Pool`]
+    /// can create a replacement for it.
+    ///
+    /// [`Pool`]: super::Pool
+    #[default]
+    Release,
+    /// Shrink the [`Pool`] by decreasing its `max_size` by one. The slot
+    /// is gone for good unless the [`Pool`] is resized via
+    /// [`Pool::resize()`].
+    ///
+    /// This keeps the number of open connections to the backend bounded
+    /// by the configured `max_size` even when objects are detached.
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`Pool::resize()`]: super::Pool::resize
+    Shrink,
+}
+
+/// Possible errors returned by [`PoolConfig::validate()`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum InvalidConfigError {
+    /// The [`PoolConfig::max_size`] is `0` so no object could ever be
+    /// retrieved. Use [`Pool::resize()`] for growing an empty pool.
+    ///
+    /// [`Pool::resize()`]: super::Pool::resize
+    ZeroMaxSize,
+
+    /// The `wait` timeout of the [`PoolConfig::timeouts`] is zero so
+    /// retrieving an object fails whenever the pool is exhausted. Pass
+    /// such timeouts to [`Pool::timeout_get()`] for non-blocking calls
+    /// instead.
+    ///
+    /// [`Pool::timeout_get()`]: super::Pool::timeout_get
+    ZeroWaitTimeout,
+
+    /// The [`PoolConfig::min_idle`] exceeds the [`PoolConfig::max_size`].
+    MinIdleExceedsMaxSize {
+        /// Configured [`PoolConfig::min_idle`].
+        min_idle: usize,
+        /// Configured [`PoolConfig::max_size`].
+        max_size: usize,
+    },
+
+    /// The `max_attempts` of the [`PoolConfig::create_retry`] is `0` so
+    /// creating an object would never be attempted.
+    ZeroRetryAttempts,
+
+    /// The `base_delay` of the [`PoolConfig::create_retry`] exceeds its
+    /// `max_delay`.
+    RetryBaseDelayExceedsMaxDelay,
+}
+
+impl fmt::Display for InvalidConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroMaxSize => write!(f, "Invalid pool config: `max_size` must not be 0"),
+            Self::ZeroWaitTimeout => {
+                write!(f, "Invalid pool config: `timeouts.wait` must not be 0")
+            }
+            Self::MinIdleExceedsMaxSize { min_idle, max_size } => write!(
+                f,
+                "Invalid pool config: `min_idle` ({}) exceeds `max_size` ({})",
+                min_idle, max_size
+            ),
+            Self::ZeroRetryAttempts => write!(
+                f,
+                "Invalid pool config: `create_retry.max_attempts` must not be 0"
+            ),
+            Self::RetryBaseDelayExceedsMaxDelay => write!(
+                f,
+                "Invalid pool config: `create_retry.base_delay` exceeds `create_retry.max_delay`"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InvalidConfigError {}
+
 /// This error is used when building pools via the config `create_pool`
 /// methods.
 #[derive(Debug)]
@@ -0,0 +1,177 @@
+//! Cumulative counters of a [`Pool`].
+//!
+//! [`Pool`]: super::Pool
+
+use std::sync::atomic::{AtomicIsize, AtomicUsize, Ordering};
+
+use super::TimeoutType;
+
+/// Cumulative counters of a [`Pool`] since it has been created.
+///
+/// Use [`Pool::counters()`] for retrieving a snapshot of them. Just like
+/// the [`Status`] the counters are not guaranteed to be consistent with
+/// each other.
+///
+/// [`Pool`]: super::Pool
+/// [`Pool::counters()`]: super::Pool::counters
+/// [`Status`]: crate::Status
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[must_use]
+pub struct Counters {
+    /// Number of successful calls to [`Manager::create()`].
+    ///
+    /// [`Manager::create()`]: super::Manager::create
+    pub created: usize,
+
+    /// Number of failed calls to [`Manager::create()`].
+    ///
+    /// [`Manager::create()`]: super::Manager::create
+    pub create_failed: usize,
+
+    /// Number of successfully recycled objects.
+    pub recycled: usize,
+
+    /// Number of objects for which [`Manager::recycle()`] failed or timed
+    /// out.
+    ///
+    /// [`Manager::recycle()`]: super::Manager::recycle
+    pub recycle_failed: usize,
+
+    /// Number of [`TimeoutType::Wait`] timeouts.
+    pub wait_timeouts: usize,
+
+    /// Number of [`TimeoutType::Create`] timeouts.
+    pub create_timeouts: usize,
+
+    /// Number of [`TimeoutType::Recycle`] timeouts.
+    pub recycle_timeouts: usize,
+
+    /// Number of [`TimeoutType::Recycle`] timeouts after which the object
+    /// was used anyway due to [`RecycleTimeoutMode::Return`]. The other
+    /// ones are counted in [`Counters::recycle_failed`].
+    ///
+    /// [`RecycleTimeoutMode::Return`]: super::RecycleTimeoutMode::Return
+    pub recycle_timeouts_returned: usize,
+
+    /// Number of objects removed via [`Object::take()`].
+    ///
+    /// [`Object::take()`]: super::Object::take
+    pub detached: usize,
+
+    /// Number of [`Object`]s held longer than the
+    /// [`PoolConfig::lease_timeout`].
+    ///
+    /// [`Object`]: super::Object
+    /// [`PoolConfig::lease_timeout`]: super::PoolConfig::lease_timeout
+    pub leases_exceeded: usize,
+}
+
+impl Counters {
+    /// Returns the number of timeouts of the given [`TimeoutType`].
+    pub fn timeouts(&self, timeout_type: TimeoutType) -> usize {
+        match timeout_type {
+            TimeoutType::Wait => self.wait_timeouts,
+            TimeoutType::Create => self.create_timeouts,
+            TimeoutType::Recycle => self.recycle_timeouts,
+        }
+    }
+}
+
+/// Atomic version of [`Counters`] which is updated by the pool.
+#[derive(Debug, Default)]
+pub(crate) struct AtomicCounters {
+    pub(crate) created: AtomicUsize,
+    pub(crate) create_failed: AtomicUsize,
+    pub(crate) recycled: AtomicUsize,
+    pub(crate) recycle_failed: AtomicUsize,
+    wait_timeouts: AtomicUsize,
+    create_timeouts: AtomicUsize,
+    recycle_timeouts: AtomicUsize,
+    pub(crate) recycle_timeouts_returned: AtomicUsize,
+    pub(crate) detached: AtomicUsize,
+    pub(crate) leases_exceeded: AtomicUsize,
+}
+
+impl AtomicCounters {
+    pub(crate) fn incr(counter: &AtomicUsize) {
+        let _ = counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn timeout(&self, timeout_type: TimeoutType) {
+        Self::incr(match timeout_type {
+            TimeoutType::Wait => &self.wait_timeouts,
+            TimeoutType::Create => &self.create_timeouts,
+            TimeoutType::Recycle => &self.recycle_timeouts,
+        });
+    }
+
+    pub(crate) fn snapshot(&self) -> Counters {
+        Counters {
+            created: self.created.load(Ordering::Relaxed),
+            create_failed: self.create_failed.load(Ordering::Relaxed),
+            recycled: self.recycled.load(Ordering::Relaxed),
+            recycle_failed: self.recycle_failed.load(Ordering::Relaxed),
+            wait_timeouts: self.wait_timeouts.load(Ordering::Relaxed),
+            create_timeouts: self.create_timeouts.load(Ordering::Relaxed),
+            recycle_timeouts: self.recycle_timeouts.load(Ordering::Relaxed),
+            recycle_timeouts_returned: self.recycle_timeouts_returned.load(Ordering::Relaxed),
+            detached: self.detached.load(Ordering::Relaxed),
+            leases_exceeded: self.leases_exceeded.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Gauge which is split into shards living on separate cache lines so tasks
+/// running on different threads don't contend on a single atomic.
+///
+/// Every thread updates the shard it has been assigned to. A value might be
+/// added on one thread and subtracted on another one, so single shards can
+/// become negative and only the sum of all shards is meaningful.
+#[derive(Debug)]
+pub(crate) struct ShardedGauge {
+    shards: Box<[CachePadded]>,
+}
+
+#[derive(Debug, Default)]
+#[repr(align(128))]
+struct CachePadded(AtomicIsize);
+
+impl ShardedGauge {
+    /// Creates a new gauge with the given number of `shards` which is at
+    /// least one.
+    pub(crate) fn new(shards: usize) -> Self {
+        Self {
+            shards: (0..shards.max(1)).map(|_| CachePadded::default()).collect(),
+        }
+    }
+
+    fn shard(&self) -> &AtomicIsize {
+        static NEXT_THREAD: AtomicUsize = AtomicUsize::new(0);
+        thread_local! {
+            static THREAD: usize = NEXT_THREAD.fetch_add(1, Ordering::Relaxed);
+        }
+        let index = THREAD.try_with(|thread| *thread).unwrap_or_default();
+        &self.shards[index % self.shards.len()].0
+    }
+
+    pub(crate) fn incr(&self) {
+        let _ = self.shard().fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn decr(&self) {
+        let _ = self.shard().fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Returns the sum of all shards. The shards are read one after another
+    /// so a concurrent update might make the sum appear negative for a
+    /// moment, in which case `0` is returned.
+    pub(crate) fn get(&self) -> usize {
+        let sum = self
+            .shards
+            .iter()
+            .map(|shard| shard.0.load(Ordering::Relaxed))
+            .fold(0isize, isize::wrapping_add);
+        usize::try_from(sum).unwrap_or(0)
+    }
+}
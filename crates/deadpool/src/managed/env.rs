@@ -0,0 +1,179 @@
+//! Reading the managed [`PoolConfig`] from environment variables.
+
+use std::str::FromStr;
+
+use super::{
+    AutoscaleConfig, CircuitBreakerConfig, DetachMode, PoolConfig, QueueMode, RecycleTimeoutMode,
+    RetryConfig, Timeouts,
+};
+use crate::env::{EnvVars, FromEnvError};
+
+impl EnvVars {
+    /// Reads the [`PoolConfig`] of the nested section with the given `key`
+    /// using [`PoolConfig::from_env()`]. Returns `None` if none of the
+    /// variables of that section is set.
+    ///
+    /// # Errors
+    ///
+    /// See [`FromEnvError`] for details.
+    pub fn pool_config(&self, key: &str) -> Result<Option<PoolConfig>, FromEnvError> {
+        let vars = self.nested(key);
+        if vars.is_present() {
+            PoolConfig::from_env(&vars.prefix).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "env")))]
+impl PoolConfig {
+    /// Reads a [`PoolConfig`] from environment variables starting with the
+    /// given `prefix`.
+    ///
+    /// Every field is read from the variable named like the field in upper
+    /// case. Nested fields are separated by `__`. Durations can be given in a
+    /// human readable form like `500ms` or `30s`. Fields whose variables
+    /// aren't set keep their default value.
+    ///
+    /// ```env
+    /// POOL__MAX_SIZE=16
+    /// POOL__TIMEOUTS__WAIT=5s
+    /// POOL__QUEUE_MODE=lifo
+    /// ```
+    /// ```rust
+    /// # use deadpool::managed::PoolConfig;
+    /// let config = PoolConfig::from_env("POOL").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`FromEnvError`] for details.
+    pub fn from_env(prefix: &str) -> Result<Self, FromEnvError> {
+        let env = EnvVars::new(prefix);
+        let mut cfg = Self::default();
+        if let Some(max_size) = env.parse("MAX_SIZE")? {
+            cfg.max_size = max_size;
+        }
+        let timeouts = env.nested("TIMEOUTS");
+        cfg.timeouts = Timeouts {
+            wait: timeouts.duration("WAIT")?,
+            create: timeouts.duration("CREATE")?,
+            recycle: timeouts.duration("RECYCLE")?,
+        };
+        if let Some(queue_mode) = env.parse("QUEUE_MODE")? {
+            cfg.queue_mode = queue_mode;
+        }
+        cfg.max_lifetime = env.duration("MAX_LIFETIME")?;
+        cfg.idle_timeout = env.duration("IDLE_TIMEOUT")?;
+        cfg.max_uses = env.parse("MAX_USES")?;
+        if let Some(min_idle) = env.parse("MIN_IDLE")? {
+            cfg.min_idle = min_idle;
+        }
+        cfg.maintenance_interval = env.duration("MAINTENANCE_INTERVAL")?;
+        cfg.health_check_interval = env.duration("HEALTH_CHECK_INTERVAL")?;
+        cfg.max_waiters = env.parse("MAX_WAITERS")?;
+        if let Some(max_overflow) = env.parse("MAX_OVERFLOW")? {
+            cfg.max_overflow = max_overflow;
+        }
+        let autoscale = env.nested("AUTOSCALE");
+        if autoscale.is_present() {
+            let mut config = AutoscaleConfig::new(
+                autoscale.require_parse("MIN_SIZE")?,
+                autoscale.require_parse("MAX_SIZE")?,
+                autoscale.require_duration("INTERVAL")?,
+            );
+            config.target_wait = autoscale.duration("TARGET_WAIT")?;
+            if let Some(wait_quantile) = autoscale.parse("WAIT_QUANTILE")? {
+                config.wait_quantile = wait_quantile;
+            }
+            if let Some(shrink_utilization) = autoscale.parse("SHRINK_UTILIZATION")? {
+                config.shrink_utilization = shrink_utilization;
+            }
+            cfg.autoscale = Some(config);
+        }
+        if let Some(shards) = env.parse("SHARDS")? {
+            cfg.shards = shards;
+        }
+        let create_retry = env.nested("CREATE_RETRY");
+        if create_retry.is_present() {
+            let mut retry = RetryConfig::new(
+                create_retry.require_parse("MAX_ATTEMPTS")?,
+                create_retry.require_duration("BASE_DELAY")?,
+            );
+            if let Some(max_delay) = create_retry.duration("MAX_DELAY")? {
+                retry.max_delay = max_delay;
+            }
+            if let Some(jitter) = create_retry.bool("JITTER")? {
+                retry.jitter = jitter;
+            }
+            cfg.create_retry = Some(retry);
+        }
+        let circuit_breaker = env.nested("CIRCUIT_BREAKER");
+        if circuit_breaker.is_present() {
+            cfg.circuit_breaker = Some(CircuitBreakerConfig::new(
+                circuit_breaker.require_parse("FAILURE_THRESHOLD")?,
+                circuit_breaker.require_duration("COOL_DOWN")?,
+            ));
+        }
+        if let Some(recycle_on_return) = env.bool("RECYCLE_ON_RETURN")? {
+            cfg.recycle_on_return = recycle_on_return;
+        }
+        if let Some(recycle_ahead) = env.parse("RECYCLE_AHEAD")? {
+            cfg.recycle_ahead = recycle_ahead;
+        }
+        if let Some(max_holders) = env.parse("MAX_HOLDERS")? {
+            cfg.max_holders = max_holders;
+        }
+        cfg.lease_timeout = env.duration("LEASE_TIMEOUT")?;
+        if let Some(recycle_timeout_mode) = env.parse("RECYCLE_TIMEOUT_MODE")? {
+            cfg.recycle_timeout_mode = recycle_timeout_mode;
+        }
+        if let Some(detach_mode) = env.parse("DETACH_MODE")? {
+            cfg.detach_mode = detach_mode;
+        }
+        Ok(cfg)
+    }
+}
+
+impl FromStr for QueueMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("fifo") {
+            Ok(Self::Fifo)
+        } else if s.eq_ignore_ascii_case("lifo") {
+            Ok(Self::Lifo)
+        } else {
+            Err("expected `fifo` or `lifo`".to_owned())
+        }
+    }
+}
+
+impl FromStr for RecycleTimeoutMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("discard") {
+            Ok(Self::Discard)
+        } else if s.eq_ignore_ascii_case("return") {
+            Ok(Self::Return)
+        } else {
+            Err("expected `discard` or `return`".to_owned())
+        }
+    }
+}
+
+impl FromStr for DetachMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("release") {
+            Ok(Self::Release)
+        } else if s.eq_ignore_ascii_case("shrink") {
+            Ok(Self::Shrink)
+        } else {
+            Err("expected `release` or `shrink`".to_owned())
+        }
+    }
+}
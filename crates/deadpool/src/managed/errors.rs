@@ -1,4 +1,8 @@
-use std::{borrow::Cow, fmt};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::SystemTime;
+use std::{borrow::Cow, convert::Infallible, fmt, time::Duration};
+
+use crate::Status;
 
 use super::hooks::HookError;
 
@@ -62,13 +66,111 @@ pub enum TimeoutType {
     Recycle,
 }
 
+/// Details of a timeout reported via [`PoolError::Timeout`].
+///
+/// The [`Status`] is captured when the timeout occurs so it reflects the
+/// state of the [`Pool`] which caused the timeout.
+///
+/// [`Pool`]: super::Pool
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct TimeoutError {
+    /// Step which timed out.
+    pub timeout_type: TimeoutType,
+
+    /// Configured timeout of the step.
+    pub timeout: Duration,
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Time spent in the step until the timeout occurred.
+    pub elapsed: Duration,
+
+    /// [`Status`] of the [`Pool`] when the timeout occurred.
+    ///
+    /// [`Pool`]: super::Pool
+    pub status: Status,
+}
+
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.timeout_type {
+            TimeoutType::Wait => write!(
+                f,
+                "Timeout occurred while waiting for a slot to become available"
+            )?,
+            TimeoutType::Create => write!(f, "Timeout occurred while creating a new object")?,
+            TimeoutType::Recycle => write!(f, "Timeout occurred while recycling an object")?,
+        }
+        let status = &self.status;
+        write!(
+            f,
+            " (timeout: {:?}, size: {}/{}, available: {}, waiting: {})",
+            self.timeout, status.size, status.max_size, status.available, status.waiting,
+        )
+    }
+}
+
+/// Operation of the [`Manager`] which caused a [`LastError`].
+///
+/// [`Manager`]: super::Manager
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ErrorStage {
+    /// [`Manager::create()`] failed or timed out.
+    ///
+    /// [`Manager::create()`]: super::Manager::create
+    Create,
+
+    /// [`Manager::recycle()`] failed or timed out.
+    ///
+    /// [`Manager::recycle()`]: super::Manager::recycle
+    Recycle,
+}
+
+impl fmt::Display for ErrorStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Create => write!(f, "create"),
+            Self::Recycle => write!(f, "recycle"),
+        }
+    }
+}
+
+/// Most recent error which occurred while creating or recycling an object.
+/// See [`Pool::last_error()`].
+///
+/// Errors of the backend are only described by a `message` if a
+/// formatter is attached via [`PoolBuilder::describe_error()`] as the
+/// [`Manager::Error`] isn't required to implement [`fmt::Display`].
+///
+/// [`Manager::Error`]: super::Manager::Error
+/// [`Pool::last_error()`]: super::Pool::last_error
+/// [`PoolBuilder::describe_error()`]: super::PoolBuilder::describe_error
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct LastError {
+    /// Operation which failed.
+    pub stage: ErrorStage,
+
+    /// Kind of the error. This is either [`PoolErrorKind::Backend`] or
+    /// [`PoolErrorKind::Timeout`].
+    pub kind: PoolErrorKind,
+
+    /// Description of the error if there is one.
+    pub message: Option<String>,
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Time when the error occurred.
+    pub time: SystemTime,
+}
+
 /// Possible errors returned by [`Pool::get()`] method.
 ///
 /// [`Pool::get()`]: super::Pool::get
 #[derive(Debug)]
 pub enum PoolError<E> {
     /// Timeout happened.
-    Timeout(TimeoutType),
+    Timeout(TimeoutError),
 
     /// Backend reported an error.
     Backend(E),
@@ -85,6 +187,118 @@ pub enum PoolError<E> {
 
     /// A `post_create` hook reported an error.
     PostCreateHook(HookError<E>),
+
+    /// An `on_acquire` hook reported an error.
+    OnAcquireHook(HookError<E>),
+
+    /// A `pre_recycle` or `post_recycle` hook aborted retrieving an
+    /// [`Object`] via [`HookError::Abort`].
+    ///
+    /// [`Object`]: super::Object
+    RecycleHook(HookError<E>),
+
+    /// Retrieving an [`Object`] has been cancelled.
+    ///
+    /// [`Object`]: super::Object
+    Cancelled,
+
+    /// Too many tasks are waiting for an [`Object`] already.
+    /// See [`PoolConfig::max_waiters`].
+    ///
+    /// [`Object`]: super::Object
+    /// [`PoolConfig::max_waiters`]: super::PoolConfig::max_waiters
+    QueueFull,
+
+    /// The circuit breaker is open because creating [`Object`]s failed
+    /// repeatedly. See [`PoolConfig::circuit_breaker`].
+    ///
+    /// [`Object`]: super::Object
+    /// [`PoolConfig::circuit_breaker`]: super::PoolConfig::circuit_breaker
+    CircuitOpen,
+}
+
+/// Kind of a [`PoolError`] without its payload.
+///
+/// This allows handling [`PoolError`]s generically, e.g. in middleware,
+/// without matching on the backend specific error type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum PoolErrorKind {
+    /// See [`PoolError::Timeout`].
+    Timeout,
+    /// See [`PoolError::Backend`].
+    Backend,
+    /// See [`PoolError::Closed`].
+    Closed,
+    /// See [`PoolError::NoRuntimeSpecified`].
+    NoRuntimeSpecified,
+    /// See [`PoolError::PostCreateHook`], [`PoolError::OnAcquireHook`] and
+    /// [`PoolError::RecycleHook`].
+    Hook,
+    /// See [`PoolError::Cancelled`].
+    Cancelled,
+    /// See [`PoolError::QueueFull`].
+    QueueFull,
+    /// See [`PoolError::CircuitOpen`].
+    CircuitOpen,
+}
+
+/// Errors which can tell whether the failed operation might succeed when
+/// being retried.
+///
+/// Implement this for the [`Manager::Error`] in order to use
+/// [`PoolError::is_retryable()`].
+///
+/// [`Manager::Error`]: super::Manager::Error
+pub trait Retryable {
+    /// Returns `true` if retrying the failed operation might succeed, e.g.
+    /// because the error was caused by a temporary network issue.
+    fn is_retryable(&self) -> bool;
+}
+
+impl Retryable for Infallible {
+    fn is_retryable(&self) -> bool {
+        match *self {}
+    }
+}
+
+impl<E> PoolError<E> {
+    /// Returns the [`PoolErrorKind`] of this error.
+    pub fn kind(&self) -> PoolErrorKind {
+        match self {
+            Self::Timeout(_) => PoolErrorKind::Timeout,
+            Self::Backend(_) => PoolErrorKind::Backend,
+            Self::Closed => PoolErrorKind::Closed,
+            Self::NoRuntimeSpecified => PoolErrorKind::NoRuntimeSpecified,
+            Self::PostCreateHook(_) | Self::OnAcquireHook(_) | Self::RecycleHook(_) => {
+                PoolErrorKind::Hook
+            }
+            Self::Cancelled => PoolErrorKind::Cancelled,
+            Self::QueueFull => PoolErrorKind::QueueFull,
+            Self::CircuitOpen => PoolErrorKind::CircuitOpen,
+        }
+    }
+}
+
+impl<E: Retryable> PoolError<E> {
+    /// Returns `true` if retrieving an [`Object`] again might succeed.
+    ///
+    /// Timeouts and errors caused by an overloaded [`Pool`] are retryable.
+    /// Errors of the backend and of hooks are retryable if the backend
+    /// error says so via [`Retryable`]. All other errors are permanent.
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Timeout(_) | Self::QueueFull | Self::CircuitOpen => true,
+            Self::Backend(e) => e.is_retryable(),
+            Self::PostCreateHook(e) | Self::OnAcquireHook(e) | Self::RecycleHook(e) => {
+                e.is_retryable()
+            }
+            Self::Closed | Self::NoRuntimeSpecified | Self::Cancelled => false,
+        }
+    }
 }
 
 impl<E> From<E> for PoolError<E> {
@@ -96,18 +310,16 @@ impl<E> From<E> for PoolError<E> {
 impl<E: fmt::Display> fmt::Display for PoolError<E> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Timeout(tt) => match tt {
-                TimeoutType::Wait => write!(
-                    f,
-                    "Timeout occurred while waiting for a slot to become available"
-                ),
-                TimeoutType::Create => write!(f, "Timeout occurred while creating a new object"),
-                TimeoutType::Recycle => write!(f, "Timeout occurred while recycling an object"),
-            },
+            Self::Timeout(e) => e.fmt(f),
             Self::Backend(e) => write!(f, "Error occurred while creating a new object: {}", e),
             Self::Closed => write!(f, "Pool has been closed"),
             Self::NoRuntimeSpecified => write!(f, "No runtime specified"),
             Self::PostCreateHook(e) => writeln!(f, "`post_create` hook failed: {}", e),
+            Self::OnAcquireHook(e) => write!(f, "`on_acquire` hook failed: {}", e),
+            Self::RecycleHook(e) => write!(f, "Recycle hook failed: {}", e),
+            Self::Cancelled => write!(f, "Retrieving an object has been cancelled"),
+            Self::QueueFull => write!(f, "Too many tasks are waiting for an object"),
+            Self::CircuitOpen => write!(f, "Circuit breaker is open"),
         }
     }
 }
@@ -115,9 +327,14 @@ impl<E: fmt::Display> fmt::Display for PoolError<E> {
 impl<E: std::error::Error + 'static> std::error::Error for PoolError<E> {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::Timeout(_) | Self::Closed | Self::NoRuntimeSpecified => None,
+            Self::Timeout(_)
+            | Self::Closed
+            | Self::NoRuntimeSpecified
+            | Self::Cancelled
+            | Self::QueueFull
+            | Self::CircuitOpen => None,
             Self::Backend(e) => Some(e),
-            Self::PostCreateHook(e) => Some(e),
+            Self::PostCreateHook(e) | Self::OnAcquireHook(e) | Self::RecycleHook(e) => Some(e),
         }
     }
 }
@@ -0,0 +1,73 @@
+//! Events emitted by a [`Pool`](super::Pool).
+
+use std::time::Duration;
+
+use super::{Metrics, TimeoutType};
+
+/// Event emitted by a [`Pool`] to the callbacks attached via
+/// [`PoolBuilder::on_event()`].
+///
+/// Objects are identified by the same id which is returned by
+/// [`Object::id()`].
+///
+/// [`Object::id()`]: super::Object::id
+/// [`Pool`]: super::Pool
+/// [`PoolBuilder::on_event()`]: super::PoolBuilder::on_event
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum PoolEvent {
+    /// A new object has been created or added via [`Pool::add()`].
+    ///
+    /// [`Pool::add()`]: super::Pool::add
+    ObjectCreated {
+        /// Id of the object.
+        id: usize,
+    },
+
+    /// An object has been recycled successfully.
+    ObjectRecycled {
+        /// Id of the object.
+        id: usize,
+        /// [`Metrics`] of the object.
+        metrics: Metrics,
+    },
+
+    /// [`Manager::recycle()`] failed or timed out. The object is dropped
+    /// afterwards.
+    ///
+    /// [`Manager::recycle()`]: super::Manager::recycle
+    RecycleFailed {
+        /// Id of the object.
+        id: usize,
+        /// [`Metrics`] of the object.
+        metrics: Metrics,
+    },
+
+    /// An object has been removed from the pool and dropped.
+    ObjectDropped {
+        /// Id of the object.
+        id: usize,
+        /// [`Metrics`] of the object.
+        metrics: Metrics,
+    },
+
+    /// An object has been held longer than the
+    /// [`PoolConfig::lease_timeout`]. This is emitted once per lease.
+    ///
+    /// [`PoolConfig::lease_timeout`]: super::PoolConfig::lease_timeout
+    LeaseExceeded {
+        /// Id of the object.
+        id: usize,
+        /// Duration the object has been held for.
+        held: Duration,
+    },
+
+    /// A timeout occurred.
+    Timeout(TimeoutType),
+
+    /// A task wasn't allowed to wait for an object because too many tasks
+    /// are waiting already. See [`PoolConfig::max_waiters`].
+    ///
+    /// [`PoolConfig::max_waiters`]: super::PoolConfig::max_waiters
+    QueueFull,
+}
@@ -0,0 +1,66 @@
+//! Active health checks of a pool. See [`Pool::health()`].
+//!
+//! [`Pool::health()`]: super::Pool::health
+
+use std::time::Duration;
+
+use crate::Status;
+
+use super::{LastError, PoolErrorKind};
+
+/// Outcome of the probe performed by [`Pool::health()`].
+///
+/// [`Pool::health()`]: super::Pool::health
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum HealthProbe {
+    /// An idle object has been recycled successfully.
+    Recycled,
+
+    /// A new object has been created successfully.
+    Created,
+
+    /// All slots of the pool are in use so no probe has been performed.
+    /// The pool is busy rather than broken.
+    Busy,
+
+    /// The probe failed with the given kind of error. See
+    /// [`Health::last_error`] for details.
+    Failed(PoolErrorKind),
+
+    /// The pool has been closed.
+    Closed,
+}
+
+/// Health report returned by [`Pool::health()`].
+///
+/// [`Pool::health()`]: super::Pool::health
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Health {
+    /// Outcome of the probe.
+    pub probe: HealthProbe,
+
+    /// Time it took to perform the probe.
+    pub elapsed: Duration,
+
+    /// [`Status`] of the pool after the probe.
+    pub status: Status,
+
+    /// Most recent error which occurred while creating or recycling an
+    /// object. See [`Pool::last_error()`].
+    ///
+    /// [`Pool::last_error()`]: super::Pool::last_error
+    pub last_error: Option<LastError>,
+}
+
+impl Health {
+    /// Returns `true` if the probe succeeded or was skipped because the
+    /// pool is busy.
+    pub fn is_healthy(&self) -> bool {
+        matches!(
+            self.probe,
+            HealthProbe::Recycled | HealthProbe::Created | HealthProbe::Busy
+        )
+    }
+}
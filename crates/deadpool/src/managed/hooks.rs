@@ -4,7 +4,9 @@ use std::{borrow::Cow, fmt, future::Future, pin::Pin};
 
 use crate::managed::object::ObjectInner;
 
-use super::{Manager, Metrics};
+use crate::Status;
+
+use super::{Manager, Metrics, PoolEvent, RecycleError, Retryable, TimeoutError};
 
 /// The result returned by hooks
 pub type HookResult<E> = Result<(), HookError<E>>;
@@ -16,11 +18,42 @@ pub type HookFuture<'a, E> = Pin<Box<dyn Future<Output = HookResult<E>> + Send +
 type SyncFn<M> =
     dyn Fn(&mut <M as Manager>::Type, &Metrics) -> HookResult<<M as Manager>::Error> + Sync + Send;
 
+/// Function signature for callbacks which can't fail
+type NotifyFn<M> = dyn Fn(&mut <M as Manager>::Type, &Metrics) + Sync + Send;
+
+/// Function signature for callbacks receiving a create error
+type CreateErrorFn<M> = dyn Fn(&<M as Manager>::Error) + Sync + Send;
+
+/// Function signature for callbacks receiving a recycle error
+type RecycleErrorFn<M> = dyn Fn(&RecycleError<<M as Manager>::Error>, &Metrics) + Sync + Send;
+
+/// Function signature for callbacks receiving a timeout
+pub(crate) type TimeoutFn = dyn Fn(&TimeoutError) + Sync + Send;
+
+/// Function signature for callbacks describing a backend error
+pub(crate) type DescribeErrorFn<M> = dyn Fn(&<M as Manager>::Error) -> String + Sync + Send;
+
+/// Function signature for callbacks receiving a pool event
+pub(crate) type EventFn = dyn Fn(&PoolEvent) + Sync + Send;
+
 /// Function siganture for async callbacks
 type AsyncFn<M> = dyn for<'a> Fn(&'a mut <M as Manager>::Type, &'a Metrics) -> HookFuture<'a, <M as Manager>::Error>
     + Sync
     + Send;
 
+/// Function signature for sync `on_acquire` callbacks
+type AcquireSyncFn<M> = dyn Fn(&mut <M as Manager>::Type, &mut Metrics) -> HookResult<<M as Manager>::Error>
+    + Sync
+    + Send;
+
+/// Function signature for async `on_acquire` callbacks
+type AcquireAsyncFn<M> = dyn for<'a> Fn(
+        &'a mut <M as Manager>::Type,
+        &'a mut Metrics,
+    ) -> HookFuture<'a, <M as Manager>::Error>
+    + Sync
+    + Send;
+
 /// Wrapper for hook functions
 pub enum Hook<M: Manager> {
     /// Use a plain function (non-async) as a hook
@@ -62,8 +95,46 @@ impl<M: Manager> fmt::Debug for Hook<M> {
     }
 }
 
-/// Error which is returned by `pre_create`, `pre_recycle` and
-/// `post_recycle` hooks.
+/// Wrapper for `on_acquire` hook functions.
+///
+/// Unlike a [`Hook`] these get mutable access to the [`Metrics`] of the
+/// object as well.
+pub enum AcquireHook<M: Manager> {
+    /// Use a plain function (non-async) as a hook
+    Fn(Box<AcquireSyncFn<M>>),
+    /// Use an async function as a hook
+    AsyncFn(Box<AcquireAsyncFn<M>>),
+}
+
+impl<M: Manager> AcquireHook<M> {
+    /// Create AcquireHook from sync function
+    pub fn sync_fn(
+        f: impl Fn(&mut M::Type, &mut Metrics) -> HookResult<M::Error> + Sync + Send + 'static,
+    ) -> Self {
+        Self::Fn(Box::new(f))
+    }
+    /// Create AcquireHook from async function
+    pub fn async_fn(
+        f: impl for<'a> Fn(&'a mut M::Type, &'a mut Metrics) -> HookFuture<'a, M::Error>
+            + Sync
+            + Send
+            + 'static,
+    ) -> Self {
+        Self::AsyncFn(Box::new(f))
+    }
+}
+
+impl<M: Manager> fmt::Debug for AcquireHook<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fn(_) => f.debug_tuple("Fn").finish(),
+            Self::AsyncFn(_) => f.debug_tuple("AsyncFn").finish(),
+        }
+    }
+}
+
+/// Error which is returned by `post_create`, `pre_recycle`, `post_recycle`
+/// and `on_acquire` hooks.
 #[derive(Debug)]
 pub enum HookError<E> {
     /// Hook failed for some other reason.
@@ -71,6 +142,71 @@ pub enum HookError<E> {
 
     /// Error caused by the backend.
     Backend(E),
+
+    /// Discard the object without failing.
+    ///
+    /// When returned by a `pre_recycle`, `post_recycle` or `on_acquire`
+    /// hook the pool tries another object. When returned by a
+    /// `post_create` hook the object is discarded and creation is retried.
+    Discard,
+
+    /// Abort the current [`Pool::get()`] with the wrapped error.
+    ///
+    /// When returned by a `pre_recycle` or `post_recycle` hook the pool
+    /// doesn't try another object but fails with
+    /// [`PoolError::RecycleHook`] instead. When returned by an `on_acquire`
+    /// hook the pool fails with [`PoolError::OnAcquireHook`].
+    ///
+    /// [`Pool::get()`]: super::Pool::get
+    /// [`PoolError::OnAcquireHook`]: super::PoolError::OnAcquireHook
+    /// [`PoolError::RecycleHook`]: super::PoolError::RecycleHook
+    Abort(Box<HookError<E>>),
+
+    /// Error of a hook together with the context it occurred in.
+    ///
+    /// This variant is never returned by hooks but added by the [`Pool`]
+    /// when it reports the error of a hook via a [`PoolError`].
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`PoolError`]: super::PoolError
+    WithContext(Box<HookError<E>>, HookContext),
+}
+
+/// Stage of a hook. See [`HookContext`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HookStage {
+    /// `post_create` hook
+    PostCreate,
+    /// `pre_recycle` hook
+    PreRecycle,
+    /// `post_recycle` hook
+    PostRecycle,
+    /// `on_acquire` hook
+    OnAcquire,
+}
+
+impl fmt::Display for HookStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PostCreate => write!(f, "post_create"),
+            Self::PreRecycle => write!(f, "pre_recycle"),
+            Self::PostRecycle => write!(f, "post_recycle"),
+            Self::OnAcquire => write!(f, "on_acquire"),
+        }
+    }
+}
+
+/// Context in which a hook failed. See [`HookError::WithContext`].
+#[derive(Clone, Copy, Debug)]
+pub struct HookContext {
+    /// Stage of the failed hook.
+    pub stage: HookStage,
+
+    /// [`Metrics`] of the object the hook was called with.
+    pub metrics: Metrics,
+
+    /// [`Status`] of the pool right after the hook failed.
+    pub status: Status,
 }
 
 impl<E> HookError<E> {
@@ -79,6 +215,55 @@ impl<E> HookError<E> {
     pub fn message(msg: impl Into<Cow<'static, str>>) -> Self {
         Self::Message(msg.into())
     }
+
+    /// Convenience constructor function for the `HookError::Abort`
+    /// variant.
+    pub fn abort(e: HookError<E>) -> Self {
+        Self::Abort(Box::new(e))
+    }
+
+    /// Returns the [`HookContext`] of an `HookError::WithContext`.
+    pub fn context(&self) -> Option<&HookContext> {
+        match self {
+            Self::WithContext(_, context) => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Returns the error returned by the hook stripping the context added
+    /// by the pool.
+    pub fn into_inner(self) -> Self {
+        match self {
+            Self::WithContext(e, _) => *e,
+            e => e,
+        }
+    }
+
+    /// Returns `true` if the error is caused by the backend and the backend
+    /// error is [`Retryable`].
+    pub fn is_retryable(&self) -> bool
+    where
+        E: Retryable,
+    {
+        match self {
+            Self::Backend(e) => e.is_retryable(),
+            Self::Abort(e) | Self::WithContext(e, _) => e.is_retryable(),
+            Self::Message(_) | Self::Discard => false,
+        }
+    }
+
+    pub(crate) fn with_context(self, context: HookContext) -> Self {
+        Self::WithContext(Box::new(self), context)
+    }
+
+    /// Unwraps the error of an `HookError::Abort` returning `None` for all
+    /// other variants.
+    pub(crate) fn into_abort(self) -> Option<Self> {
+        match self {
+            Self::Abort(e) => Some(*e),
+            _ => None,
+        }
+    }
 }
 
 impl<E: fmt::Display> fmt::Display for HookError<E> {
@@ -86,6 +271,17 @@ impl<E: fmt::Display> fmt::Display for HookError<E> {
         match self {
             Self::Message(msg) => write!(f, "{}", msg),
             Self::Backend(e) => write!(f, "{}", e),
+            Self::Discard => write!(f, "Object discarded by hook"),
+            Self::Abort(e) => write!(f, "{}", e),
+            Self::WithContext(e, context) => write!(
+                f,
+                "{} (stage: {}, recycle count: {}, pool size: {}, available: {})",
+                e,
+                context.stage,
+                context.metrics.recycle_count,
+                context.status.size,
+                context.status.available,
+            ),
         }
     }
 }
@@ -93,8 +289,9 @@ impl<E: fmt::Display> fmt::Display for HookError<E> {
 impl<E: std::error::Error + 'static> std::error::Error for HookError<E> {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::Message(_) => None,
+            Self::Message(_) | Self::Discard => None,
             Self::Backend(e) => Some(e),
+            Self::Abort(e) | Self::WithContext(e, _) => e.source(),
         }
     }
 }
@@ -137,6 +334,133 @@ impl<M: Manager> HookVec<M> {
     }
 }
 
+/// Hooks which are called right before an object is handed out.
+pub(crate) struct AcquireHookVec<M: Manager> {
+    vec: Vec<AcquireHook<M>>,
+}
+
+// Implemented manually to avoid unnecessary trait bound on `M` type parameter.
+impl<M: Manager> fmt::Debug for AcquireHookVec<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AcquireHookVec").finish_non_exhaustive()
+    }
+}
+
+// Implemented manually to avoid unnecessary trait bound on `M` type parameter.
+impl<M: Manager> Default for AcquireHookVec<M> {
+    fn default() -> Self {
+        Self { vec: Vec::new() }
+    }
+}
+
+impl<M: Manager> AcquireHookVec<M> {
+    pub(crate) async fn apply(
+        &self,
+        inner: &mut ObjectInner<M>,
+    ) -> Result<(), HookError<M::Error>> {
+        for hook in &self.vec {
+            match hook {
+                AcquireHook::Fn(f) => f(&mut inner.obj, &mut inner.metrics)?,
+                AcquireHook::AsyncFn(f) => f(&mut inner.obj, &mut inner.metrics).await?,
+            };
+        }
+        Ok(())
+    }
+    pub(crate) fn push(&mut self, hook: AcquireHook<M>) {
+        self.vec.push(hook);
+    }
+}
+
+/// Hooks which are called when an object is returned to the pool. As this
+/// happens when dropping the object only sync functions are supported.
+pub(crate) struct SyncHookVec<M: Manager> {
+    vec: Vec<Box<SyncFn<M>>>,
+}
+
+// Implemented manually to avoid unnecessary trait bound on `M` type parameter.
+impl<M: Manager> fmt::Debug for SyncHookVec<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncHookVec").finish_non_exhaustive()
+    }
+}
+
+// Implemented manually to avoid unnecessary trait bound on `M` type parameter.
+impl<M: Manager> Default for SyncHookVec<M> {
+    fn default() -> Self {
+        Self { vec: Vec::new() }
+    }
+}
+
+impl<M: Manager> SyncHookVec<M> {
+    pub(crate) fn apply(&self, inner: &mut ObjectInner<M>) -> Result<(), HookError<M::Error>> {
+        for f in &self.vec {
+            f(&mut inner.obj, &inner.metrics)?;
+        }
+        Ok(())
+    }
+    pub(crate) fn push(&mut self, f: Box<SyncFn<M>>) {
+        self.vec.push(f);
+    }
+}
+
+/// Hooks which are only notified about an event and can't fail.
+pub(crate) struct NotifyHookVec<M: Manager> {
+    vec: Vec<Box<NotifyFn<M>>>,
+}
+
+// Implemented manually to avoid unnecessary trait bound on `M` type parameter.
+impl<M: Manager> fmt::Debug for NotifyHookVec<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NotifyHookVec").finish_non_exhaustive()
+    }
+}
+
+// Implemented manually to avoid unnecessary trait bound on `M` type parameter.
+impl<M: Manager> Default for NotifyHookVec<M> {
+    fn default() -> Self {
+        Self { vec: Vec::new() }
+    }
+}
+
+impl<M: Manager> NotifyHookVec<M> {
+    pub(crate) fn apply(&self, inner: &mut ObjectInner<M>) {
+        for f in &self.vec {
+            f(&mut inner.obj, &inner.metrics);
+        }
+    }
+    pub(crate) fn push(&mut self, f: Box<NotifyFn<M>>) {
+        self.vec.push(f);
+    }
+}
+
+/// Plain list of callbacks which are called directly by the pool.
+pub(crate) struct FnVec<F: ?Sized> {
+    vec: Vec<Box<F>>,
+}
+
+// Implemented manually as the callbacks don't implement `Debug`.
+impl<F: ?Sized> fmt::Debug for FnVec<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnVec").finish_non_exhaustive()
+    }
+}
+
+// Implemented manually to avoid unnecessary trait bound on `F`.
+impl<F: ?Sized> Default for FnVec<F> {
+    fn default() -> Self {
+        Self { vec: Vec::new() }
+    }
+}
+
+impl<F: ?Sized> FnVec<F> {
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &F> {
+        self.vec.iter().map(Box::as_ref)
+    }
+    pub(crate) fn push(&mut self, f: Box<F>) {
+        self.vec.push(f);
+    }
+}
+
 /// Collection of all the hooks that can be configured for a [`Pool`].
 ///
 /// [`Pool`]: super::Pool
@@ -144,6 +468,14 @@ pub(crate) struct Hooks<M: Manager> {
     pub(crate) post_create: HookVec<M>,
     pub(crate) pre_recycle: HookVec<M>,
     pub(crate) post_recycle: HookVec<M>,
+    pub(crate) on_acquire: AcquireHookVec<M>,
+    pub(crate) pre_return: SyncHookVec<M>,
+    pub(crate) on_detach: NotifyHookVec<M>,
+    pub(crate) on_create_error: FnVec<CreateErrorFn<M>>,
+    pub(crate) on_recycle_error: FnVec<RecycleErrorFn<M>>,
+    pub(crate) on_event: FnVec<EventFn>,
+    pub(crate) on_timeout: FnVec<TimeoutFn>,
+    pub(crate) describe_error: Option<Box<DescribeErrorFn<M>>>,
 }
 
 // Implemented manually to avoid unnecessary trait bound on `M` type parameter.
@@ -153,6 +485,14 @@ impl<M: Manager> fmt::Debug for Hooks<M> {
             .field("post_create", &self.post_create)
             .field("pre_recycle", &self.post_recycle)
             .field("post_recycle", &self.post_recycle)
+            .field("on_acquire", &self.on_acquire)
+            .field("pre_return", &self.pre_return)
+            .field("on_detach", &self.on_detach)
+            .field("on_create_error", &self.on_create_error)
+            .field("on_recycle_error", &self.on_recycle_error)
+            .field("on_event", &self.on_event)
+            .field("on_timeout", &self.on_timeout)
+            .field("describe_error", &self.describe_error.is_some())
             .finish()
     }
 }
@@ -164,6 +504,14 @@ impl<M: Manager> Default for Hooks<M> {
             pre_recycle: HookVec::default(),
             post_create: HookVec::default(),
             post_recycle: HookVec::default(),
+            on_acquire: AcquireHookVec::default(),
+            pre_return: SyncHookVec::default(),
+            on_detach: NotifyHookVec::default(),
+            on_create_error: FnVec::default(),
+            on_recycle_error: FnVec::default(),
+            on_event: FnVec::default(),
+            on_timeout: FnVec::default(),
+            describe_error: None,
         }
     }
 }
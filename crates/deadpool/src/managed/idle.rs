@@ -0,0 +1,406 @@
+//! Storage of idle objects split into shards to reduce lock contention.
+
+use super::{
+    slab::Slab,
+    sync::{ArrayQueue, AtomicUsize, Mutex, Ordering, SegQueue},
+    QueueMode,
+};
+
+/// Queue of idle objects which is split into shards.
+///
+/// With [`QueueMode::Fifo`] every shard is a lock-free queue so checking out
+/// and returning objects never blocks. There is no lock-free stack which
+/// could be used for [`QueueMode::Lifo`] so in that case every shard is a
+/// [`Slab`] protected by its own mutex instead.
+///
+/// Objects are distributed across the shards in a round robin fashion.
+/// Within a shard objects are always pushed to the back so the front holds
+/// the object which has been idle for the longest time.
+///
+/// The storage of all shards is allocated up front for the capacity the
+/// queue is created with. Checking out and returning objects therefore
+/// doesn't allocate unless the pool grows beyond that capacity.
+///
+/// Objects which have been validated ahead of time are kept in a separate
+/// buffer and handed out before all other objects. See
+/// [`PoolConfig::recycle_ahead`](super::PoolConfig::recycle_ahead).
+#[derive(Debug)]
+pub(crate) struct IdleQueue<T> {
+    shards: Box<[Shard<T>]>,
+    ready: RingQueue<T>,
+    next_push: AtomicUsize,
+    next_pop: AtomicUsize,
+}
+
+// The lock-free queues are padded to the size of a cache line on purpose.
+// This also keeps the shards from sharing cache lines.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+enum Shard<T> {
+    Fifo(RingQueue<T>),
+    Lifo(Mutex<Slab<T>>),
+}
+
+/// Lock-free queue which is backed by a ring buffer of fixed capacity.
+///
+/// Objects not fitting into the ring buffer are put into an unbounded queue
+/// which allocates its storage in blocks. As long as that queue isn't empty
+/// all objects are pushed to it so the order of the objects is preserved.
+#[derive(Debug)]
+struct RingQueue<T> {
+    ring: ArrayQueue<T>,
+    spill: SegQueue<T>,
+}
+
+impl<T> RingQueue<T> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            ring: ArrayQueue::new(capacity.max(1)),
+            spill: SegQueue::new(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.ring.len() + self.spill.len()
+    }
+
+    /// Pushes an object to the ring buffer and returns it if there is no
+    /// room left.
+    fn try_push(&self, obj: T) -> Result<(), T> {
+        if self.spill.is_empty() {
+            self.ring.push(obj)
+        } else {
+            Err(obj)
+        }
+    }
+
+    fn push(&self, obj: T) {
+        if let Err(obj) = self.try_push(obj) {
+            self.spill.push(obj);
+        }
+    }
+
+    fn pop(&self) -> Option<T> {
+        self.ring.pop().or_else(|| self.spill.pop())
+    }
+}
+
+impl<T> Shard<T> {
+    fn len(&self) -> usize {
+        match self {
+            Self::Fifo(queue) => queue.len(),
+            Self::Lifo(queue) => queue.lock().unwrap().len(),
+        }
+    }
+
+    fn try_push_back(&self, obj: T) -> Result<(), T> {
+        match self {
+            Self::Fifo(queue) => queue.try_push(obj),
+            Self::Lifo(queue) => {
+                queue.lock().unwrap().push_back(obj);
+                Ok(())
+            }
+        }
+    }
+
+    fn push_back(&self, obj: T) {
+        match self {
+            Self::Fifo(queue) => queue.push(obj),
+            Self::Lifo(queue) => queue.lock().unwrap().push_back(obj),
+        }
+    }
+
+    fn pop_front(&self) -> Option<T> {
+        match self {
+            Self::Fifo(queue) => queue.pop(),
+            Self::Lifo(queue) => queue.lock().unwrap().pop_front(),
+        }
+    }
+
+    fn pop(&self) -> Option<T> {
+        match self {
+            Self::Fifo(queue) => queue.pop(),
+            Self::Lifo(queue) => queue.lock().unwrap().pop_back(),
+        }
+    }
+}
+
+impl<T> IdleQueue<T> {
+    /// Creates a new queue with the given number of `shards` which is at
+    /// least one. Room for `capacity` objects and `ready_capacity` objects
+    /// in the ready buffer is allocated up front.
+    pub(crate) fn new(
+        shards: usize,
+        capacity: usize,
+        ready_capacity: usize,
+        queue_mode: QueueMode,
+    ) -> Self {
+        let shards = shards.max(1);
+        let shard_capacity = capacity.div_ceil(shards);
+        Self {
+            shards: (0..shards)
+                .map(|_| match queue_mode {
+                    QueueMode::Fifo => Shard::Fifo(RingQueue::new(shard_capacity)),
+                    QueueMode::Lifo => Shard::Lifo(Mutex::new(Slab::with_capacity(shard_capacity))),
+                })
+                .collect(),
+            ready: RingQueue::new(ready_capacity),
+            next_push: AtomicUsize::new(0),
+            next_pop: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the total number of objects in all shards including the
+    /// ready buffer.
+    pub(crate) fn len(&self) -> usize {
+        self.shards.iter().map(Shard::len).sum::<usize>() + self.ready.len()
+    }
+
+    /// Returns the number of objects in the ready buffer.
+    pub(crate) fn ready_len(&self) -> usize {
+        self.ready.len()
+    }
+
+    /// Pushes an object which has been validated to the ready buffer.
+    pub(crate) fn push_ready(&self, obj: T) {
+        self.ready.push(obj);
+    }
+
+    /// Indicates whether all shards are empty.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Pushes an object to the back of the next shard and returns the index
+    /// of that shard. If the storage of that shard is used up the other
+    /// shards are tried before allocating more storage.
+    pub(crate) fn push(&self, obj: T) -> usize {
+        let start = self.next_push.fetch_add(1, Ordering::Relaxed);
+        let mut obj = obj;
+        for i in 0..self.shards.len() {
+            let index = (start + i) % self.shards.len();
+            match self.shards[index].try_push_back(obj) {
+                Ok(()) => return index,
+                Err(rejected) => obj = rejected,
+            }
+        }
+        let index = start % self.shards.len();
+        self.shards[index].push_back(obj);
+        index
+    }
+
+    /// Pops an object from the ready buffer or, if that is empty,
+    /// according to the [`QueueMode`] of this queue.
+    pub(crate) fn pop(&self) -> Option<T> {
+        self.ready.pop().or_else(|| self.pop_unready())
+    }
+
+    /// Pops an object according to the [`QueueMode`] of this queue skipping
+    /// the ready buffer. The shards are searched starting with the next
+    /// shard in a round robin fashion.
+    pub(crate) fn pop_unready(&self) -> Option<T> {
+        self.find_map(Shard::pop)
+    }
+
+    /// Pops the object which has been idle for the longest time of one of
+    /// the shards. The ready buffer is only used once all shards are empty.
+    pub(crate) fn pop_oldest(&self) -> Option<T> {
+        self.find_map(Shard::pop_front).or_else(|| self.ready.pop())
+    }
+
+    fn find_map(&self, f: impl Fn(&Shard<T>) -> Option<T>) -> Option<T> {
+        let start = self.next_pop.fetch_add(1, Ordering::Relaxed);
+        (0..self.shards.len()).find_map(|i| f(&self.shards[(start + i) % self.shards.len()]))
+    }
+
+    /// Removes objects from the front of the shard with the given `index`
+    /// as long as they match the `predicate`.
+    ///
+    /// The front of a lock-free shard can't be inspected without removing
+    /// the object and putting it back would break the order in which the
+    /// objects are handed out. Lock-free shards are therefore left untouched
+    /// and need to be cleaned up using [`IdleQueue::retain()`] instead.
+    pub(crate) fn pop_front_while(
+        &self,
+        index: usize,
+        mut predicate: impl FnMut(&T) -> bool,
+    ) -> Vec<T> {
+        let mut removed = Vec::new();
+        match &self.shards[index] {
+            Shard::Fifo(_) => {}
+            Shard::Lifo(queue) => {
+                let mut queue = queue.lock().unwrap();
+                while queue.front().is_some_and(&mut predicate) {
+                    removed.extend(queue.pop_front());
+                }
+            }
+        }
+        removed
+    }
+
+    /// Removes all objects for which the `predicate` returns `false`.
+    ///
+    /// Lock-free shards are processed by taking all objects out and pushing
+    /// the retained ones back.
+    pub(crate) fn retain(&self, mut predicate: impl FnMut(&mut T) -> bool) -> Vec<T> {
+        let mut removed = Vec::new();
+        for shard in self.shards.iter() {
+            match shard {
+                Shard::Fifo(queue) => Self::retain_queue(queue, &mut predicate, &mut removed),
+                Shard::Lifo(queue) => queue
+                    .lock()
+                    .unwrap()
+                    .retain(&mut predicate, |obj| removed.push(obj)),
+            }
+        }
+        Self::retain_queue(&self.ready, &mut predicate, &mut removed);
+        removed
+    }
+
+    fn retain_queue(
+        queue: &RingQueue<T>,
+        mut predicate: impl FnMut(&mut T) -> bool,
+        removed: &mut Vec<T>,
+    ) {
+        let mut retained = Vec::with_capacity(queue.len());
+        while let Some(mut obj) = queue.pop() {
+            if predicate(&mut obj) {
+                retained.push(obj);
+            } else {
+                removed.push(obj);
+            }
+        }
+        for obj in retained {
+            queue.push(obj);
+        }
+    }
+
+    /// Calls `f` for every object without removing any of them. See
+    /// [`IdleQueue::retain()`].
+    pub(crate) fn for_each(&self, mut f: impl FnMut(&T)) {
+        let removed = self.retain(|obj| {
+            f(obj);
+            true
+        });
+        debug_assert!(removed.is_empty());
+    }
+
+    /// Shrinks the capacity of all mutex protected shards to fit the given
+    /// `capacity`. The ring buffers of lock-free shards keep their size.
+    pub(crate) fn shrink_to(&self, capacity: usize) {
+        let capacity = capacity.div_ceil(self.shards.len());
+        for shard in self.shards.iter() {
+            if let Shard::Lifo(queue) = shard {
+                queue.lock().unwrap().shrink_to(capacity);
+            }
+        }
+    }
+
+    /// Reserves room for `additional` objects across all mutex protected
+    /// shards. Lock-free shards put objects which don't fit into their ring
+    /// buffers into a queue which grows on demand instead.
+    pub(crate) fn reserve(&self, additional: usize) {
+        let additional = additional.div_ceil(self.shards.len());
+        for shard in self.shards.iter() {
+            if let Shard::Lifo(queue) = shard {
+                queue.lock().unwrap().reserve(additional);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, deadpool_loom))]
+mod loom_tests {
+    use loom::{sync::Arc, thread};
+
+    use super::{IdleQueue, QueueMode};
+
+    fn queue(shards: usize, queue_mode: QueueMode) -> Arc<IdleQueue<usize>> {
+        Arc::new(IdleQueue::new(shards, 2, 1, queue_mode))
+    }
+
+    /// Every object which is returned concurrently is checked out exactly
+    /// once.
+    fn push_pop(queue_mode: QueueMode) {
+        loom::model(move || {
+            let queue = queue(2, queue_mode);
+            let _ = queue.push(0);
+            let returner = thread::spawn({
+                let queue = queue.clone();
+                move || {
+                    let _ = queue.push(1);
+                }
+            });
+            let checkout = thread::spawn({
+                let queue = queue.clone();
+                move || queue.pop()
+            });
+            returner.join().unwrap();
+            let mut objects: Vec<_> = checkout.join().unwrap().into_iter().collect();
+            while let Some(obj) = queue.pop_oldest() {
+                objects.push(obj);
+            }
+            objects.sort_unstable();
+            assert_eq!(objects, vec![0, 1]);
+        });
+    }
+
+    #[test]
+    fn push_pop_fifo() {
+        push_pop(QueueMode::Fifo);
+    }
+
+    #[test]
+    fn push_pop_lifo() {
+        push_pop(QueueMode::Lifo);
+    }
+
+    /// Concurrent checkouts never hand out the same object twice.
+    #[test]
+    fn concurrent_pop() {
+        loom::model(|| {
+            let queue = queue(1, QueueMode::Lifo);
+            let _ = queue.push(0);
+            queue.push_ready(1);
+            let checkouts: Vec<_> = (0..2)
+                .map(|_| {
+                    let queue = queue.clone();
+                    thread::spawn(move || queue.pop())
+                })
+                .collect();
+            let mut objects: Vec<_> = checkouts
+                .into_iter()
+                .filter_map(|checkout| checkout.join().unwrap())
+                .collect();
+            objects.sort_unstable();
+            assert_eq!(objects, vec![0, 1]);
+            assert!(queue.is_empty());
+        });
+    }
+
+    /// Objects which are returned while the queue is being cleaned up are
+    /// neither lost nor removed twice.
+    #[test]
+    fn retain_push() {
+        loom::model(|| {
+            let queue = queue(2, QueueMode::Fifo);
+            let _ = queue.push(0);
+            let _ = queue.push(1);
+            let returner = thread::spawn({
+                let queue = queue.clone();
+                move || {
+                    let _ = queue.push(2);
+                }
+            });
+            let removed = queue.retain(|obj| *obj != 0);
+            returner.join().unwrap();
+            assert_eq!(removed, vec![0]);
+            let mut objects = Vec::new();
+            while let Some(obj) = queue.pop_oldest() {
+                objects.push(obj);
+            }
+            objects.sort_unstable();
+            assert_eq!(objects, vec![1, 2]);
+        });
+    }
+}
@@ -0,0 +1,435 @@
+//! Keyed version of the managed pool.
+//!
+//! A [`KeyedPool`] hands out objects which were created for a given key,
+//! e.g. connections to a tenant database or to a shard of a cluster. Every
+//! key gets a [`Pool`] of its own which is created on first use and
+//! configured by a shared [`PoolConfig`]. Sub-pools without any objects are
+//! removed by [`KeyedPool::remove_idle()`] which is called periodically if a
+//! [`PoolConfig::maintenance_interval`] is configured.
+//!
+//! # Example
+//!
+//! ```rust
+//! use deadpool::managed::{keyed, Metrics, RecycleResult};
+//!
+//! struct Manager {}
+//!
+//! impl keyed::KeyedManager<String> for Manager {
+//!     type Type = String;
+//!     type Error = ();
+//!
+//!     async fn create(&self, tenant: &String) -> Result<String, ()> {
+//!         Ok(format!("connection to {}", tenant))
+//!     }
+//!     async fn recycle(&self, _: &String, _: &mut String, _: &Metrics) -> RecycleResult<()> {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! type Pool = keyed::KeyedPool<String, Manager>;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let pool = Pool::builder(Manager {}).max_size(4).build().unwrap();
+//!     let conn = pool.get(&"tenant-a".to_string()).await.unwrap();
+//!     assert_eq!(*conn, "connection to tenant-a");
+//! }
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    hash::Hash,
+    marker::PhantomData,
+    sync::{Arc, Mutex, Weak},
+    time::Duration,
+};
+
+use crate::{Runtime, Status};
+
+use super::{
+    BuildError, Manager, Metrics, Object, Pool, PoolBuilder, PoolConfig, PoolError, RecycleResult,
+    Validation,
+};
+
+/// Manager responsible for creating new objects for a key or recycling
+/// existing ones.
+///
+/// This is the keyed counterpart of [`Manager`].
+pub trait KeyedManager<K>: Sync + Send {
+    /// Type of objects that this [`KeyedManager`] creates and recycles.
+    type Type: Send;
+    /// Error that this [`KeyedManager`] can return when creating and/or
+    /// recycling objects.
+    type Error: Send;
+
+    /// Creates a new instance of [`KeyedManager::Type`] for the given `key`.
+    fn create(&self, key: &K) -> impl Future<Output = Result<Self::Type, Self::Error>> + Send;
+
+    /// Tries to recycle an instance of [`KeyedManager::Type`] which has been
+    /// created for the given `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KeyedManager::Error`] if the instance couldn't be recycled.
+    fn recycle(
+        &self,
+        key: &K,
+        obj: &mut Self::Type,
+        metrics: &Metrics,
+    ) -> impl Future<Output = RecycleResult<Self::Error>> + Send;
+
+    /// Cheaply checks an idle instance of [`KeyedManager::Type`] which has
+    /// been created for the given `key`. See [`Manager::validate()`].
+    fn validate(&self, _key: &K, _obj: &mut Self::Type, _metrics: &Metrics) -> Validation {
+        Validation::Recycle
+    }
+
+    /// Destroys an instance of [`KeyedManager::Type`] which has been
+    /// created for the given `key`. See [`Manager::destroy()`].
+    fn destroy(&self, _key: &K, obj: Self::Type) -> impl Future<Output = ()> + Send {
+        drop(obj);
+        async {}
+    }
+
+    /// Detaches an instance of [`KeyedManager::Type`] from this
+    /// [`KeyedManager`]. See [`Manager::detach()`].
+    fn detach(&self, _key: &K, _obj: &mut Self::Type) {}
+}
+
+/// [`Manager`] of the [`Pool`] of a single key which delegates to a shared
+/// [`KeyedManager`].
+pub struct KeyManager<K, M> {
+    key: K,
+    manager: Arc<M>,
+}
+
+impl<K, M> KeyManager<K, M> {
+    /// Returns the key of this [`KeyManager`].
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Returns the [`KeyedManager`] which is shared by all keys.
+    pub fn manager(&self) -> &M {
+        &self.manager
+    }
+}
+
+impl<K: fmt::Debug, M: fmt::Debug> fmt::Debug for KeyManager<K, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyManager")
+            .field("key", &self.key)
+            .field("manager", &self.manager)
+            .finish()
+    }
+}
+
+impl<K, M> Manager for KeyManager<K, M>
+where
+    K: Send + Sync,
+    M: KeyedManager<K>,
+{
+    type Type = M::Type;
+    type Error = M::Error;
+
+    fn create(&self) -> impl Future<Output = Result<M::Type, M::Error>> + Send {
+        self.manager.create(&self.key)
+    }
+
+    fn recycle(
+        &self,
+        obj: &mut M::Type,
+        metrics: &Metrics,
+    ) -> impl Future<Output = RecycleResult<M::Error>> + Send {
+        self.manager.recycle(&self.key, obj, metrics)
+    }
+
+    fn validate(&self, obj: &mut M::Type, metrics: &Metrics) -> Validation {
+        self.manager.validate(&self.key, obj, metrics)
+    }
+
+    fn destroy(&self, obj: M::Type) -> impl Future<Output = ()> + Send {
+        self.manager.destroy(&self.key, obj)
+    }
+
+    fn detach(&self, obj: &mut M::Type) {
+        self.manager.detach(&self.key, obj);
+    }
+}
+
+/// Object handed out by a [`KeyedPool`].
+pub type KeyedObject<K, M> = Object<KeyManager<K, M>>;
+
+/// Pool handing out objects created for a key. See the
+/// [module documentation](self) for details.
+///
+/// This struct can be cloned and transferred across thread boundaries and
+/// uses reference counting for its internal state.
+pub struct KeyedPool<K, M: KeyedManager<K>>
+where
+    K: Send + Sync,
+{
+    inner: Arc<KeyedPoolInner<K, M>>,
+}
+
+struct KeyedPoolInner<K, M: KeyedManager<K>>
+where
+    K: Send + Sync,
+{
+    manager: Arc<M>,
+    config: PoolConfig,
+    runtime: Option<Runtime>,
+    /// Sub-pools by key. `None` once the pool has been closed.
+    pools: Mutex<Option<KeyPools<K, M>>>,
+}
+
+type KeyPools<K, M> = HashMap<K, Pool<KeyManager<K, M>>>;
+
+impl<K, M> fmt::Debug for KeyedPool<K, M>
+where
+    K: fmt::Debug + Send + Sync,
+    M: fmt::Debug + KeyedManager<K>,
+    M::Type: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyedPool")
+            .field("manager", &self.inner.manager)
+            .field("config", &self.inner.config)
+            .field("runtime", &self.inner.runtime)
+            .field("pools", &self.inner.pools)
+            .finish()
+    }
+}
+
+impl<K, M> Clone for KeyedPool<K, M>
+where
+    K: Send + Sync,
+    M: KeyedManager<K>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K, M> KeyedPool<K, M>
+where
+    K: Clone + Eq + Hash + Send + Sync + 'static,
+    M: KeyedManager<K> + 'static,
+{
+    /// Instantiates a builder for a new [`KeyedPool`].
+    pub fn builder(manager: M) -> KeyedPoolBuilder<K, M> {
+        KeyedPoolBuilder {
+            manager,
+            config: PoolConfig::default(),
+            runtime: None,
+            _key: PhantomData,
+        }
+    }
+
+    /// Retrieves an object for the given `key` from this [`KeyedPool`] or
+    /// waits for one to become available.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn get(&self, key: &K) -> Result<KeyedObject<K, M>, PoolError<M::Error>> {
+        self.pool(key).ok_or(PoolError::Closed)?.get().await
+    }
+
+    /// Returns the [`Pool`] of the given `key` creating it if necessary.
+    ///
+    /// Returns [`None`] if this [`KeyedPool`] has been closed.
+    pub fn pool(&self, key: &K) -> Option<Pool<KeyManager<K, M>>> {
+        let mut pools = self.inner.pools.lock().unwrap();
+        let pools = pools.as_mut()?;
+        if let Some(pool) = pools.get(key) {
+            return Some(pool.clone());
+        }
+        let mut builder = PoolBuilder::new(KeyManager {
+            key: key.clone(),
+            manager: self.inner.manager.clone(),
+        });
+        builder.config = self.inner.config;
+        builder.runtime = self.inner.runtime;
+        let pool = Pool::from_builder(builder);
+        let _ = pools.insert(key.clone(), pool.clone());
+        Some(pool)
+    }
+
+    /// Retrieves the [`Status`] of the [`Pool`] of the given `key`.
+    ///
+    /// Returns [`None`] if there is no [`Pool`] for that `key`.
+    pub fn status(&self, key: &K) -> Option<Status> {
+        let pools = self.inner.pools.lock().unwrap();
+        pools.as_ref()?.get(key).map(Pool::status)
+    }
+
+    /// Returns the number of keys which currently have a [`Pool`].
+    pub fn len(&self) -> usize {
+        let pools = self.inner.pools.lock().unwrap();
+        pools.as_ref().map_or(0, HashMap::len)
+    }
+
+    /// Indicates whether there are no [`Pool`]s at all.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes the [`Pool`]s of all keys which have neither objects nor
+    /// tasks using them.
+    ///
+    /// Returns the number of removed [`Pool`]s.
+    pub fn remove_idle(&self) -> usize {
+        self.inner.remove_idle()
+    }
+
+    /// Closes this [`KeyedPool`] and the [`Pool`]s of all keys.
+    ///
+    /// See [`Pool::close()`] for details.
+    pub fn close(&self) {
+        let pools = self.inner.pools.lock().unwrap().take();
+        for pool in pools.into_iter().flat_map(HashMap::into_values) {
+            pool.close();
+        }
+    }
+
+    /// Indicates whether this [`KeyedPool`] has been closed.
+    pub fn is_closed(&self) -> bool {
+        self.inner.pools.lock().unwrap().is_none()
+    }
+
+    /// Returns the [`KeyedManager`] of this [`KeyedPool`].
+    pub fn manager(&self) -> &M {
+        &self.inner.manager
+    }
+}
+
+impl<K, M> KeyedPoolInner<K, M>
+where
+    K: Eq + Hash + Send + Sync,
+    M: KeyedManager<K>,
+{
+    fn remove_idle(&self) -> usize {
+        let mut pools = self.pools.lock().unwrap();
+        let Some(pools) = pools.as_mut() else {
+            return 0;
+        };
+        let len = pools.len();
+        // Pools are only cloned while holding the lock. A pool which isn't
+        // referenced anywhere else can't be in use by a caller of `get`.
+        pools.retain(|_, pool| Arc::strong_count(&pool.inner) > 1 || pool.status().size > 0);
+        len - pools.len()
+    }
+}
+
+/// Builder for [`KeyedPool`]s.
+///
+/// Instances of this are created by calling the [`KeyedPool::builder()`]
+/// method.
+#[must_use = "builder does nothing itself, use `.build()` to build it"]
+pub struct KeyedPoolBuilder<K, M> {
+    manager: M,
+    config: PoolConfig,
+    runtime: Option<Runtime>,
+    _key: PhantomData<fn() -> K>,
+}
+
+impl<K, M: fmt::Debug> fmt::Debug for KeyedPoolBuilder<K, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyedPoolBuilder")
+            .field("manager", &self.manager)
+            .field("config", &self.config)
+            .field("runtime", &self.runtime)
+            .finish()
+    }
+}
+
+impl<K, M> KeyedPoolBuilder<K, M>
+where
+    K: Clone + Eq + Hash + Send + Sync + 'static,
+    M: KeyedManager<K> + 'static,
+{
+    /// Builds the [`KeyedPool`].
+    ///
+    /// # Errors
+    ///
+    /// See [`BuildError`] for details.
+    pub fn build(mut self) -> Result<KeyedPool<K, M>, BuildError> {
+        self.config.validate().map_err(BuildError::InvalidConfig)?;
+        if self.runtime.is_none() {
+            self.runtime = Runtime::detect();
+        }
+        if self.config.requires_runtime() && self.runtime.is_none() {
+            return Err(BuildError::NoRuntimeSpecified);
+        }
+        let pool = KeyedPool {
+            inner: Arc::new(KeyedPoolInner {
+                manager: Arc::new(self.manager),
+                config: self.config,
+                runtime: self.runtime,
+                pools: Mutex::new(Some(HashMap::new())),
+            }),
+        };
+        if let (Some(runtime), Some(interval)) = (self.runtime, self.config.maintenance_interval) {
+            spawn_remove_idle(runtime, interval, Arc::downgrade(&pool.inner));
+        }
+        Ok(pool)
+    }
+
+    /// Sets the [`PoolConfig`] which is used for the [`Pool`] of every key.
+    pub fn config(mut self, value: PoolConfig) -> Self {
+        self.config = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::max_size`] of the [`Pool`] of every key.
+    pub fn max_size(mut self, value: usize) -> Self {
+        self.config.max_size = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::idle_timeout`] of the [`Pool`] of every key.
+    pub fn idle_timeout(mut self, value: Option<Duration>) -> Self {
+        self.config.idle_timeout = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::maintenance_interval`] of the [`Pool`] of
+    /// every key. This also removes the [`Pool`]s without objects.
+    pub fn maintenance_interval(mut self, value: Option<Duration>) -> Self {
+        self.config.maintenance_interval = value;
+        self
+    }
+
+    /// Sets the [`Runtime`].
+    ///
+    /// See [`PoolBuilder::runtime()`] for details.
+    pub fn runtime(mut self, value: Runtime) -> Self {
+        self.runtime = Some(value);
+        self
+    }
+}
+
+/// Spawns a task periodically removing the [`Pool`]s without objects.
+fn spawn_remove_idle<K, M>(runtime: Runtime, interval: Duration, pool: Weak<KeyedPoolInner<K, M>>)
+where
+    K: Eq + Hash + Send + Sync + 'static,
+    M: KeyedManager<K> + 'static,
+{
+    runtime.spawn(async move {
+        loop {
+            runtime.sleep(interval).await;
+            let Some(pool) = pool.upgrade() else {
+                break;
+            };
+            if pool.pools.lock().unwrap().is_none() {
+                break;
+            }
+            let _ = pool.remove_idle();
+        }
+    });
+}
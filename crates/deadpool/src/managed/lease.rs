@@ -0,0 +1,114 @@
+//! Tracking of the objects which are currently handed out by a pool. See
+//! [`PoolConfig::lease_timeout`] and the `debug-leases` feature.
+//!
+//! [`PoolConfig::lease_timeout`]: super::PoolConfig::lease_timeout
+
+#[cfg(feature = "debug-leases")]
+use std::{backtrace::Backtrace, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::clock;
+
+/// Information about an [`Object`] which is currently handed out by a
+/// [`Pool`]. See [`Pool::leases()`].
+///
+/// [`Object`]: super::Object
+/// [`Pool`]: super::Pool
+/// [`Pool::leases()`]: super::Pool::leases
+#[cfg(feature = "debug-leases")]
+#[derive(Clone, Debug)]
+pub struct LeaseInfo {
+    /// Id of the object.
+    pub id: usize,
+    /// Duration the object has been held for.
+    pub held: Duration,
+    /// Backtrace captured when the object was handed out.
+    pub backtrace: Arc<Backtrace>,
+}
+
+/// Acquisition times of the objects which are currently handed out.
+#[derive(Debug, Default)]
+pub(crate) struct Leases {
+    leases: Mutex<HashMap<usize, Lease>>,
+}
+
+#[derive(Debug)]
+struct Lease {
+    acquired: Instant,
+    /// Whether the lease has been reported as exceeded already. Every
+    /// lease is only reported once.
+    reported: bool,
+    #[cfg(feature = "debug-leases")]
+    backtrace: Arc<Backtrace>,
+}
+
+impl Leases {
+    /// Starts tracking the object with the given `id`.
+    pub(crate) fn acquire(&self, id: usize) {
+        let lease = Lease {
+            acquired: clock::now(),
+            reported: false,
+            #[cfg(feature = "debug-leases")]
+            backtrace: Arc::new(Backtrace::force_capture()),
+        };
+        let _ = self.leases.lock().unwrap().insert(id, lease);
+    }
+
+    /// Stops tracking the object with the given `id`.
+    pub(crate) fn release(&self, id: usize) {
+        let _ = self.leases.lock().unwrap().remove(&id);
+    }
+
+    /// Returns the objects which have been held longer than the given
+    /// `timeout` and haven't been reported, yet.
+    pub(crate) fn exceeded(&self, timeout: Duration) -> Vec<ExceededLease> {
+        let mut leases = self.leases.lock().unwrap();
+        leases
+            .iter_mut()
+            .filter_map(|(id, lease)| {
+                let held = clock::elapsed(lease.acquired);
+                if lease.reported || held < timeout {
+                    return None;
+                }
+                lease.reported = true;
+                Some(ExceededLease {
+                    id: *id,
+                    held,
+                    #[cfg(feature = "debug-leases")]
+                    backtrace: lease.backtrace.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns all current leases ordered by their id.
+    #[cfg(feature = "debug-leases")]
+    pub(crate) fn snapshot(&self) -> Vec<LeaseInfo> {
+        let mut leases = self
+            .leases
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, lease)| LeaseInfo {
+                id: *id,
+                held: clock::elapsed(lease.acquired),
+                backtrace: lease.backtrace.clone(),
+            })
+            .collect::<Vec<_>>();
+        leases.sort_by_key(|lease| lease.id);
+        leases
+    }
+}
+
+/// A lease which has been held longer than the lease timeout.
+#[derive(Debug)]
+pub(crate) struct ExceededLease {
+    pub(crate) id: usize,
+    pub(crate) held: Duration,
+    #[cfg(feature = "debug-leases")]
+    pub(crate) backtrace: Arc<Backtrace>,
+}
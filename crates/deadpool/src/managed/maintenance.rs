@@ -0,0 +1,169 @@
+//! Background tasks enforcing the limits configured in [`PoolConfig`].
+//!
+//! [`PoolConfig`]: super::PoolConfig
+
+use std::{future::Future, sync::Arc, time::Duration};
+
+use tokio::sync::mpsc;
+
+use crate::Runtime;
+
+use super::{object::ObjectInner, AutoscaleConfig, Manager, Pool, WeakPool};
+
+/// Spawns the maintenance task for the given `pool`.
+pub(crate) fn spawn<M: Manager + 'static>(runtime: Runtime, interval: Duration, pool: WeakPool<M>) {
+    spawn_periodic(runtime, interval, pool, "maintain", |pool| async move {
+        pool.maintain().await;
+    });
+}
+
+/// Spawns the health check task for the given `pool`.
+pub(crate) fn spawn_health_check<M: Manager + 'static>(
+    runtime: Runtime,
+    interval: Duration,
+    pool: WeakPool<M>,
+) {
+    spawn_periodic(runtime, interval, pool, "health_check", |pool| async move {
+        pool.check_health().await;
+    });
+}
+
+/// Spawns the task reporting objects held longer than the given `timeout`.
+pub(crate) fn spawn_lease_watchdog<M: Manager + 'static>(
+    runtime: Runtime,
+    timeout: Duration,
+    pool: WeakPool<M>,
+) {
+    spawn_periodic(
+        runtime,
+        timeout,
+        pool,
+        "lease_watchdog",
+        move |pool| async move {
+            pool.check_leases(timeout);
+        },
+    );
+}
+
+/// Spawns the autoscaler task for the given `pool`.
+pub(crate) fn spawn_autoscale<M: Manager + 'static>(
+    runtime: Runtime,
+    config: AutoscaleConfig,
+    pool: WeakPool<M>,
+) {
+    spawn_periodic(
+        runtime,
+        config.interval,
+        pool,
+        "autoscale",
+        move |pool| async move {
+            pool.autoscale(&config);
+        },
+    );
+}
+
+/// Spawns a task warming up the given `pool` with `n` objects.
+pub(crate) fn spawn_warm_up<M: Manager + 'static>(runtime: Runtime, n: usize, pool: WeakPool<M>) {
+    runtime.spawn(async move {
+        if let Some(pool) = pool.upgrade() {
+            // Objects which failed to be created are created on demand.
+            let _ = pool.warm_up(n).await;
+        }
+    });
+}
+
+/// Spawns the task recycling objects returned to the given `pool`.
+///
+/// Every object received via the `receiver` is recycled by a task of its
+/// own. The task stops once the pool is dropped.
+pub(crate) fn spawn_recycler<M: Manager + 'static>(
+    runtime: Runtime,
+    mut receiver: mpsc::UnboundedReceiver<ObjectInner<M>>,
+    pool: WeakPool<M>,
+) {
+    runtime.spawn(async move {
+        while let Some(inner) = receiver.recv().await {
+            let Some(pool) = pool.upgrade() else {
+                break;
+            };
+            runtime.spawn(async move {
+                pool.recycle_returned(inner).await;
+            });
+        }
+    });
+}
+
+/// Spawns the task calling [`Manager::destroy()`] for every object which is
+/// received via the `receiver`. The task stops once the pool is dropped.
+pub(crate) fn spawn_destroyer<M: Manager + 'static>(
+    runtime: Runtime,
+    mut receiver: mpsc::UnboundedReceiver<(Arc<M>, M::Type)>,
+) {
+    runtime.spawn(async move {
+        while let Some((manager, obj)) = receiver.recv().await {
+            runtime.spawn(async move {
+                manager.destroy(obj).await;
+            });
+        }
+    });
+}
+
+/// Spawns the task refilling the buffer of objects which are recycled
+/// ahead of time.
+///
+/// The buffer is refilled every time a message is received via the
+/// `receiver`. The task stops once the pool is dropped.
+pub(crate) fn spawn_refill<M: Manager + 'static>(
+    runtime: Runtime,
+    mut receiver: mpsc::Receiver<()>,
+    pool: WeakPool<M>,
+) {
+    runtime.spawn(async move {
+        while receiver.recv().await.is_some() {
+            let Some(pool) = pool.upgrade() else {
+                break;
+            };
+            pool.refill_ready().await;
+        }
+    });
+}
+
+/// Spawns a task calling `f` every `interval`.
+///
+/// The task only holds a [`WeakPool`] and stops as soon as the pool is
+/// closed or dropped. Every call is instrumented with a span named after the
+/// `task` if the `tracing` feature is enabled.
+fn spawn_periodic<M, F, Fut>(
+    runtime: Runtime,
+    interval: Duration,
+    pool: WeakPool<M>,
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))] task: &'static str,
+    f: F,
+) where
+    M: Manager + 'static,
+    F: Fn(Pool<M>) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    runtime.spawn(async move {
+        loop {
+            runtime.sleep(interval).await;
+            let Some(pool) = pool.upgrade() else {
+                break;
+            };
+            if pool.is_closed() {
+                break;
+            }
+            #[cfg(feature = "tracing")]
+            let span = tracing::debug_span!(
+                "deadpool.maintenance",
+                task,
+                pool.name = pool.name().unwrap_or_default(),
+                pool.labels = ?pool.labels(),
+            );
+            let future = f(pool);
+            #[cfg(feature = "tracing")]
+            let future = tracing::Instrument::instrument(future, span);
+            future.await;
+        }
+    });
+}
@@ -1,4 +1,4 @@
-use std::future::Future;
+use std::{any::Any, future::Future};
 
 use crate::managed::{Metrics, RecycleError};
 
@@ -13,6 +13,19 @@ pub trait Manager: Sync + Send {
     /// Creates a new instance of [`Manager::Type`].
     fn create(&self) -> impl Future<Output = Result<Self::Type, Self::Error>> + Send;
 
+    /// Creates a new instance of [`Manager::Type`] using the context which
+    /// was passed to [`super::Pool::get_with()`].
+    ///
+    /// The context is type-erased and can be accessed via
+    /// `ctx.downcast_ref::<T>()` using the type the caller passed in. The
+    /// default implementation ignores it and calls [`Manager::create()`].
+    fn create_with(
+        &self,
+        _ctx: &(dyn Any + Send + Sync),
+    ) -> impl Future<Output = Result<Self::Type, Self::Error>> + Send {
+        self.create()
+    }
+
     /// Tries to recycle an instance of [`Manager::Type`].
     ///
     /// # Errors
@@ -24,6 +37,49 @@ pub trait Manager: Sync + Send {
         metrics: &Metrics,
     ) -> impl Future<Output = RecycleResult<Self::Error>> + Send;
 
+    /// Cheaply checks an idle instance of [`Manager::Type`] right before it
+    /// is handed out.
+    ///
+    /// This method is called on every checkout and must not perform any
+    /// I/O. Returning [`Validation::Valid`] hands out the instance without
+    /// calling [`Manager::recycle()`] which is then only run by the
+    /// periodic [`super::PoolConfig::health_check_interval`].
+    ///
+    /// The default implementation returns [`Validation::Recycle`].
+    fn validate(&self, _obj: &mut Self::Type, _metrics: &Metrics) -> Validation {
+        Validation::Recycle
+    }
+
+    /// Returns the weight of an instance of [`Manager::Type`].
+    ///
+    /// An object with a weight of `n` takes up `n` units of the
+    /// [`super::PoolConfig::max_size`] while it is in use. This allows
+    /// mixing light and heavy objects in one [`super::Pool`] while keeping
+    /// an accurate budget. The weight is determined once after the object
+    /// has been created and limited to the `max_size` of the
+    /// [`super::Pool`]. Overflow objects don't take up any units.
+    ///
+    /// The default implementation returns `1`.
+    fn weight(&self, _obj: &Self::Type) -> usize {
+        1
+    }
+
+    /// Destroys an instance of [`Manager::Type`] which is discarded by the
+    /// [`super::Pool`].
+    ///
+    /// This method is called for every object which is evicted or removed
+    /// when closing the [`super::Pool`] and allows sending a graceful
+    /// terminate message instead of relying on [`Drop`]. It is run in a task
+    /// of its own and therefore only called if a [`crate::Runtime`] is
+    /// configured. Objects removed via [`super::Object::take()`] or
+    /// [`super::Pool::retain()`] are not destroyed.
+    ///
+    /// The default implementation simply drops the object.
+    fn destroy(&self, obj: Self::Type) -> impl Future<Output = ()> + Send {
+        drop(obj);
+        async {}
+    }
+
     /// Detaches an instance of [`Manager::Type`] from this [`Manager`].
     ///
     /// This method is called when using the [`super::Object::take()`] method for
@@ -35,3 +91,17 @@ pub trait Manager: Sync + Send {
 
 /// Result type of the [`Manager::recycle()`] method.
 pub type RecycleResult<E> = Result<(), RecycleError<E>>;
+
+/// Outcome of the [`Manager::validate()`] method.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Validation {
+    /// The object is usable and can be handed out without recycling it.
+    Valid,
+
+    /// The object is broken and must be discarded.
+    Invalid,
+
+    /// The object can't be checked cheaply and must be recycled via
+    /// [`Manager::recycle()`] before it is handed out.
+    Recycle,
+}
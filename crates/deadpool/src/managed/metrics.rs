@@ -1,7 +1,19 @@
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::{Duration, Instant};
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::clock;
+
 /// Statistics regarding an object returned by the pool
+///
+/// With the `serde` feature enabled this implements `Serialize`. As
+/// [`Instant`]s can't be serialized the [`Metrics::age()`] and the
+/// [`Metrics::last_used()`] duration are serialized instead of the
+/// instants.
+///
+/// With the `rt_tokio_1` feature all instants are taken from the clock of
+/// Tokio. Pausing it via `tokio::time::pause()` makes the durations
+/// deterministic in tests.
 #[derive(Clone, Copy, Debug)]
 #[must_use]
 pub struct Metrics {
@@ -11,20 +23,47 @@ pub struct Metrics {
     #[cfg(not(target_arch = "wasm32"))]
     /// The instant when this object was last used
     pub recycled: Option<Instant>,
-    /// The number of times the objects was recycled
+    #[cfg(not(target_arch = "wasm32"))]
+    /// The instant when this object was last returned to the pool
+    pub returned: Option<Instant>,
+    /// The number of times the object was recycled. This is updated by the
+    /// pool after every successful recycle.
     pub recycle_count: usize,
+    /// The number of times the object was handed out by the pool. This
+    /// includes the checkout which created the object.
+    pub use_count: usize,
 }
 
 impl Metrics {
     #[cfg(not(target_arch = "wasm32"))]
     /// Access the age of this object
     pub fn age(&self) -> Duration {
-        self.created.elapsed()
+        clock::elapsed(self.created)
     }
     #[cfg(not(target_arch = "wasm32"))]
     /// Get the time elapsed when this object was last used
     pub fn last_used(&self) -> Duration {
-        self.recycled.unwrap_or(self.created).elapsed()
+        let last_used = match (self.recycled, self.returned) {
+            (Some(recycled), Some(returned)) => recycled.max(returned),
+            (recycled, returned) => recycled.or(returned).unwrap_or(self.created),
+        };
+        clock::elapsed(last_used)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Metrics {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("Metrics", 4)?;
+        #[cfg(not(target_arch = "wasm32"))]
+        s.serialize_field("age", &self.age())?;
+        #[cfg(not(target_arch = "wasm32"))]
+        s.serialize_field("last_used", &self.last_used())?;
+        s.serialize_field("recycle_count", &self.recycle_count)?;
+        s.serialize_field("use_count", &self.use_count)?;
+        s.end()
     }
 }
 
@@ -32,10 +71,13 @@ impl Default for Metrics {
     fn default() -> Self {
         Self {
             #[cfg(not(target_arch = "wasm32"))]
-            created: Instant::now(),
+            created: clock::now(),
             #[cfg(not(target_arch = "wasm32"))]
             recycled: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            returned: None,
             recycle_count: 0,
+            use_count: 0,
         }
     }
 }
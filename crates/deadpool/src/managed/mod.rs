@@ -48,26 +48,71 @@
 //! For a more complete example please see
 //! [`deadpool-postgres`](https://crates.io/crates/deadpool-postgres) crate.
 
+mod acquire;
+mod any;
+mod breaker;
 mod builder;
 mod config;
+mod counters;
 mod dropguard;
+#[cfg(feature = "env")]
+mod env;
 mod errors;
+mod events;
+mod health;
 mod hooks;
+mod idle;
+pub mod keyed;
+mod lease;
+mod maintenance;
 mod manager;
 mod metrics;
 mod object;
+#[cfg(feature = "opentelemetry")]
+#[cfg_attr(docsrs, doc(cfg(feature = "opentelemetry")))]
+pub mod opentelemetry;
 mod pool;
+mod priority;
+#[cfg(feature = "prometheus")]
+#[cfg_attr(docsrs, doc(cfg(feature = "prometheus")))]
+pub mod prometheus;
+#[cfg(feature = "metrics")]
+mod recorder;
 pub mod reexports;
+mod slab;
+mod stats;
+mod sync;
+mod trace;
 
 pub use crate::Status;
 
+#[cfg(feature = "env")]
+#[cfg_attr(docsrs, doc(cfg(feature = "env")))]
+pub use crate::env::{EnvVars, FromEnvError};
+
+#[cfg(feature = "debug-leases")]
+#[cfg_attr(docsrs, doc(cfg(feature = "debug-leases")))]
+pub use self::lease::LeaseInfo;
+
 pub use self::{
+    acquire::Acquire,
+    any::{AnyPool, DynPool},
     builder::{BuildError, PoolBuilder},
-    config::{CreatePoolError, PoolConfig, QueueMode, Timeouts},
-    errors::{PoolError, RecycleError, TimeoutType},
-    hooks::{Hook, HookError, HookFuture, HookResult},
-    manager::{Manager, RecycleResult},
+    config::{
+        AutoscaleConfig, CircuitBreakerConfig, CreatePoolError, DetachMode, InvalidConfigError,
+        PoolConfig, QueueMode, RecycleTimeoutMode, RetryConfig, Timeouts,
+    },
+    counters::Counters,
+    errors::{
+        ErrorStage, LastError, PoolError, PoolErrorKind, RecycleError, Retryable, TimeoutError,
+        TimeoutType,
+    },
+    events::PoolEvent,
+    health::{Health, HealthProbe},
+    hooks::{AcquireHook, Hook, HookContext, HookError, HookFuture, HookResult, HookStage},
+    manager::{Manager, RecycleResult, Validation},
     metrics::Metrics,
-    object::Object,
-    pool::{Pool, RetainResult, WeakPool},
+    object::{MappedObject, Object, SharedObject},
+    pool::{Pool, RetainFuture, RetainResult, WeakPool},
+    stats::{Histogram, Rates, Stats, DEFAULT_HISTOGRAM_BUCKETS, DEFAULT_STATS_WINDOW},
 };
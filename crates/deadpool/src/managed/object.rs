@@ -1,9 +1,10 @@
 use std::{
     fmt,
     ops::{Deref, DerefMut},
+    sync::Arc,
 };
 
-use crate::managed::{Manager, Metrics, Pool, WeakPool};
+use crate::managed::{Manager, Metrics, Pool, PoolError, WeakPool};
 
 /// Wrapper around the actual pooled object which implements [`Deref`],
 /// [`DerefMut`] and [`Drop`] traits.
@@ -36,14 +37,37 @@ pub(crate) struct ObjectInner<M: Manager> {
     /// Actual pooled object.
     pub obj: M::Type,
 
-    /// The id of this object. This number is strictly monotonically
-    /// increasing. The order of IDs is equal to the time the objects
-    /// was created.
+    /// The id of this object. Unless a custom generator is set via
+    /// [`PoolBuilder::id_generator()`] this number is strictly
+    /// monotonically increasing. The order of IDs is equal to the time the
+    /// objects was created.
     ///
     /// This can be used to discard objects after a configuration change
     /// or simply identify an objects for debugging purposes.
+    ///
+    /// [`PoolBuilder::id_generator()`]: super::PoolBuilder::id_generator
     pub id: usize,
 
+    /// The generation of the pool at the time this object was created.
+    /// Objects of an older generation are discarded by the pool.
+    pub generation: usize,
+
+    /// Whether this object was created on top of the `max_size` of the
+    /// pool. Overflow objects are dropped when being returned.
+    pub overflow: bool,
+
+    /// Whether this object has been recycled when it was returned to the
+    /// pool. Such objects are handed out without recycling them again.
+    pub recycled: bool,
+
+    /// Number of permits this object holds while it is in use. See
+    /// [`Manager::weight()`].
+    pub weight: usize,
+
+    /// Whether this object has been pinned via [`Object::pin()`]. Pinned
+    /// objects are exempt from the limits of the pool.
+    pub pinned: bool,
+
     /// Object metrics.
     pub metrics: Metrics,
 }
@@ -51,13 +75,18 @@ pub(crate) struct ObjectInner<M: Manager> {
 impl<M: Manager> Object<M> {
     /// Takes this [`Object`] from its [`Pool`] permanently. This reduces the
     /// size of the [`Pool`].
+    ///
+    /// Whether the [`Pool`] may create a replacement for the object or
+    /// shrinks permanently is determined by the [`PoolConfig::detach_mode`].
+    ///
+    /// [`PoolConfig::detach_mode`]: super::PoolConfig::detach_mode
     #[must_use]
     pub fn take(mut this: Self) -> M::Type {
-        let mut inner = this.inner.take().unwrap().obj;
+        let mut inner = this.inner.take().unwrap();
         if let Some(pool) = Object::pool(&this) {
-            pool.inner.detach_object(&mut inner)
+            pool.inner.take_object(&mut inner)
         }
-        inner
+        inner.obj
     }
 
     /// Returns the unique ID of this object.
@@ -65,6 +94,11 @@ impl<M: Manager> Object<M> {
     /// Object IDs are strictly monotonically increasing — each new object
     /// receives an ID greater than that of the previously created object.
     /// However, IDs are not guaranteed to be consecutive; gaps may exist.
+    ///
+    /// IDs are generated by the function set via
+    /// [`PoolBuilder::id_generator()`] instead if there is one.
+    ///
+    /// [`PoolBuilder::id_generator()`]: super::PoolBuilder::id_generator
     pub fn id(this: &Self) -> usize {
         this.inner.as_ref().unwrap().id
     }
@@ -74,6 +108,54 @@ impl<M: Manager> Object<M> {
         &this.inner.as_ref().unwrap().metrics
     }
 
+    /// Replaces the wrapped object by a newly created one and returns the
+    /// previous one.
+    ///
+    /// The [`Object`] keeps its id and its slot in the [`Pool`]. Its
+    /// [`Metrics`] are reset as if it had just been created. This allows
+    /// recovering from a broken connection in place instead of taking it
+    /// via [`Object::take()`] and shrinking the [`Pool`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoolError::Closed`] if the [`Pool`] has been dropped.
+    /// Otherwise the same errors as [`Pool::get()`] are returned for
+    /// creating the object. The [`Object`] keeps its previous value in that
+    /// case.
+    pub async fn replace(this: &mut Self) -> Result<M::Type, PoolError<M::Error>> {
+        let Some(pool) = Object::pool(this) else {
+            return Err(PoolError::Closed);
+        };
+        pool.replace_object(this.inner.as_mut().unwrap()).await
+    }
+
+    /// Pins this [`Object`] so it is never discarded due to the
+    /// [`PoolConfig::max_lifetime`], [`PoolConfig::idle_timeout`] or
+    /// [`PoolConfig::max_uses`] limits.
+    ///
+    /// This is meant for objects carrying session state which can't be
+    /// restored. The [`Object`] stays pinned while being idle until
+    /// [`Object::unpin()`] is called. It is still discarded if recycling it
+    /// fails or if it is invalidated via [`Pool::invalidate_all()`].
+    ///
+    /// [`PoolConfig::idle_timeout`]: super::PoolConfig::idle_timeout
+    /// [`PoolConfig::max_lifetime`]: super::PoolConfig::max_lifetime
+    /// [`PoolConfig::max_uses`]: super::PoolConfig::max_uses
+    pub fn pin(this: &mut Self) {
+        this.inner.as_mut().unwrap().pinned = true;
+    }
+
+    /// Unpins this [`Object`] making the configured limits apply to it
+    /// again. See [`Object::pin()`].
+    pub fn unpin(this: &mut Self) {
+        this.inner.as_mut().unwrap().pinned = false;
+    }
+
+    /// Indicates whether this [`Object`] is pinned. See [`Object::pin()`].
+    pub fn is_pinned(this: &Self) -> bool {
+        this.inner.as_ref().unwrap().pinned
+    }
+
     /// Returns the [`Pool`] this [`Object`] belongs to.
     ///
     /// Since [`Object`]s only hold a [`std::sync::Weak`] reference to the
@@ -81,6 +163,39 @@ impl<M: Manager> Object<M> {
     pub fn pool(this: &Self) -> Option<Pool<M>> {
         this.pool.upgrade()
     }
+
+    /// Turns this [`Object`] into a [`MappedObject`] which derefs to a
+    /// component of the pooled object, e.g. a client inside a struct.
+    ///
+    /// The component is created once by calling `f` with the pooled object
+    /// and owned by the [`MappedObject`]. It therefore can't borrow from the
+    /// pooled object, which makes this a good fit for cheaply cloneable
+    /// handles. The [`Object`] is still returned to its [`Pool`] once the
+    /// [`MappedObject`] is dropped.
+    ///
+    /// ```rust,ignore
+    /// let client = Object::map(obj, |conn| conn.client.clone());
+    /// ```
+    pub fn map<T>(mut this: Self, f: impl FnOnce(&mut M::Type) -> T) -> MappedObject<M, T> {
+        let value = f(&mut this);
+        MappedObject { value, obj: this }
+    }
+
+    /// Like [`Object::map()`] but for components which might not exist,
+    /// e.g. a variant of an enum.
+    ///
+    /// # Errors
+    ///
+    /// Returns this [`Object`] if `f` returns [`None`].
+    pub fn try_map<T>(
+        mut this: Self,
+        f: impl FnOnce(&mut M::Type) -> Option<T>,
+    ) -> Result<MappedObject<M, T>, Self> {
+        match f(&mut this) {
+            Some(value) => Ok(MappedObject { value, obj: this }),
+            None => Err(this),
+        }
+    }
 }
 
 impl<M: Manager> Drop for Object<M> {
@@ -117,3 +232,126 @@ impl<M: Manager> AsMut<M::Type> for Object<M> {
         self
     }
 }
+
+/// [`Object`] which might be used by multiple holders at the same time.
+///
+/// Instances of this are handed out by [`Pool::get_shared()`]. Cloning a
+/// [`SharedObject`] adds another holder without checking the
+/// [`PoolConfig::max_holders`]. The [`Object`] is returned to its [`Pool`]
+/// once the last holder has been dropped.
+///
+/// [`PoolConfig::max_holders`]: super::PoolConfig::max_holders
+#[must_use]
+pub struct SharedObject<M: Manager> {
+    pub(crate) inner: Arc<Object<M>>,
+}
+
+impl<M> fmt::Debug for SharedObject<M>
+where
+    M: fmt::Debug + Manager,
+    M::Type: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedObject")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<M: Manager> SharedObject<M> {
+    /// Returns the number of holders of this [`SharedObject`].
+    pub fn holders(this: &Self) -> usize {
+        Arc::strong_count(&this.inner)
+    }
+
+    /// Returns the shared [`Object`].
+    pub fn object(this: &Self) -> &Object<M> {
+        &this.inner
+    }
+}
+
+impl<M: Manager> Clone for SharedObject<M> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<M: Manager> Deref for SharedObject<M> {
+    type Target = M::Type;
+    fn deref(&self) -> &M::Type {
+        &self.inner
+    }
+}
+
+impl<M: Manager> AsRef<M::Type> for SharedObject<M> {
+    fn as_ref(&self) -> &M::Type {
+        self
+    }
+}
+
+/// [`Object`] which derefs to a component of the pooled object.
+///
+/// Instances of this are created by [`Object::map()`] and
+/// [`Object::try_map()`]. The component is dropped before the [`Object`] is
+/// returned to its [`Pool`] once this is dropped.
+#[must_use]
+pub struct MappedObject<M: Manager, T> {
+    // Declared first so the component is dropped before the object is
+    // returned to the pool.
+    value: T,
+    obj: Object<M>,
+}
+
+impl<M, T> fmt::Debug for MappedObject<M, T>
+where
+    M: fmt::Debug + Manager,
+    M::Type: fmt::Debug,
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MappedObject")
+            .field("value", &self.value)
+            .field("obj", &self.obj)
+            .finish()
+    }
+}
+
+impl<M: Manager, T> MappedObject<M, T> {
+    /// Returns the underlying [`Object`].
+    pub fn object(this: &Self) -> &Object<M> {
+        &this.obj
+    }
+
+    /// Drops the component and turns this back into the underlying
+    /// [`Object`].
+    pub fn into_object(this: Self) -> Object<M> {
+        this.obj
+    }
+}
+
+impl<M: Manager, T> Deref for MappedObject<M, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<M: Manager, T> DerefMut for MappedObject<M, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<M: Manager, T> AsRef<T> for MappedObject<M, T> {
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<M: Manager, T> AsMut<T> for MappedObject<M, T> {
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
@@ -0,0 +1,149 @@
+//! Exposing the statistics of [`Pool`]s as [`opentelemetry`] instruments.
+//!
+//! The instruments follow the semantic conventions for database client
+//! connection pools:
+//!
+//! | Instrument | Type | Value |
+//! | ---------- | ---- | ----- |
+//! | `db.client.connections.usage` | up-down counter | [`Status::available`] with `state` = `idle` and the remaining [`Status::size`] with `state` = `used` |
+//! | `db.client.connections.max` | up-down counter | [`Status::max_size`] |
+//! | `db.client.connections.pending_requests` | up-down counter | [`Status::waiting`] |
+//! | `db.client.connections.timeouts` | counter | [`Counters::wait_timeouts`] |
+//!
+//! All instruments carry the [name] of the pool as `pool.name` attribute
+//! along with its [labels].
+//!
+//! # Example
+//!
+//! ```rust
+//! use deadpool::managed::{self, Metrics, RecycleResult};
+//!
+//! struct Manager {}
+//!
+//! impl managed::Manager for Manager {
+//!     type Type = ();
+//!     type Error = ();
+//!
+//!     async fn create(&self) -> Result<(), ()> {
+//!         Ok(())
+//!     }
+//!     async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<()> {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let pool = managed::Pool::<Manager>::builder(Manager {})
+//!     .name("db")
+//!     .build()
+//!     .unwrap();
+//! let meter = opentelemetry::global::meter("app");
+//! managed::opentelemetry::register(&meter, &pool);
+//! ```
+//!
+//! [`Counters::wait_timeouts`]: super::Counters::wait_timeouts
+//! [labels]: super::PoolBuilder::label
+//! [name]: super::PoolBuilder::name
+
+use opentelemetry::{metrics::Meter, KeyValue};
+
+use crate::Status;
+
+use super::{Manager, Object, Pool, WeakPool};
+
+/// Name, description, unit and value of an up-down counter.
+type Gauge = (
+    &'static str,
+    &'static str,
+    &'static str,
+    fn(&Status) -> usize,
+);
+
+/// Registers observable instruments reporting the statistics of the given
+/// [`Pool`] with the given [`Meter`].
+///
+/// The instruments only hold weak references to the pool. Once the pool has
+/// been dropped they stop reporting values.
+pub fn register<M, W>(meter: &Meter, pool: &Pool<M, W>)
+where
+    M: Manager + 'static,
+    W: From<Object<M>>,
+{
+    let pool_attributes = std::iter::once(KeyValue::new(
+        "pool.name",
+        pool.name().unwrap_or_default().to_owned(),
+    ))
+    .chain(
+        pool.labels()
+            .iter()
+            .map(|(key, value)| KeyValue::new(key.clone(), value.clone())),
+    )
+    .collect::<Vec<_>>();
+    let status = |pool: WeakPool<M>| move || Some(pool.upgrade()?.status());
+
+    let usage = status(pool.weak());
+    let attributes = ["idle", "used"].map(|state| {
+        let mut attributes = pool_attributes.clone();
+        attributes.push(KeyValue::new("state", state));
+        attributes
+    });
+    let _ = meter
+        .i64_observable_up_down_counter("db.client.connections.usage")
+        .with_description("The number of connections that are currently in state described by the `state` attribute")
+        .with_unit("{connection}")
+        .with_callback(move |observer| {
+            if let Some(status) = usage() {
+                let idle = status.available;
+                let used = status.size.saturating_sub(idle);
+                observer.observe(to_i64(idle), &attributes[0]);
+                observer.observe(to_i64(used), &attributes[1]);
+            }
+        })
+        .build();
+
+    let gauges: [Gauge; 2] = [
+        (
+            "db.client.connections.max",
+            "The maximum number of open connections allowed",
+            "{connection}",
+            |status| status.max_size,
+        ),
+        (
+            "db.client.connections.pending_requests",
+            "The number of pending requests for an open connection, cumulative for the entire pool",
+            "{request}",
+            |status| status.waiting,
+        ),
+    ];
+    for (instrument, description, unit, value) in gauges {
+        let status = status(pool.weak());
+        let attributes = pool_attributes.clone();
+        let _ = meter
+            .i64_observable_up_down_counter(instrument)
+            .with_description(description)
+            .with_unit(unit)
+            .with_callback(move |observer| {
+                if let Some(status) = status() {
+                    observer.observe(to_i64(value(&status)), &attributes);
+                }
+            })
+            .build();
+    }
+
+    let weak = pool.weak();
+    let attributes = pool_attributes;
+    let _ = meter
+        .u64_observable_counter("db.client.connections.timeouts")
+        .with_description("The number of connection timeouts that have occurred trying to obtain a connection from the pool")
+        .with_unit("{timeout}")
+        .with_callback(move |observer| {
+            if let Some(pool) = weak.upgrade() {
+                let timeouts = pool.counters().wait_timeouts;
+                observer.observe(u64::try_from(timeouts).unwrap_or(u64::MAX), &attributes);
+            }
+        })
+        .build();
+}
+
+fn to_i64(value: usize) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
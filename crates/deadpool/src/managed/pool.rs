@@ -1,24 +1,47 @@
 #[cfg(not(target_arch = "wasm32"))]
-use std::time::Instant;
+use std::time::{Instant, SystemTime};
 use std::{
-    collections::VecDeque,
+    any::Any,
     fmt,
-    future::Future,
+    future::{poll_fn, Future},
     marker::PhantomData,
+    pin::{pin, Pin},
     sync::{
         atomic::{AtomicUsize, Ordering},
-        Arc, Mutex, Weak,
+        Arc, Mutex, MutexGuard, RwLock, Weak,
     },
+    task::Poll,
     time::Duration,
 };
 
 use deadpool_runtime::Runtime;
-use tokio::sync::{Semaphore, TryAcquireError};
+use tokio::sync::{mpsc, oneshot, Notify, Semaphore, TryAcquireError};
 
+#[cfg(feature = "debug-leases")]
+use crate::managed::lease::LeaseInfo;
+#[cfg(feature = "metrics")]
+use crate::managed::recorder::Recorder;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::managed::stats::RateEvent;
 use crate::{
+    clock,
     managed::{
-        dropguard::DropGuard, hooks::Hooks, object::ObjectInner, Manager, Metrics, Object,
-        PoolBuilder, PoolConfig, PoolError, QueueMode, TimeoutType, Timeouts,
+        breaker::CircuitBreaker,
+        builder::IdGeneratorFn,
+        counters::{AtomicCounters, Counters, ShardedGauge},
+        dropguard::DropGuard,
+        health::{Health, HealthProbe},
+        hooks::{HookContext, HookError, HookStage, Hooks},
+        idle::IdleQueue,
+        lease::Leases,
+        maintenance,
+        object::{ObjectInner, SharedObject},
+        priority::PriorityQueue,
+        stats::{AtomicHistogram, AtomicRates, Histogram, Stats},
+        trace::instrument,
+        Acquire, AutoscaleConfig, DetachMode, ErrorStage, LastError, Manager, Metrics, Object,
+        PoolBuilder, PoolConfig, PoolError, PoolErrorKind, PoolEvent, RecycleError,
+        RecycleTimeoutMode, TimeoutError, TimeoutType, Timeouts, Validation,
     },
     Status,
 };
@@ -64,29 +87,116 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
         PoolBuilder::new(manager)
     }
 
-    pub(crate) fn from_builder(builder: PoolBuilder<M, W>) -> Self {
-        Self {
+    pub(crate) fn from_builder(builder: PoolBuilder<M, W>) -> Self
+    where
+        M: 'static,
+    {
+        let (recycler, recycle_receiver) = match builder.runtime {
+            Some(_) if builder.config.recycle_on_return => {
+                let (sender, receiver) = mpsc::unbounded_channel();
+                (Some(sender), Some(receiver))
+            }
+            _ => (None, None),
+        };
+        let (destroyer, destroy_receiver) = match builder.runtime {
+            Some(_) => {
+                let (sender, receiver) = mpsc::unbounded_channel();
+                (Some(sender), Some(receiver))
+            }
+            None => (None, None),
+        };
+        let (refill, refill_receiver) = match builder.runtime {
+            Some(_) if builder.config.recycle_ahead > 0 => {
+                // Refill requests are coalesced while the buffer is refilled.
+                let (sender, receiver) = mpsc::channel(1);
+                (Some(sender), Some(receiver))
+            }
+            _ => (None, None),
+        };
+        #[cfg(feature = "metrics")]
+        let recorder = Recorder::new(builder.name.as_deref(), &builder.labels);
+        let pool = Self {
             inner: Arc::new(PoolInner {
-                manager: builder.manager,
+                name: builder.name,
+                labels: builder.labels,
+                leases: (builder.config.lease_timeout.is_some() || cfg!(feature = "debug-leases"))
+                    .then(Leases::default),
+                manager: RwLock::new(Arc::new(builder.manager)),
                 next_id: AtomicUsize::new(0),
-                slots: Mutex::new(Slots {
-                    vec: VecDeque::with_capacity(builder.config.max_size),
-                    size: 0,
-                    max_size: builder.config.max_size,
-                }),
-                users: AtomicUsize::new(0),
+                id_generator: builder.id_generator,
+                generation: AtomicUsize::new(0),
+                slots: Slots::new(builder.config.max_size),
+                idle: IdleQueue::new(
+                    builder.config.shards,
+                    builder.config.max_size,
+                    builder.config.recycle_ahead,
+                    builder.config.queue_mode,
+                ),
+                users: ShardedGauge::new(builder.config.shards),
+                waiters: AtomicUsize::new(0),
+                waited: AtomicUsize::new(0),
+                creating: ShardedGauge::new(builder.config.shards),
+                counters: AtomicCounters::default(),
+                last_error: Mutex::new(None),
+                wait_time: AtomicHistogram::new(&builder.histogram_buckets),
+                sampled_wait_time: Mutex::new(None),
+                create_time: AtomicHistogram::new(&builder.histogram_buckets),
+                rates: AtomicRates::new(builder.stats_window),
+                #[cfg(feature = "metrics")]
+                recorder,
+                overflow: AtomicUsize::new(0),
                 semaphore: Semaphore::new(builder.config.max_size),
+                priority_waiters: PriorityQueue::default(),
+                size_decreased: Notify::new(),
+                breaker: CircuitBreaker::new(builder.config.circuit_breaker),
+                shared: Mutex::new(Vec::new()),
+                recycler,
+                refill,
+                destroyer,
                 config: builder.config,
                 hooks: builder.hooks,
                 runtime: builder.runtime,
             }),
             _wrapper: PhantomData,
+        };
+        if let Some(runtime) = pool.inner.runtime {
+            if let Some(receiver) = recycle_receiver {
+                maintenance::spawn_recycler(runtime, receiver, pool.weak());
+            }
+            if let Some(receiver) = destroy_receiver {
+                maintenance::spawn_destroyer(runtime, receiver);
+            }
+            if let Some(receiver) = refill_receiver {
+                maintenance::spawn_refill(runtime, receiver, pool.weak());
+            }
+            if builder.warm_up_on_create {
+                maintenance::spawn_warm_up(runtime, pool.inner.config.max_size, pool.weak());
+            }
+            if let Some(interval) = pool.inner.config.sweep_interval() {
+                maintenance::spawn(runtime, interval, pool.weak());
+            }
+            if let Some(interval) = pool.inner.config.health_check_interval {
+                maintenance::spawn_health_check(runtime, interval, pool.weak());
+            }
+            if let Some(timeout) = pool.inner.config.lease_timeout {
+                maintenance::spawn_lease_watchdog(runtime, timeout, pool.weak());
+            }
+            if let Some(config) = pool.inner.config.autoscale {
+                maintenance::spawn_autoscale(runtime, config, pool.weak());
+            }
         }
+        pool
     }
 
     /// Retrieves an [`Object`] from this [`Pool`] or waits for one to
     /// become available.
     ///
+    /// Waiting is fair: tasks waiting for an [`Object`] are served in the
+    /// order they started waiting. Tasks waiting via
+    /// [`Pool::get_with_priority()`] might be served earlier, see there.
+    /// Overflow [`Object`]s (see [`PoolConfig::max_overflow`]) are only
+    /// created while no task is waiting.
+    ///
     /// # Errors
     ///
     /// See [`PoolError`] for details.
@@ -94,67 +204,511 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
         self.timeout_get(&self.timeouts()).await
     }
 
+    /// Same as [`Pool::get()`] but returns a named [`Acquire`] future.
+    ///
+    /// See [`Acquire`] for what happens when the future is dropped before
+    /// it completes. The future is boxed, so prefer [`Pool::get()`] unless
+    /// the type needs to be named.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub fn acquire(&self) -> Acquire<'_, M, W>
+    where
+        W: Send,
+    {
+        Acquire::new(self)
+    }
+
     /// Retrieves an [`Object`] from this [`Pool`] using a different `timeout`
     /// than the configured one.
     ///
+    /// This allows call sites sharing a [`Pool`] to use different timeouts,
+    /// e.g. a strict `wait` timeout when serving requests and looser ones
+    /// for background jobs. The given `timeouts` replace the configured ones
+    /// as a whole. Start from [`Pool::timeouts()`] in order to only override
+    /// some of them.
+    ///
     /// # Errors
     ///
     /// See [`PoolError`] for details.
     pub async fn timeout_get(&self, timeouts: &Timeouts) -> Result<W, PoolError<M::Error>> {
-        let _ = self.inner.users.fetch_add(1, Ordering::Relaxed);
-        let users_guard = DropGuard(|| {
-            let _ = self.inner.users.fetch_sub(1, Ordering::Relaxed);
-        });
+        self.get_inner(|| *timeouts, None, None)
+            .await
+            .map(Into::into)
+    }
+
+    /// Retrieves an [`Object`] from this [`Pool`] without waiting for one to
+    /// become available.
+    ///
+    /// Returns `None` right away if neither an idle [`Object`] nor a free
+    /// slot is available. The task never queues up behind other waiting
+    /// tasks. Creating or recycling an [`Object`] still takes as long as it
+    /// takes, bounded by the configured [`Timeouts`]. Unlike calling
+    /// [`Pool::timeout_get()`] with a zero `wait` timeout a `None` isn't
+    /// counted as [`TimeoutType::Wait`] timeout.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn try_get(&self) -> Result<Option<W>, PoolError<M::Error>> {
+        let timeouts = self.timeouts();
+        let obj = instrument!(
+            self.try_get_object(|| timeouts),
+            "deadpool.try_get",
+            pool.name = self.inner.name(),
+            pool.labels = ?self.inner.labels,
+            object.id = tracing::field::Empty,
+        )
+        .await?;
+        Ok(obj.map(Into::into))
+    }
+
+    /// Retrieves an [`Object`] from this [`Pool`] bounding the entire
+    /// acquisition by the given `deadline`.
+    ///
+    /// Waiting for a slot, creating and recycling objects must all be done
+    /// before the `deadline`. The configured [`Timeouts`] still apply to the
+    /// individual steps if they expire earlier.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoolError::Timeout`] with the step that was running when the
+    /// `deadline` was reached.
+    ///
+    /// Returns [`PoolError::NoRuntimeSpecified`] if no [`Runtime`] was
+    /// configured.
+    ///
+    /// See [`PoolError`] for details.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn get_deadline(&self, deadline: Instant) -> Result<W, PoolError<M::Error>> {
+        let timeouts = self.timeouts();
+        self.get_inner(|| timeouts_until(&timeouts, deadline), None, None)
+            .await
+            .map(Into::into)
+    }
+
+    /// Retrieves an [`Object`] from this [`Pool`] unless the given `token`
+    /// is cancelled first.
+    ///
+    /// Cancelling the `token` stops waiting for a slot and aborts creating
+    /// or recycling an object, just like dropping the future returned by
+    /// [`Pool::get()`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoolError::Cancelled`] if the `token` is cancelled before
+    /// an [`Object`] could be retrieved.
+    ///
+    /// See [`PoolError`] for details.
+    #[cfg(feature = "tokio-util")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-util")))]
+    pub async fn get_cancellable(
+        &self,
+        token: &tokio_util::sync::CancellationToken,
+    ) -> Result<W, PoolError<M::Error>> {
+        let mut get = pin!(self.get());
+        let mut cancelled = pin!(token.cancelled());
+        poll_fn(|cx| {
+            if cancelled.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(Err(PoolError::Cancelled));
+            }
+            get.as_mut().poll(cx)
+        })
+        .await
+    }
+
+    /// Retrieves an [`Object`] from this [`Pool`] or waits for one to
+    /// become available, jumping ahead of other waiting tasks.
+    ///
+    /// Tasks waiting with a higher `priority` are served first. Tasks
+    /// waiting with the same `priority` are served in the order they
+    /// started waiting. Tasks waiting via [`Pool::get()`] are only served
+    /// when no task with a priority is waiting.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn get_with_priority(&self, priority: u8) -> Result<W, PoolError<M::Error>> {
+        let timeouts = self.timeouts();
+        self.get_inner(|| timeouts, Some(priority), None)
+            .await
+            .map(Into::into)
+    }
+
+    /// Retrieves an [`Object`] from this [`Pool`] passing the given `ctx` to
+    /// [`Manager::create_with()`] if a new [`Object`] needs to be created.
+    ///
+    /// This allows passing per-checkout parameters like a role, a namespace
+    /// or session settings to the [`Manager`]. Idle [`Object`]s are handed
+    /// out regardless of the context they were created with. Use a
+    /// [`keyed::KeyedPool`](super::keyed::KeyedPool) if objects must not be
+    /// shared between different contexts.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn get_with<C: Any + Send + Sync>(&self, ctx: &C) -> Result<W, PoolError<M::Error>> {
+        let timeouts = self.timeouts();
+        self.get_inner(|| timeouts, None, Some(ctx))
+            .await
+            .map(Into::into)
+    }
+
+    /// Retrieves a [`SharedObject`] which might be used by other holders
+    /// at the same time.
+    ///
+    /// This is meant for inherently multiplexed objects like HTTP/2 or
+    /// cluster clients. An [`Object`] which is already shared by less than
+    /// [`PoolConfig::max_holders`] holders is handed out again. The one
+    /// with the fewest holders is picked. If there is none, a new
+    /// [`Object`] is retrieved just like with [`Pool::get()`]. The
+    /// [`Object`] is returned to the [`Pool`] once the last holder drops
+    /// it.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn get_shared(&self) -> Result<SharedObject<M>, PoolError<M::Error>>
+    where
+        M: 'static,
+        M::Type: Sync,
+    {
+        if let Some(obj) = self.inner.try_share() {
+            return Ok(obj);
+        }
+        let timeouts = self.timeouts();
+        let obj = Arc::new(self.get_inner(|| timeouts, None, None).await?);
+        let weak = Arc::downgrade(&obj);
+        self.inner.shared.lock().unwrap().push(weak);
+        Ok(SharedObject { inner: obj })
+    }
+
+    /// Retrieves an [`Object`] calling `timeouts` before every step in order
+    /// to get the [`Timeouts`] to apply to it.
+    async fn get_inner(
+        &self,
+        timeouts: impl Fn() -> Timeouts,
+        priority: Option<u8>,
+        ctx: Option<&(dyn Any + Send + Sync)>,
+    ) -> Result<Object<M>, PoolError<M::Error>> {
+        instrument!(
+            self.get_object(timeouts, priority, ctx),
+            "deadpool.get",
+            pool.name = self.inner.name(),
+            pool.labels = ?self.inner.labels,
+            object.id = tracing::field::Empty,
+        )
+        .await
+    }
 
-        let non_blocking = match timeouts.wait {
-            Some(t) => t.as_nanos() == 0,
-            None => false,
+    async fn get_object(
+        &self,
+        timeouts: impl Fn() -> Timeouts,
+        priority: Option<u8>,
+        ctx: Option<&(dyn Any + Send + Sync)>,
+    ) -> Result<Object<M>, PoolError<M::Error>> {
+        let users_guard = self.user_guard();
+        let _report_guard = self.report_guard();
+        let slot = match self.try_reserve()? {
+            Some(slot) => slot,
+            None => self.wait_for_slot(timeouts().wait, priority).await?,
         };
+        self.checkout(slot, users_guard, &timeouts, ctx).await
+    }
 
-        let permit = if non_blocking {
-            self.inner.semaphore.try_acquire().map_err(|e| match e {
-                TryAcquireError::Closed => PoolError::Closed,
-                TryAcquireError::NoPermits => PoolError::Timeout(TimeoutType::Wait),
-            })?
-        } else {
-            apply_timeout(
-                self.inner.runtime,
-                TimeoutType::Wait,
-                timeouts.wait,
-                async {
-                    self.inner
-                        .semaphore
-                        .acquire()
-                        .await
-                        .map_err(|_| PoolError::Closed)
-                },
-            )
-            .await?
+    /// Like [`Pool::get_object()`] but returns `None` right away instead of
+    /// waiting for a slot. See [`Pool::try_get()`].
+    async fn try_get_object(
+        &self,
+        timeouts: impl Fn() -> Timeouts,
+    ) -> Result<Option<Object<M>>, PoolError<M::Error>> {
+        let users_guard = self.user_guard();
+        let _report_guard = self.report_guard();
+        let Some(slot) = self.try_reserve()? else {
+            return Ok(None);
         };
+        self.checkout(slot, users_guard, &timeouts, None)
+            .await
+            .map(Some)
+    }
+
+    /// Counts a user of the pool until the returned guard is dropped or the
+    /// user received an [`Object`].
+    fn user_guard(&self) -> DropGuard<impl Fn() + '_> {
+        self.inner.users.incr();
+        DropGuard(|| self.inner.users.decr())
+    }
+
+    /// Records the time it took to get an [`Object`] and reports the status
+    /// once the returned guard is dropped.
+    fn report_guard(&self) -> DropGuard<impl Fn() + '_> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let start = clock::now();
+        DropGuard(move || {
+            #[cfg(not(target_arch = "wasm32"))]
+            self.inner.record_wait_time(clock::elapsed(start));
+            self.inner.report_status();
+        })
+    }
+
+    /// Takes a permit or reserves room for an overflow object without
+    /// waiting. Returns `None` if neither is available.
+    fn try_reserve(&self) -> Result<Option<Slot<'_, M>>, PoolError<M::Error>> {
+        match self.inner.try_acquire() {
+            Ok(permit) => Ok(Some(Slot::Permit(permit))),
+            Err(TryAcquireError::Closed) => Err(PoolError::Closed),
+            // No permit is needed for overflow objects. They aren't created
+            // while other tasks are waiting as that would let this task
+            // jump the queue.
+            Err(TryAcquireError::NoPermits)
+                if self.inner.waiters.load(Ordering::SeqCst) == 0
+                    && self.inner.reserve_overflow() =>
+            {
+                Ok(Some(Slot::Overflow))
+            }
+            Err(TryAcquireError::NoPermits) => Ok(None),
+        }
+    }
+
+    /// Waits for a permit after [`Pool::try_reserve()`] failed. A zero
+    /// `wait` timeout fails right away with a [`TimeoutType::Wait`] timeout.
+    async fn wait_for_slot(
+        &self,
+        wait: Option<Duration>,
+        priority: Option<u8>,
+    ) -> Result<Slot<'_, M>, PoolError<M::Error>> {
+        if wait.is_some_and(|t| t.is_zero()) {
+            let e = self.inner.timeout_error(
+                TimeoutType::Wait,
+                Duration::ZERO,
+                #[cfg(not(target_arch = "wasm32"))]
+                Duration::ZERO,
+            );
+            self.inner.timeout(&e);
+            return Err(PoolError::Timeout(e));
+        }
+        let _ = self.inner.waited.fetch_add(1, Ordering::Relaxed);
+        let waiters = self.inner.waiters.fetch_add(1, Ordering::Relaxed);
+        let _waiters_guard = DropGuard(|| {
+            let _ = self.inner.waiters.fetch_sub(1, Ordering::Relaxed);
+        });
+        if let Some(max_waiters) = self.inner.config.max_waiters {
+            if waiters >= max_waiters {
+                self.inner.emit(PoolEvent::QueueFull);
+                return Err(PoolError::QueueFull);
+            }
+        }
+        let permit = self
+            .with_timeout(TimeoutType::Wait, wait, self.acquire_permit(priority))
+            .await?;
+        Ok(Slot::Permit(permit))
+    }
 
+    /// Takes an idle object or creates a new one for the given `slot` and
+    /// hands it out. The `users_guard` is disarmed as soon as the
+    /// [`Object`] takes over counting the user.
+    async fn checkout(
+        &self,
+        slot: Slot<'_, M>,
+        users_guard: DropGuard<impl Fn()>,
+        timeouts: &impl Fn() -> Timeouts,
+        ctx: Option<&(dyn Any + Send + Sync)>,
+    ) -> Result<Object<M>, PoolError<M::Error>> {
+        let (mut permit, overflow_guard) = match slot {
+            Slot::Overflow => (None, Some(DropGuard(|| self.inner.release_overflow()))),
+            Slot::Permit(permit) => (Some(permit), None),
+        };
         let inner_obj = loop {
-            let inner_obj = match self.inner.config.queue_mode {
-                QueueMode::Fifo => self.inner.slots.lock().unwrap().vec.pop_front(),
-                QueueMode::Lifo => self.inner.slots.lock().unwrap().vec.pop_back(),
+            let mut inner_obj = match permit.take() {
+                None => loop {
+                    if let Some(inner_obj) = self.try_create(&timeouts(), true, ctx).await? {
+                        break inner_obj;
+                    }
+                },
+                Some(p) => {
+                    let (inner_obj, p) = self.lease(timeouts, ctx, p).await?;
+                    permit = Some(p);
+                    inner_obj
+                }
             };
-            let inner_obj = if let Some(inner_obj) = inner_obj {
-                self.try_recycle(timeouts, inner_obj).await?
-            } else {
-                self.try_create(timeouts).await?
+            inner_obj.metrics.use_count += 1;
+
+            // Apply on_acquire hooks. The object is discarded when the
+            // `unready_obj` is dropped, e.g. if a hook asks for it.
+            let mut unready_obj = UnreadyObject {
+                inner: Some(inner_obj),
+                pool: &self.inner,
             };
-            if let Some(inner_obj) = inner_obj {
-                break inner_obj;
+            let e = match self.inner.hooks.on_acquire.apply(unready_obj.inner()).await {
+                Ok(()) => break unready_obj.ready(),
+                Err(HookError::Discard) => continue,
+                Err(HookError::Abort(e)) => *e,
+                Err(e) => e,
+            };
+            // The object is returned to the pool if a hook failed.
+            let metrics = unready_obj.inner().metrics;
+            if permit.is_some() {
+                let _ = self.inner.idle.push(unready_obj.ready());
             }
+            let e = self.hook_error(e, HookStage::OnAcquire, &metrics);
+            return Err(PoolError::OnAcquireHook(e));
         };
+        if let Some(overflow_guard) = overflow_guard {
+            overflow_guard.disarm();
+        }
 
         users_guard.disarm();
-        permit.forget();
+        if let Some(permit) = permit {
+            permit.forget();
+        }
 
-        Ok(Object {
+        if let Some(leases) = &self.inner.leases {
+            leases.acquire(inner_obj.id);
+        }
+        let obj = Object {
             inner: Some(inner_obj),
             pool: self.weak(),
+        };
+
+        #[cfg(feature = "tracing")]
+        let _ = tracing::Span::current().record("object.id", Object::id(&obj));
+
+        Ok(obj)
+    }
+
+    /// Takes an idle object or creates a new one and makes sure that the
+    /// returned permit covers the weight of the object. See
+    /// [`Manager::weight()`].
+    async fn lease<'a>(
+        &'a self,
+        timeouts: &impl Fn() -> Timeouts,
+        ctx: Option<&(dyn Any + Send + Sync)>,
+        mut permit: Permit<'a, M>,
+    ) -> Result<(ObjectInner<M>, Permit<'a, M>), PoolError<M::Error>> {
+        loop {
+            let mut inner_obj = self.get_or_create(timeouts, ctx).await?;
+            // Objects weighing more than the whole pool would wait forever.
+            let max_size = self.inner.slots.max_size();
+            inner_obj.weight = inner_obj.weight.clamp(1, max_size.max(1));
+            if inner_obj.weight <= permit.count {
+                permit.release(permit.count - inner_obj.weight);
+                return Ok((inner_obj, permit));
+            }
+            if let Ok(extra) = self.inner.try_acquire_many(inner_obj.weight - permit.count) {
+                permit.merge(extra);
+                return Ok((inner_obj, permit));
+            }
+            // Holding on to some permits while waiting for more could
+            // dead-lock the pool. The object is put back and all permits
+            // it needs are acquired at once instead.
+            let weight = inner_obj.weight;
+            inner_obj.recycled = true;
+            let _ = self.inner.idle.push(inner_obj);
+            drop(permit);
+            let _ = self.inner.waiters.fetch_add(1, Ordering::Relaxed);
+            let waiters_guard = DropGuard(|| {
+                let _ = self.inner.waiters.fetch_sub(1, Ordering::Relaxed);
+            });
+            permit = self
+                .with_timeout(
+                    TimeoutType::Wait,
+                    timeouts().wait,
+                    self.acquire_many(weight),
+                )
+                .await?;
+            drop(waiters_guard);
+        }
+    }
+
+    /// Waits for `n` permits of the semaphore.
+    async fn acquire_many(&self, n: usize) -> Result<Permit<'_, M>, PoolError<M::Error>> {
+        self.inner
+            .semaphore
+            .acquire_many(u32::try_from(n).unwrap_or(u32::MAX))
+            .await
+            .map_err(|_| PoolError::Closed)?
+            .forget();
+        Ok(Permit {
+            pool: &self.inner,
+            count: n,
+        })
+    }
+
+    /// Takes an idle object from the pool or creates a new one if none is
+    /// available. The caller must hold a permit.
+    async fn get_or_create(
+        &self,
+        timeouts: &impl Fn() -> Timeouts,
+        ctx: Option<&(dyn Any + Send + Sync)>,
+    ) -> Result<ObjectInner<M>, PoolError<M::Error>> {
+        loop {
+            let inner_obj = if let Some(inner_obj) = self.inner.idle.pop() {
+                self.inner.request_refill();
+                self.try_recycle(&timeouts(), inner_obj).await?
+            } else if !self.inner.idle_is_empty() {
+                continue;
+            } else {
+                self.try_create(&timeouts(), false, ctx).await?
+            };
+            if let Some(inner_obj) = inner_obj {
+                return Ok(inner_obj);
+            }
         }
-        .into())
+    }
+
+    /// Waits for a permit of the semaphore. If a `priority` is given the
+    /// task also waits in the [`PriorityQueue`] and takes whichever permit
+    /// it receives first.
+    async fn acquire_permit(
+        &self,
+        priority: Option<u8>,
+    ) -> Result<Permit<'_, M>, PoolError<M::Error>> {
+        let Some(priority) = priority else {
+            let permit = self
+                .inner
+                .semaphore
+                .acquire()
+                .await
+                .map_err(|_| PoolError::Closed)?;
+            permit.forget();
+            return Ok(Permit {
+                pool: &self.inner,
+                count: 1,
+            });
+        };
+        let mut handoff = Handoff {
+            receiver: Some(self.inner.priority_waiters.push(priority)),
+            pool: &self.inner,
+        };
+        let mut acquire = pin!(self.inner.semaphore.acquire());
+        poll_fn(|cx| {
+            if let Some(receiver) = handoff.receiver.as_mut() {
+                if let Poll::Ready(result) = Pin::new(receiver).poll(cx) {
+                    handoff.receiver = None;
+                    return Poll::Ready(match result {
+                        Ok(()) => Ok(Permit {
+                            pool: &self.inner,
+                            count: 1,
+                        }),
+                        Err(_) => Err(PoolError::Closed),
+                    });
+                }
+            }
+            // Waiting for the semaphore as well makes sure that permits
+            // which are added to the semaphore directly aren't missed.
+            acquire.as_mut().poll(cx).map(|result| match result {
+                Ok(permit) => {
+                    permit.forget();
+                    Ok(Permit {
+                        pool: &self.inner,
+                        count: 1,
+                    })
+                }
+                Err(_) => Err(PoolError::Closed),
+            })
+        })
+        .await
     }
 
     #[inline]
@@ -169,60 +723,217 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
         };
         let inner = unready_obj.inner();
 
-        // Apply pre_recycle hooks
-        if let Err(_e) = self.inner.hooks.pre_recycle.apply(inner).await {
-            // TODO log pre_recycle error
+        if self.inner.is_expired(inner) {
             return Ok(None);
         }
 
-        if apply_timeout(
-            self.inner.runtime,
-            TimeoutType::Recycle,
-            timeouts.recycle,
-            self.inner.manager.recycle(&mut inner.obj, &inner.metrics),
-        )
-        .await
-        .is_err()
+        let recycled = std::mem::take(&mut inner.recycled);
+        match self
+            .inner
+            .manager()
+            .validate(&mut inner.obj, &inner.metrics)
         {
+            Validation::Valid => return Ok(Some(unready_obj.ready())),
+            Validation::Invalid => return Ok(None),
+            Validation::Recycle => {}
+        }
+
+        // Objects recycled when being returned are ready already.
+        if recycled {
+            return Ok(Some(unready_obj.ready()));
+        }
+
+        if !self.recycle(timeouts, inner).await? {
             return Ok(None);
         }
 
+        Ok(Some(unready_obj.ready()))
+    }
+
+    /// Runs the recycle hooks and [`Manager::recycle()`] on the given object.
+    ///
+    /// Returns `false` if the object is broken and must be discarded. An
+    /// error is only returned if a hook asked to abort via
+    /// [`HookError::Abort`].
+    async fn recycle(
+        &self,
+        timeouts: &Timeouts,
+        inner: &mut ObjectInner<M>,
+    ) -> Result<bool, PoolError<M::Error>> {
+        // Apply pre_recycle hooks
+        if let Err(e) = self.inner.hooks.pre_recycle.apply(inner).await {
+            // TODO log pre_recycle error
+            return match e.into_abort() {
+                Some(e) => Err(PoolError::RecycleHook(self.hook_error(
+                    e,
+                    HookStage::PreRecycle,
+                    &inner.metrics,
+                ))),
+                None => Ok(false),
+            };
+        }
+
+        let manager = self.inner.manager();
+        if let Err(e) = self
+            .with_timeout(
+                TimeoutType::Recycle,
+                timeouts.recycle,
+                instrument!(
+                    manager.recycle(&mut inner.obj, &inner.metrics),
+                    "deadpool.recycle",
+                    pool.name = self.inner.name(),
+                    pool.labels = ?self.inner.labels,
+                    object.id = inner.id,
+                ),
+            )
+            .await
+        {
+            if self.keep_after_recycle_timeout(&e) {
+                return Ok(true);
+            }
+            AtomicCounters::incr(&self.inner.counters.recycle_failed);
+            self.inner.emit(PoolEvent::RecycleFailed {
+                id: inner.id,
+                metrics: inner.metrics,
+            });
+            // Apply on_recycle_error hooks
+            if let PoolError::Backend(e) = e {
+                self.inner.record_recycle_error(&e);
+                for hook in self.inner.hooks.on_recycle_error.iter() {
+                    hook(&e, &inner.metrics);
+                }
+            }
+            return Ok(false);
+        }
+
         // Apply post_recycle hooks
-        if let Err(_e) = self.inner.hooks.post_recycle.apply(inner).await {
+        if let Err(e) = self.inner.hooks.post_recycle.apply(inner).await {
             // TODO log post_recycle error
-            return Ok(None);
+            return match e.into_abort() {
+                Some(e) => Err(PoolError::RecycleHook(self.hook_error(
+                    e,
+                    HookStage::PostRecycle,
+                    &inner.metrics,
+                ))),
+                None => Ok(false),
+            };
         }
 
+        AtomicCounters::incr(&self.inner.counters.recycled);
         inner.metrics.recycle_count += 1;
         #[cfg(not(target_arch = "wasm32"))]
         {
-            inner.metrics.recycled = Some(Instant::now());
+            inner.metrics.recycled = Some(clock::now());
         }
+        self.inner.emit(PoolEvent::ObjectRecycled {
+            id: inner.id,
+            metrics: inner.metrics,
+        });
 
-        Ok(Some(unready_obj.ready()))
+        Ok(true)
+    }
+
+    /// Checks whether an object must be kept although recycling it failed
+    /// with the given error. This is the case for recycle timeouts if the
+    /// [`PoolConfig::recycle_timeout_mode`] is [`RecycleTimeoutMode::Return`].
+    fn keep_after_recycle_timeout<E>(&self, e: &PoolError<E>) -> bool {
+        if matches!(e, PoolError::Timeout(_))
+            && self.inner.config.recycle_timeout_mode == RecycleTimeoutMode::Return
+        {
+            AtomicCounters::incr(&self.inner.counters.recycle_timeouts_returned);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Applies the `duration` to the `future` via [`apply_timeout()`] and
+    /// counts the timeouts.
+    async fn with_timeout<O, E>(
+        &self,
+        timeout_type: TimeoutType,
+        duration: Option<Duration>,
+        future: impl Future<Output = Result<O, impl Into<PoolError<E>>>>,
+    ) -> Result<O, PoolError<E>> {
+        let result = apply_timeout(&self.inner, timeout_type, duration, future).await;
+        if let Err(PoolError::Timeout(e)) = &result {
+            self.inner.timeout(e);
+        }
+        result
+    }
+
+    /// Attaches the [`HookContext`] to the error of a hook.
+    fn hook_error(
+        &self,
+        e: HookError<M::Error>,
+        stage: HookStage,
+        metrics: &Metrics,
+    ) -> HookError<M::Error> {
+        e.with_context(HookContext {
+            stage,
+            metrics: *metrics,
+            status: self.status(),
+        })
+    }
+
+    /// Recycles an object which has been returned to the pool and makes it
+    /// available again. See [`PoolConfig::recycle_on_return`].
+    ///
+    /// There is no [`Pool::get()`] which could be aborted so the object is
+    /// simply discarded if a hook returns [`HookError::Abort`].
+    pub(crate) async fn recycle_returned(&self, mut inner: ObjectInner<M>) {
+        if let Ok(true) = self.recycle(&self.timeouts(), &mut inner).await {
+            inner.recycled = true;
+            self.inner.make_idle(inner);
+        } else {
+            self.inner.detach_object(&mut inner);
+            self.inner.destroy(inner);
+        }
     }
 
     #[inline]
     async fn try_create(
         &self,
         timeouts: &Timeouts,
+        overflow: bool,
+        ctx: Option<&(dyn Any + Send + Sync)>,
     ) -> Result<Option<ObjectInner<M>>, PoolError<M::Error>> {
+        // The generation must be loaded before the manager is. Otherwise an
+        // object created by a replaced manager could be tagged with the
+        // generation of its replacement.
+        let generation = self.inner.generation.load(Ordering::Relaxed);
+        let obj = self
+            .with_timeout(
+                TimeoutType::Create,
+                timeouts.create,
+                instrument!(
+                    self.create_with_retry(ctx),
+                    "deadpool.create",
+                    pool.name = self.inner.name(),
+                    pool.labels = ?self.inner.labels,
+                ),
+            )
+            .await?;
         let mut unready_obj = UnreadyObject {
             inner: Some(ObjectInner {
-                obj: apply_timeout(
-                    self.inner.runtime,
-                    TimeoutType::Create,
-                    timeouts.create,
-                    self.inner.manager.create(),
-                )
-                .await?,
-                id: self.inner.next_id.fetch_add(1, Ordering::Relaxed),
+                weight: self.inner.manager().weight(&obj),
+                obj,
+                id: self.inner.next_id(),
+                generation,
+                overflow,
+                recycled: false,
+                pinned: false,
                 metrics: Metrics::default(),
             }),
             pool: &self.inner,
         };
 
-        self.inner.slots.lock().unwrap().size += 1;
+        if !overflow {
+            self.inner.slots.grow();
+        }
+        self.inner.emit(PoolEvent::ObjectCreated {
+            id: unready_obj.inner().id,
+        });
 
         // Apply post_create hooks
         if let Err(e) = self
@@ -232,57 +943,467 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
             .apply(unready_obj.inner())
             .await
         {
+            let e = match e {
+                HookError::Discard => return Ok(None),
+                HookError::Abort(e) => *e,
+                e => e,
+            };
+            let metrics = unready_obj.inner().metrics;
+            let e = self.hook_error(e, HookStage::PostCreate, &metrics);
             return Err(PoolError::PostCreateHook(e));
         }
 
         Ok(Some(unready_obj.ready()))
     }
 
-    /**
-     * Resize the pool. This change the `max_size` of the pool dropping
-     * excess objects and/or making space for new ones.
-     *
-     * If the pool is closed this method does nothing. The [`Pool::status`] method
-     * always reports a `max_size` of 0 for closed pools.
-     */
-    pub fn resize(&self, max_size: usize) {
-        if self.inner.semaphore.is_closed() {
-            return;
+    /// Replaces the object of the given [`Object`] which is in use by a
+    /// newly created one. See [`Object::replace()`].
+    pub(crate) async fn replace_object(
+        &self,
+        inner: &mut ObjectInner<M>,
+    ) -> Result<M::Type, PoolError<M::Error>> {
+        let generation = self.inner.generation.load(Ordering::Relaxed);
+        let obj = self
+            .with_timeout(
+                TimeoutType::Create,
+                self.timeouts().create,
+                instrument!(
+                    self.create_with_retry(None),
+                    "deadpool.create",
+                    pool.name = self.inner.name(),
+                    pool.labels = ?self.inner.labels,
+                ),
+            )
+            .await?;
+        let old_obj = std::mem::replace(&mut inner.obj, obj);
+        let old_metrics = std::mem::replace(
+            &mut inner.metrics,
+            Metrics {
+                use_count: 1,
+                ..Metrics::default()
+            },
+        );
+        let old_generation = std::mem::replace(&mut inner.generation, generation);
+
+        // Apply post_create hooks
+        if let Err(e) = self.inner.hooks.post_create.apply(inner).await {
+            let metrics = inner.metrics;
+            inner.obj = old_obj;
+            inner.metrics = old_metrics;
+            inner.generation = old_generation;
+            let e = self.hook_error(e, HookStage::PostCreate, &metrics);
+            return Err(PoolError::PostCreateHook(e));
         }
-        let mut slots = self.inner.slots.lock().unwrap();
-        let old_max_size = slots.max_size;
-        slots.max_size = max_size;
-        // shrink pool
-        if max_size < old_max_size {
-            while slots.size > slots.max_size {
-                if let Ok(permit) = self.inner.semaphore.try_acquire() {
-                    permit.forget();
-                    if slots.vec.pop_front().is_some() {
-                        slots.size -= 1;
-                    }
-                } else {
-                    break;
+
+        Ok(old_obj)
+    }
+
+    /// Calls [`Manager::create()`] or [`Manager::create_with()`] if a `ctx`
+    /// is given retrying it according to the [`PoolConfig::create_retry`].
+    ///
+    /// Every attempt must pass the [`PoolConfig::circuit_breaker`].
+    async fn create_with_retry(
+        &self,
+        ctx: Option<&(dyn Any + Send + Sync)>,
+    ) -> Result<M::Type, PoolError<M::Error>> {
+        let mut attempt = 1;
+        loop {
+            let Some(breaker_attempt) = self.inner.breaker.attempt() else {
+                return Err(PoolError::CircuitOpen);
+            };
+            self.inner.creating.incr();
+            let creating = DropGuard(|| self.inner.creating.decr());
+            #[cfg(not(target_arch = "wasm32"))]
+            let start = clock::now();
+            let result = match ctx {
+                Some(ctx) => self.inner.manager().create_with(ctx).await,
+                None => self.inner.manager().create().await,
+            };
+            #[cfg(not(target_arch = "wasm32"))]
+            self.inner.record_create_time(clock::elapsed(start));
+            drop(creating);
+            AtomicCounters::incr(match result {
+                Ok(_) => &self.inner.counters.created,
+                Err(_) => &self.inner.counters.create_failed,
+            });
+            #[cfg(not(target_arch = "wasm32"))]
+            if result.is_ok() {
+                self.inner.rates.record(RateEvent::Create);
+            }
+            breaker_attempt.finish(result.is_ok());
+            // Apply on_create_error hooks
+            if let Err(e) = &result {
+                self.inner.record_error(
+                    ErrorStage::Create,
+                    PoolErrorKind::Backend,
+                    self.inner.describe_error(e),
+                );
+                for hook in self.inner.hooks.on_create_error.iter() {
+                    hook(e);
                 }
             }
-            // Create a new VecDeque with a smaller capacity
-            let mut vec = VecDeque::with_capacity(max_size);
-            for obj in slots.vec.drain(..) {
-                vec.push_back(obj);
+            match (&self.inner.config.create_retry, self.inner.runtime) {
+                (Some(retry), Some(runtime)) if result.is_err() && attempt < retry.max_attempts => {
+                    runtime.sleep(retry.delay(attempt)).await;
+                    attempt += 1;
+                }
+                _ => return result.map_err(PoolError::Backend),
             }
-            slots.vec = vec;
-        }
-        // grow pool
-        if max_size > old_max_size {
-            let additional = slots.max_size - old_max_size;
-            slots.vec.reserve_exact(additional);
-            self.inner.semaphore.add_permits(additional);
         }
     }
 
-    /// Retains only the objects specified by the given function.
-    ///
-    /// This function is typically used to remove objects from
-    /// the pool based on their current state or metrics.
+    /// Recycles idle objects until the buffer of objects which are recycled
+    /// ahead of time is full. See [`PoolConfig::recycle_ahead`].
+    pub(crate) async fn refill_ready(&self) {
+        let timeouts = self.timeouts();
+        while self.inner.idle.ready_len() < self.inner.config.recycle_ahead {
+            // The permit is held while recycling the object. Otherwise
+            // concurrent calls to `Pool::get` could create new objects
+            // and exceed the `max_size` of the pool.
+            let Ok(_permit) = self.inner.try_acquire() else {
+                break;
+            };
+            let Some(inner_obj) = self.inner.idle.pop_unready() else {
+                break;
+            };
+            let mut unready_obj = UnreadyObject {
+                inner: Some(inner_obj),
+                pool: &self.inner,
+            };
+            let inner = unready_obj.inner();
+            if self.inner.is_expired(inner) {
+                continue;
+            }
+            if !inner.recycled {
+                if !matches!(self.recycle(&timeouts, inner).await, Ok(true)) {
+                    continue;
+                }
+                inner.recycled = true;
+            }
+            self.inner.idle.push_ready(unready_obj.ready());
+        }
+    }
+
+    /// Eagerly creates new [`Object`]s until at least `n` idle [`Object`]s
+    /// are available or the [`PoolConfig::max_size`] is reached.
+    ///
+    /// This can be used to warm up the [`Pool`] before serving traffic so
+    /// the first callers of [`Pool::get()`] don't need to wait for new
+    /// [`Object`]s to be created.
+    ///
+    /// Returns the number of created [`Object`]s.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error that occurred while creating an [`Object`].
+    /// [`Object`]s created up to that point stay in the [`Pool`].
+    pub async fn warm_up(&self, n: usize) -> Result<usize, PoolError<M::Error>> {
+        self.replenish(n).await
+    }
+
+    /// Waits until at least `n` [`Object`]s are available, i.e. are idle or
+    /// could be created without exceeding the [`PoolConfig::max_size`].
+    ///
+    /// This is useful for waiting for a [`Pool`] to settle, e.g. during
+    /// startup or in tests. Waiting is fair, so tasks calling
+    /// [`Pool::get()`] after this method queue up behind it. If `n` exceeds
+    /// the `max_size` this waits until the [`Pool`] is resized accordingly.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoolError::Closed`] if the [`Pool`] is closed while
+    /// waiting.
+    pub async fn wait_for_available(&self, n: usize) -> Result<(), PoolError<M::Error>> {
+        if n > 0 {
+            // The permits are given back right away.
+            drop(self.acquire_many(n).await?);
+        }
+        Ok(())
+    }
+
+    /// Adds an externally created object to the [`Pool`] as idle [`Object`].
+    ///
+    /// The object counts towards the [`PoolConfig::max_size`] and receives
+    /// an id and fresh [`Metrics`] just like [`Object`]s created by the
+    /// [`Pool`]. No hooks are run for it. This can be used for handing over
+    /// objects detached via [`Object::take()`] from a previous [`Pool`],
+    /// e.g. when reloading the configuration.
+    ///
+    /// Returns the id of the added [`Object`].
+    ///
+    /// # Errors
+    ///
+    /// If the [`Pool`] is full or has been closed the object is returned
+    /// instead.
+    pub fn add(&self, obj: M::Type) -> Result<usize, M::Type> {
+        // The permit keeps concurrent calls to `Pool::get` from creating
+        // an object for the slot which is taken by this one.
+        let Ok(_permit) = self.inner.try_acquire() else {
+            return Err(obj);
+        };
+        if !self.inner.slots.try_grow() {
+            return Err(obj);
+        }
+        let id = self.inner.next_id();
+        let _ = self.inner.idle.push(ObjectInner {
+            weight: self.inner.manager().weight(&obj),
+            obj,
+            id,
+            generation: self.inner.generation.load(Ordering::Relaxed),
+            overflow: false,
+            recycled: false,
+            pinned: false,
+            metrics: Metrics::default(),
+        });
+        self.inner.emit(PoolEvent::ObjectCreated { id });
+        self.inner.request_refill();
+        self.inner.report_status();
+        Ok(id)
+    }
+
+    /// Runs a single iteration of the background maintenance task.
+    pub(crate) async fn maintain(&self) {
+        self.inner.remove_expired();
+        let min_idle = self.inner.config.min_idle;
+        if min_idle > 0 {
+            // Errors are reported to the callers of `Pool::get` anyways.
+            // The next run of the task simply tries again.
+            let _ = self.replenish(min_idle).await;
+        }
+    }
+
+    /// Reports the objects which have been held longer than the given
+    /// `timeout`. See [`PoolConfig::lease_timeout`].
+    pub(crate) fn check_leases(&self, timeout: Duration) {
+        let Some(leases) = &self.inner.leases else {
+            return;
+        };
+        for lease in leases.exceeded(timeout) {
+            AtomicCounters::incr(&self.inner.counters.leases_exceeded);
+            #[cfg(all(feature = "tracing", not(feature = "debug-leases")))]
+            tracing::warn!(
+                pool.name = self.inner.name(),
+                pool.labels = ?self.inner.labels,
+                object.id = lease.id,
+                held = ?lease.held,
+                "object held longer than the lease timeout",
+            );
+            #[cfg(all(feature = "tracing", feature = "debug-leases"))]
+            tracing::warn!(
+                pool.name = self.inner.name(),
+                pool.labels = ?self.inner.labels,
+                object.id = lease.id,
+                held = ?lease.held,
+                backtrace = %lease.backtrace,
+                "object held longer than the lease timeout",
+            );
+            self.inner.emit(PoolEvent::LeaseExceeded {
+                id: lease.id,
+                held: lease.held,
+            });
+        }
+    }
+
+    /// Runs [`Manager::recycle()`] on all idle objects and discards the
+    /// broken ones.
+    ///
+    /// Objects are only checked while there are free slots in the pool.
+    /// Unlike recycling an object in [`Pool::get()`] this doesn't run any
+    /// hooks and doesn't update the [`Metrics`] of the objects.
+    pub(crate) async fn check_health(&self) {
+        let timeouts = self.timeouts();
+        let count = self.inner.idle.len();
+        for _ in 0..count {
+            // The permit is held while checking the object. Otherwise
+            // concurrent calls to `Pool::get` could create new objects
+            // and exceed the `max_size` of the pool.
+            let Ok(_permit) = self.inner.try_acquire() else {
+                break;
+            };
+            let Some(inner_obj) = self.inner.idle.pop_oldest() else {
+                break;
+            };
+            let mut unready_obj = UnreadyObject {
+                inner: Some(inner_obj),
+                pool: &self.inner,
+            };
+            let inner = unready_obj.inner();
+            if self.inner.is_expired(inner) {
+                continue;
+            }
+            if let Err(e) = self
+                .with_timeout(
+                    TimeoutType::Recycle,
+                    timeouts.recycle,
+                    self.inner.manager().recycle(&mut inner.obj, &inner.metrics),
+                )
+                .await
+            {
+                if self.keep_after_recycle_timeout(&e) {
+                    let _ = self.inner.idle.push(unready_obj.ready());
+                    continue;
+                }
+                if let PoolError::Backend(e) = e {
+                    self.inner.record_recycle_error(&e);
+                }
+                AtomicCounters::incr(&self.inner.counters.recycle_failed);
+                self.inner.emit(PoolEvent::RecycleFailed {
+                    id: inner.id,
+                    metrics: inner.metrics,
+                });
+                continue;
+            }
+            AtomicCounters::incr(&self.inner.counters.recycled);
+            self.inner.emit(PoolEvent::ObjectRecycled {
+                id: inner.id,
+                metrics: inner.metrics,
+            });
+            let _ = self.inner.idle.push(unready_obj.ready());
+        }
+    }
+
+    /// Runs a single iteration of the autoscaler.
+    pub(crate) fn autoscale(&self, config: &AutoscaleConfig) {
+        let waited = self.inner.waited.swap(0, Ordering::Relaxed);
+        let wait_time = self.inner.wait_time.snapshot();
+        let sampled = self
+            .inner
+            .sampled_wait_time
+            .lock()
+            .unwrap()
+            .replace(wait_time.clone());
+        let wait_time = match sampled {
+            Some(sampled) => wait_time.since(&sampled),
+            None => wait_time,
+        };
+        let status = self.status();
+        let in_use = status.size - status.available;
+        let waiting = waited.max(status.waiting);
+        // Tasks which are still waiting haven't recorded their wait time,
+        // yet. If no task got an object since the last sample the target
+        // is considered to be missed.
+        let too_slow = match config.target_wait {
+            Some(target_wait) => wait_time
+                .quantile(config.wait_quantile)
+                .map_or(true, |wait| wait > target_wait),
+            None => true,
+        };
+        let max_size = if waiting > 0 && too_slow {
+            status.max_size + waiting
+        } else if waiting == 0
+            && (in_use as f64) < status.max_size as f64 * config.shrink_utilization
+        {
+            status.max_size - 1
+        } else {
+            status.max_size
+        };
+        // Not using `clamp` as it panics if `min_size > max_size`.
+        let max_size = max_size.min(config.max_size).max(config.min_size);
+        if max_size != status.max_size {
+            self.resize(max_size);
+        }
+    }
+
+    /// Creates new objects until at least `min_idle` objects are idle
+    /// or the `max_size` of the pool is reached.
+    ///
+    /// Returns the number of objects created.
+    pub(crate) async fn replenish(&self, min_idle: usize) -> Result<usize, PoolError<M::Error>> {
+        let mut created = 0;
+        while self.inner.idle.len() < min_idle {
+            if !self.add_idle().await? {
+                break;
+            }
+            created += 1;
+        }
+        self.inner.request_refill();
+        Ok(created)
+    }
+
+    /// Creates a new object and adds it to the pool as idle object without
+    /// waiting for a free slot.
+    ///
+    /// Returns `false` if the pool has no room for another object.
+    async fn add_idle(&self) -> Result<bool, PoolError<M::Error>> {
+        // The permit is only held while creating the object. Idle objects
+        // don't hold a permit.
+        let Ok(_permit) = self.inner.try_acquire() else {
+            return Ok(false);
+        };
+        if self.inner.slots.size() >= self.inner.slots.max_size() {
+            return Ok(false);
+        }
+        let inner_obj = loop {
+            if let Some(inner_obj) = self.try_create(&self.timeouts(), false, None).await? {
+                break inner_obj;
+            }
+        };
+        let _ = self.inner.idle.push(inner_obj);
+        Ok(true)
+    }
+
+    /**
+     * Resize the pool. This change the `max_size` of the pool dropping
+     * excess objects and/or making space for new ones.
+     *
+     * If the pool is closed this method does nothing. The [`Pool::status`] method
+     * always reports a `max_size` of 0 for closed pools.
+     *
+     * When shrinking, the permits of objects which are in use or being
+     * created are revoked once they are released. Growing the pool again
+     * before that cancels the pending revocations first, so the number of
+     * objects never exceeds the `max_size` in the long run and waiters
+     * are woken up as soon as there is room for them.
+     */
+    pub fn resize(&self, max_size: usize) {
+        if self.inner.semaphore.is_closed() {
+            return;
+        }
+        let _lock = self.inner.slots.lock();
+        let old_max_size = self.inner.slots.max_size.swap(max_size, Ordering::SeqCst);
+        // shrink pool
+        if max_size < old_max_size {
+            let _ = self
+                .inner
+                .slots
+                .revoked
+                .fetch_add(old_max_size - max_size, Ordering::SeqCst);
+            // Permits which are available are revoked right away. Objects
+            // in use and tasks creating objects hold the remaining ones
+            // which are revoked once they are released.
+            while let Ok(permit) = self.inner.try_acquire() {
+                if !self.inner.slots.take_revoked() {
+                    // Released permits paid off the remaining revocations
+                    // in the meantime.
+                    drop(permit);
+                    break;
+                }
+                permit.forget();
+                if self.inner.slots.size() > max_size {
+                    if let Some(inner) = self.inner.idle.pop_oldest() {
+                        if self.inner.slots.try_shrink() {
+                            self.inner.destroy(inner);
+                        } else {
+                            let _ = self.inner.idle.push(inner);
+                        }
+                    }
+                }
+            }
+            self.inner.idle.shrink_to(max_size);
+        }
+        // grow pool
+        if max_size > old_max_size {
+            // Pending revocations are cancelled instead of adding permits.
+            let additional = self.inner.slots.release(max_size - old_max_size);
+            self.inner.idle.reserve(additional);
+            self.inner.add_permits(additional);
+        }
+    }
+
+    /// Retains only the objects specified by the given function.
+    ///
+    /// This function is typically used to remove objects from
+    /// the pool based on their current state or metrics.
     ///
     /// **Caution:** This function blocks the entire pool while
     /// it is running. Therefore the given function should not
@@ -306,26 +1427,149 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
         &self,
         mut predicate: impl FnMut(&M::Type, Metrics) -> bool,
     ) -> RetainResult<M::Type> {
-        let mut removed = Vec::with_capacity(self.status().size);
-        let mut guard = self.inner.slots.lock().unwrap();
-        let mut i = 0;
-        // This code can be simplified once `Vec::extract_if` lands in stable Rust.
-        // https://doc.rust-lang.org/std/vec/struct.Vec.html#method.extract_if
-        while i < guard.vec.len() {
-            let obj = &mut guard.vec[i];
-            if predicate(&mut obj.obj, obj.metrics) {
-                i += 1;
-            } else {
-                let mut obj = guard.vec.remove(i).unwrap();
+        let _lock = self.inner.slots.lock();
+        let mut retained = 0;
+        let removed = self
+            .inner
+            .idle
+            .retain(|obj| {
+                let retain = predicate(&mut obj.obj, obj.metrics);
+                if retain {
+                    retained += 1;
+                }
+                retain
+            })
+            .into_iter()
+            .map(|mut obj| {
                 self.manager().detach(&mut obj.obj);
-                removed.push(obj.obj);
+                obj.obj
+            })
+            .collect::<Vec<_>>();
+        self.inner.slots.shrink(removed.len());
+        RetainResult { retained, removed }
+    }
+
+    /// Retains only the objects specified by the given async function.
+    ///
+    /// Unlike [`Pool::retain()`] this allows the decision to involve I/O,
+    /// e.g. pinging the server or checking a revocation list. The [`Pool`]
+    /// isn't blocked while the function is running. Every idle object is
+    /// taken out of the [`Pool`] while it is being checked and counts
+    /// towards its `max_size` in the meantime. Objects which are in use or
+    /// become idle while this function is running are not checked.
+    ///
+    /// ```rust,ignore
+    /// pool.retain_async(|conn, _| Box::pin(async move { conn.ping().await.is_ok() }))
+    ///     .await;
+    /// ```
+    pub async fn retain_async(
+        &self,
+        mut predicate: impl for<'a> FnMut(&'a M::Type, Metrics) -> RetainFuture<'a>,
+    ) -> RetainResult<M::Type> {
+        let mut result = RetainResult::default();
+        let count = self.inner.idle.len();
+        for _ in 0..count {
+            // The permit is held while checking the object. Otherwise
+            // concurrent calls to `Pool::get` could create new objects
+            // and exceed the `max_size` of the pool.
+            let Ok(_permit) = self.acquire_permit(None).await else {
+                break;
+            };
+            let Some(inner_obj) = self.inner.idle.pop_oldest() else {
+                break;
+            };
+            let mut unready_obj = UnreadyObject {
+                inner: Some(inner_obj),
+                pool: &self.inner,
+            };
+            let inner = unready_obj.inner();
+            if predicate(&inner.obj, inner.metrics).await {
+                result.retained += 1;
+                let _ = self.inner.idle.push(unready_obj.ready());
+            } else {
+                let mut inner = unready_obj.ready();
+                self.inner.slots.shrink(1);
+                self.inner.size_decreased.notify_waiters();
+                self.manager().detach(&mut inner.obj);
+                result.removed.push(inner.obj);
             }
         }
-        guard.size -= removed.len();
-        RetainResult {
-            retained: i,
-            removed,
+        result
+    }
+
+    /// Calls the given function for every idle object of this [`Pool`]
+    /// without checking it out.
+    ///
+    /// This is meant for debugging and statistics, e.g. for finding out
+    /// which objects are sitting in the [`Pool`] and for how long. Objects
+    /// which are currently in use are not visited.
+    ///
+    /// **Caution:** Just like [`Pool::retain()`] this function blocks the
+    /// entire pool while it is running. Therefore the given function should
+    /// not block.
+    ///
+    /// ```rust,ignore
+    /// let mut ages = Vec::new();
+    /// pool.visit_idle(|_, metrics| ages.push(metrics.age()));
+    /// ```
+    pub fn visit_idle(&self, mut f: impl FnMut(&M::Type, Metrics)) {
+        let _lock = self.inner.slots.lock();
+        self.inner.idle.for_each(|obj| f(&obj.obj, obj.metrics));
+    }
+
+    /// Invalidates all [`Object`]s which currently exist in this [`Pool`].
+    ///
+    /// Idle [`Object`]s are discarded when they are about to be handed out
+    /// and [`Object`]s which are currently in use are discarded when they
+    /// are returned to the [`Pool`]. New [`Object`]s are created on demand.
+    ///
+    /// This is useful after a configuration or credential change which
+    /// renders existing connections untrustworthy.
+    pub fn invalidate_all(&self) {
+        let _ = self.inner.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Discards all idle [`Object`]s right away.
+    ///
+    /// [`Object`]s which are currently in use are not affected. New
+    /// [`Object`]s are created on demand. This is useful after a failover
+    /// of the backend in order to force reconnects. Use
+    /// [`Pool::invalidate_all()`] to discard the [`Object`]s in use, too.
+    ///
+    /// Returns the number of discarded [`Object`]s.
+    pub fn purge_idle(&self) -> usize {
+        let lock = self.inner.slots.lock();
+        let removed = self.inner.idle.retain(|_| false);
+        self.inner.slots.shrink(removed.len());
+        drop(lock);
+        let count = removed.len();
+        if count > 0 {
+            self.inner.size_decreased.notify_waiters();
         }
+        for mut inner in removed {
+            self.inner.manager().detach(&mut inner.obj);
+            self.inner.destroy(inner);
+        }
+        self.inner.report_status();
+        count
+    }
+
+    /// Replaces the [`Manager`] of this [`Pool`] returning the previous one.
+    ///
+    /// All future [`Object`]s are created and recycled by the new
+    /// [`Manager`]. Existing [`Object`]s are invalidated just like
+    /// [`Pool::invalidate_all()`] does and phased out as they are returned.
+    /// Objects are detached via the [`Manager`] which is current when they
+    /// are discarded.
+    ///
+    /// This is useful for rotating credentials or failing over to another
+    /// endpoint without recreating the [`Pool`].
+    pub fn replace_manager(&self, manager: M) -> Arc<M> {
+        let mut guard = self.inner.manager.write().unwrap();
+        let old = std::mem::replace(&mut *guard, Arc::new(manager));
+        // Bumped while holding the lock. See `try_create`.
+        let _ = self.inner.generation.fetch_add(1, Ordering::Relaxed);
+        old
     }
 
     /// Get current timeout configuration
@@ -342,6 +1586,66 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
     pub fn close(&self) {
         self.resize(0);
         self.inner.semaphore.close();
+        self.inner.priority_waiters.close();
+    }
+
+    /// Closes this [`Pool`] and returns all idle objects instead of
+    /// dropping them.
+    ///
+    /// This allows handing the objects over to a replacement [`Pool`] via
+    /// [`Pool::add()`] or closing them gracefully. Just like with
+    /// [`Pool::close()`] [`Object`]s which are currently in use are dropped
+    /// as soon as they are returned to the [`Pool`].
+    pub fn drain(&self) -> Vec<M::Type> {
+        let lock = self.inner.slots.lock();
+        let removed = self.inner.idle.retain(|_| false);
+        self.inner.slots.shrink(removed.len());
+        drop(lock);
+        self.close();
+        removed
+            .into_iter()
+            .map(|mut inner| {
+                self.manager().detach(&mut inner.obj);
+                inner.obj
+            })
+            .collect()
+    }
+
+    /// Closes this [`Pool`] and waits for all [`Object`]s to be returned.
+    ///
+    /// Just like with [`Pool::close()`] all current and future tasks waiting
+    /// for [`Object`]s will return [`PoolError::Closed`] immediately. Idle
+    /// [`Object`]s are dropped right away while [`Object`]s which are
+    /// currently in use are detached and dropped as soon as they are
+    /// returned to the [`Pool`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoolError::Timeout`] if there are still [`Object`]s in use
+    /// after the given `timeout` has elapsed. In that case the [`Pool`] stays
+    /// closed and the remaining [`Object`]s are dropped when they are
+    /// returned.
+    ///
+    /// Returns [`PoolError::NoRuntimeSpecified`] if a `timeout` is given but
+    /// no [`Runtime`] was configured.
+    pub async fn close_graceful(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<(), PoolError<M::Error>> {
+        self.close();
+        apply_timeout(&self.inner, TimeoutType::Wait, timeout, async {
+            loop {
+                // The `Notified` future receives notifications as soon as it
+                // has been created. This makes sure no notification is missed
+                // between checking the size and awaiting the future.
+                let notified = self.inner.size_decreased.notified();
+                if self.inner.slots.size() == 0 && self.inner.overflow.load(Ordering::SeqCst) == 0 {
+                    return Ok::<_, PoolError<M::Error>>(());
+                }
+                notified.await;
+            }
+        })
+        .await
     }
 
     /// Indicates whether this [`Pool`] has been closed.
@@ -350,30 +1654,154 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
     }
 
     /// Retrieves [`Status`] of this [`Pool`].
+    ///
+    /// This only reads atomics and never waits for a lock, so it can be
+    /// called frequently, e.g. by metric scrapers, without slowing down
+    /// [`Pool::get()`].
     #[must_use]
     pub fn status(&self) -> Status {
-        let slots = self.inner.slots.lock().unwrap();
-        let users = self.inner.users.load(Ordering::Relaxed);
-        let (available, waiting) = if users < slots.size {
-            (slots.size - users, 0)
-        } else {
-            (0, users - slots.size)
+        self.inner.status()
+    }
+
+    /// Returns the name of this [`Pool`]. See [`PoolBuilder::name()`].
+    pub fn name(&self) -> Option<&str> {
+        self.inner.name.as_deref()
+    }
+
+    /// Returns the labels of this [`Pool`]. See [`PoolBuilder::label()`].
+    pub fn labels(&self) -> &[(String, String)] {
+        &self.inner.labels
+    }
+
+    /// Returns the [`Object`]s which are currently handed out by this
+    /// [`Pool`] along with the backtraces captured when they were handed
+    /// out.
+    ///
+    /// This is meant for diagnosing an exhausted [`Pool`]. Capturing the
+    /// backtraces slows down [`Pool::get()`] noticeably.
+    #[cfg(feature = "debug-leases")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "debug-leases")))]
+    pub fn leases(&self) -> Vec<LeaseInfo> {
+        self.inner
+            .leases
+            .as_ref()
+            .map(Leases::snapshot)
+            .unwrap_or_default()
+    }
+
+    /// Actively probes this [`Pool`] and returns a [`Health`] report
+    /// suitable for readiness checks.
+    ///
+    /// Unlike calling [`Pool::get()`] this never waits for a slot and never
+    /// hands out an [`Object`]. If a slot is free an idle [`Object`] is
+    /// recycled or, if there is none, a new one is created and added to
+    /// the [`Pool`]. Otherwise the probe is skipped and reported as
+    /// [`HealthProbe::Busy`].
+    ///
+    /// The probe must finish within the given `timeout`. This is only
+    /// enforced if a [`Runtime`] is configured.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn health(&self, timeout: Duration) -> Health {
+        let start = clock::now();
+        let probe = match self.inner.try_acquire() {
+            Err(TryAcquireError::Closed) => HealthProbe::Closed,
+            Err(TryAcquireError::NoPermits) => HealthProbe::Busy,
+            Ok(_permit) => {
+                let deadline = start + timeout;
+                let timeouts = || match self.inner.runtime {
+                    Some(_) => timeouts_until(&self.timeouts(), deadline),
+                    None => self.timeouts(),
+                };
+                match self.probe(timeouts).await {
+                    Ok(probe) => probe,
+                    Err(e) => HealthProbe::Failed(e.kind()),
+                }
+            }
         };
-        Status {
-            max_size: slots.max_size,
-            size: slots.size,
-            available,
-            waiting,
+        Health {
+            probe,
+            elapsed: clock::elapsed(start),
+            status: self.status(),
+            last_error: self.last_error(),
         }
     }
 
+    /// Recycles an idle object or creates a new one and adds it back to
+    /// the idle objects. The caller must hold a permit.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn probe(
+        &self,
+        timeouts: impl Fn() -> Timeouts,
+    ) -> Result<HealthProbe, PoolError<M::Error>> {
+        loop {
+            let (inner_obj, probe) = if let Some(inner_obj) = self.inner.idle.pop() {
+                // Unlike `try_recycle` this always recycles the object.
+                // Broken objects are discarded just like in `Pool::get()`.
+                let mut unready_obj = UnreadyObject {
+                    inner: Some(inner_obj),
+                    pool: &self.inner,
+                };
+                let inner = unready_obj.inner();
+                if self.inner.is_expired(inner) || !self.recycle(&timeouts(), inner).await? {
+                    continue;
+                }
+                (Some(unready_obj.ready()), HealthProbe::Recycled)
+            } else {
+                if self.inner.slots.size() >= self.inner.slots.max_size() {
+                    return Ok(HealthProbe::Busy);
+                }
+                let inner_obj = self.try_create(&timeouts(), false, None).await?;
+                (inner_obj, HealthProbe::Created)
+            };
+            if let Some(mut inner_obj) = inner_obj {
+                inner_obj.recycled = true;
+                let _ = self.inner.idle.push(inner_obj);
+                return Ok(probe);
+            }
+        }
+    }
+
+    /// Retrieves the [`Stats`] of this [`Pool`].
+    pub fn stats(&self) -> Stats {
+        Stats {
+            wait_time: self.inner.wait_time.snapshot(),
+            create_time: self.inner.create_time.snapshot(),
+            rates: self.inner.rates.snapshot(),
+        }
+    }
+
+    /// Retrieves the cumulative [`Counters`] of this [`Pool`].
+    pub fn counters(&self) -> Counters {
+        self.inner.counters.snapshot()
+    }
+
+    /// Returns the most recent error which occurred while creating or
+    /// recycling an [`Object`].
+    ///
+    /// This includes errors which weren't returned to any caller, e.g.
+    /// failed recycles and retried creates. The error is kept until the
+    /// next one occurs. Compare its [`LastError::time`] in order to tell
+    /// whether the [`Pool`] recovered in the meantime.
+    pub fn last_error(&self) -> Option<LastError> {
+        self.inner.last_error.lock().unwrap().clone()
+    }
+
     /// Returns [`Manager`] of this [`Pool`].
+    ///
+    /// This allows calling backend specific methods of the [`Manager`]
+    /// without keeping a separate reference to it. The [`Manager`] can be
+    /// replaced via [`Pool::replace_manager()`] so this returns the current
+    /// one.
     #[must_use]
-    pub fn manager(&self) -> &M {
-        &self.inner.manager
+    pub fn manager(&self) -> Arc<M> {
+        self.inner.manager()
     }
 
     /// Returns a [`WeakPool<T>`] of this [`Pool`].
+    ///
+    /// The [`WeakPool<T>`] doesn't keep the [`Pool`] alive and can be
+    /// turned back into a [`Pool`] via [`WeakPool::upgrade()`] as long as
+    /// the [`Pool`] hasn't been dropped.
     pub fn weak(&self) -> WeakPool<M> {
         WeakPool {
             inner: Arc::downgrade(&self.inner),
@@ -399,6 +1827,16 @@ pub struct WeakPool<M: Manager, W: From<Object<M>> = Object<M>> {
     _wrapper: PhantomData<fn() -> W>,
 }
 
+// Implemented manually to avoid unnecessary trait bound on the struct.
+impl<M: Manager, W: From<Object<M>>> Clone for WeakPool<M, W> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _wrapper: PhantomData,
+        }
+    }
+}
+
 impl<M: Manager, W: From<Object<M>>> WeakPool<M, W> {
     /// Attempts to upgrade the `WeakPool` to a strong [`Pool<T>`] reference.
     ///
@@ -413,23 +1851,197 @@ impl<M: Manager, W: From<Object<M>>> WeakPool<M, W> {
 }
 
 pub(crate) struct PoolInner<M: Manager> {
-    manager: M,
+    /// See [`PoolBuilder::name()`].
+    name: Option<String>,
+    /// See [`PoolBuilder::label()`].
+    labels: Vec<(String, String)>,
+    /// Objects which are currently handed out. Only tracked if the
+    /// [`PoolConfig::lease_timeout`] is set or the `debug-leases` feature
+    /// is enabled.
+    leases: Option<Leases>,
+    /// Current manager. See [`Pool::replace_manager()`].
+    manager: RwLock<Arc<M>>,
     next_id: AtomicUsize,
-    slots: Mutex<Slots<ObjectInner<M>>>,
+    /// See [`PoolBuilder::id_generator()`].
+    id_generator: Option<Box<IdGeneratorFn>>,
+    /// Current generation of the pool. Objects created with an older
+    /// generation are discarded. See [`Pool::invalidate_all()`].
+    generation: AtomicUsize,
+    slots: Slots,
+    /// Idle objects. Operations taking idle objects out of the queue
+    /// temporarily must hold the lock of `slots`. See
+    /// [`PoolInner::idle_is_empty()`].
+    idle: IdleQueue<ObjectInner<M>>,
     /// Number of ['Pool'] users. A user is both a future which is waiting for an ['Object'] or one
     /// with an ['Object'] which hasn't been returned, yet.
-    users: AtomicUsize,
+    users: ShardedGauge,
+    /// Number of futures which are waiting for a permit of the `semaphore`.
+    waiters: AtomicUsize,
+    /// Number of futures which had to wait for a permit since the
+    /// autoscaler last sampled the pool.
+    waited: AtomicUsize,
+    /// Number of in-flight calls to [`Manager::create()`].
+    creating: ShardedGauge,
+    counters: AtomicCounters,
+    /// See [`Pool::last_error()`].
+    last_error: Mutex<Option<LastError>>,
+    /// See [`Stats::wait_time`].
+    wait_time: AtomicHistogram,
+    /// Snapshot of the `wait_time` taken when the autoscaler last sampled
+    /// the pool.
+    sampled_wait_time: Mutex<Option<Histogram>>,
+    /// See [`Stats::create_time`].
+    create_time: AtomicHistogram,
+    /// See [`Stats::rates`].
+    rates: AtomicRates,
+    #[cfg(feature = "metrics")]
+    recorder: Recorder,
+    /// Number of overflow objects. See [`PoolConfig::max_overflow`].
+    overflow: AtomicUsize,
     semaphore: Semaphore,
+    /// Tasks waiting via [`Pool::get_with_priority()`]. Released permits are
+    /// handed to them before being added to the `semaphore`.
+    priority_waiters: PriorityQueue,
+    /// Notified every time an object leaves the pool for good. This is used
+    /// by [`Pool::close_graceful()`] to wait for all objects to be returned.
+    size_decreased: Notify,
+    breaker: CircuitBreaker,
+    /// Objects handed out via [`Pool::get_shared()`]. They are type-erased
+    /// so only [`Pool::get_shared()`] requires the objects to be [`Sync`].
+    shared: Mutex<Vec<Weak<dyn Any + Send + Sync>>>,
+    /// Sender for objects which are recycled when being returned. See
+    /// [`PoolConfig::recycle_on_return`].
+    recycler: Option<mpsc::UnboundedSender<ObjectInner<M>>>,
+    /// Sender for requests to refill the buffer of objects which are
+    /// recycled ahead of time. See [`PoolConfig::recycle_ahead`].
+    refill: Option<mpsc::Sender<()>>,
+    /// Sender for objects which are handed to [`Manager::destroy()`].
+    destroyer: Option<mpsc::UnboundedSender<(Arc<M>, M::Type)>>,
     config: PoolConfig,
     runtime: Option<Runtime>,
     hooks: Hooks<M>,
 }
 
+/// Accounting of the slots of a pool.
+///
+/// The counters are atomics so returning and creating objects doesn't need
+/// a lock. Operations which need a consistent view of the idle objects,
+/// e.g. resizing the pool or taking idle objects out of the queue
+/// temporarily, are serialized by the `lock` instead. See
+/// [`PoolInner::idle_is_empty()`].
 #[derive(Debug)]
-struct Slots<T> {
-    vec: VecDeque<T>,
-    size: usize,
-    max_size: usize,
+struct Slots {
+    lock: Mutex<()>,
+    size: AtomicUsize,
+    max_size: AtomicUsize,
+    /// Number of permits which still have to be revoked because the pool
+    /// has been shrunk while they were held. See [`Pool::resize()`].
+    revoked: AtomicUsize,
+}
+
+impl Slots {
+    fn new(max_size: usize) -> Self {
+        Self {
+            lock: Mutex::new(()),
+            size: AtomicUsize::new(0),
+            max_size: AtomicUsize::new(max_size),
+            revoked: AtomicUsize::new(0),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ()> {
+        self.lock.lock().unwrap()
+    }
+
+    fn size(&self) -> usize {
+        self.size.load(Ordering::SeqCst)
+    }
+
+    fn max_size(&self) -> usize {
+        self.max_size.load(Ordering::SeqCst)
+    }
+
+    /// Occupies a slot for a newly created object.
+    fn grow(&self) {
+        let _ = self.size.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Occupies a slot unless the pool is full.
+    fn try_grow(&self) -> bool {
+        let mut size = self.size();
+        while size < self.max_size() {
+            match self.size.compare_exchange_weak(
+                size,
+                size + 1,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => return true,
+                Err(actual) => size = actual,
+            }
+        }
+        false
+    }
+
+    /// Frees the slots of `n` objects.
+    fn shrink(&self, n: usize) {
+        let _ = self.size.fetch_sub(n, Ordering::SeqCst);
+    }
+
+    /// Frees a slot if the pool exceeds its `max_size`.
+    fn try_shrink(&self) -> bool {
+        let mut size = self.size();
+        while size > self.max_size() {
+            match self.size.compare_exchange_weak(
+                size,
+                size - 1,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => return true,
+                Err(actual) => size = actual,
+            }
+        }
+        false
+    }
+
+    /// Uses `n` released permits to pay off the revoked ones first and
+    /// returns the number of permits which are left to be added to the
+    /// semaphore.
+    fn release(&self, n: usize) -> usize {
+        let mut revoked = self.revoked.load(Ordering::SeqCst);
+        while revoked > 0 && n > 0 {
+            let paid = revoked.min(n);
+            match self.revoked.compare_exchange_weak(
+                revoked,
+                revoked - paid,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => return n - paid,
+                Err(actual) => revoked = actual,
+            }
+        }
+        n
+    }
+
+    /// Revokes a permit which has been taken from the semaphore. Returns
+    /// `false` if there are no revoked permits left.
+    fn take_revoked(&self) -> bool {
+        let mut revoked = self.revoked.load(Ordering::SeqCst);
+        while revoked > 0 {
+            match self.revoked.compare_exchange_weak(
+                revoked,
+                revoked - 1,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => return true,
+                Err(actual) => revoked = actual,
+            }
+        }
+        false
+    }
 }
 
 // Implemented manually to avoid unnecessary trait bound on the struct.
@@ -442,8 +2054,10 @@ where
         f.debug_struct("PoolInner")
             .field("manager", &self.manager)
             .field("slots", &self.slots)
+            .field("idle", &self.idle)
             .field("used", &self.users)
             .field("semaphore", &self.semaphore)
+            .field("priority_waiters", &self.priority_waiters)
             .field("config", &self.config)
             .field("runtime", &self.runtime)
             .field("hooks", &self.hooks)
@@ -452,29 +2066,457 @@ where
 }
 
 impl<M: Manager> PoolInner<M> {
+    /// Returns the name of the pool or an empty string.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or_default()
+    }
+
+    /// Returns the id of a new object. See [`PoolBuilder::id_generator()`].
+    fn next_id(&self) -> usize {
+        match &self.id_generator {
+            Some(f) => f(),
+            None => self.next_id.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the current [`Manager`].
+    pub(crate) fn manager(&self) -> Arc<M> {
+        self.manager.read().unwrap().clone()
+    }
+
+    /// Returns the current [`Status`] by summing up the sharded gauges. No
+    /// lock is taken so scraping it often doesn't slow down [`Pool::get()`].
+    fn status(&self) -> Status {
+        let size = self.slots.size();
+        let waiting = self.waiters.load(Ordering::Relaxed);
+        // Overflow objects don't occupy a slot of the pool.
+        let users = self
+            .users
+            .get()
+            .saturating_sub(self.overflow.load(Ordering::Relaxed))
+            .saturating_sub(waiting);
+        Status {
+            max_size: self.slots.max_size(),
+            size,
+            available: size.saturating_sub(users),
+            waiting,
+            creating: self.creating.get(),
+        }
+    }
+
+    /// Reports the current [`Status`] to the `metrics` recorder.
+    fn report_status(&self) {
+        #[cfg(feature = "metrics")]
+        self.recorder.status(&self.status());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn record_wait_time(&self, duration: Duration) {
+        self.wait_time.record(duration);
+        self.rates.record(RateEvent::Get);
+        #[cfg(feature = "metrics")]
+        self.recorder.wait_time(duration);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn record_create_time(&self, duration: Duration) {
+        self.create_time.record(duration);
+        #[cfg(feature = "metrics")]
+        self.recorder.create_time(duration);
+    }
+
     pub(crate) fn return_object(&self, mut inner: ObjectInner<M>) {
-        let _ = self.users.fetch_sub(1, Ordering::Relaxed);
-        let mut slots = self.slots.lock().unwrap();
-        if slots.size <= slots.max_size {
-            slots.vec.push_back(inner);
-            drop(slots);
-            self.semaphore.add_permits(1);
+        self.release_lease(&inner);
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            inner.metrics.returned = Some(clock::now());
+        }
+        // Apply pre_return hooks
+        if inner.overflow
+            || self.is_expired(&inner)
+            || self.hooks.pre_return.apply(&mut inner).is_err()
+        {
+            self.detach_object(&mut inner);
+            self.destroy(inner);
+            return;
+        }
+        if let Some(recycler) = &self.recycler {
+            // The object keeps its permit until it has been recycled.
+            match recycler.send(inner) {
+                Ok(()) => return,
+                Err(mpsc::error::SendError(returned)) => inner = returned,
+            }
+        }
+        self.make_idle(inner);
+    }
+
+    /// Stops tracking the lease of an object which left the hands of its
+    /// user.
+    fn release_lease(&self, inner: &ObjectInner<M>) {
+        if let Some(leases) = &self.leases {
+            leases.release(inner.id);
+        }
+    }
+
+    /// Adds a returned object to the idle objects and releases its permits.
+    ///
+    /// This only updates atomics and the shard the object is pushed to so
+    /// returning objects doesn't contend on a pool wide lock.
+    fn make_idle(&self, mut inner: ObjectInner<M>) {
+        self.users.decr();
+        let permits = self.slots.release(inner.weight);
+        if self.slots.try_shrink() {
+            // The pool is shrinking so the slot of the object is dropped.
+            self.add_permits(permits);
+            self.size_decreased.notify_waiters();
+            self.manager().detach(&mut inner.obj);
+            self.destroy(inner);
         } else {
-            slots.size -= 1;
-            drop(slots);
-            self.manager.detach(&mut inner.obj);
+            let shard = self.idle.push(inner);
+            let evicted = self.evict_idle(shard);
+            self.add_permits(permits);
+            self.request_refill();
+            for mut inner in evicted {
+                self.manager().detach(&mut inner.obj);
+                self.destroy(inner);
+            }
+        }
+        self.report_status();
+    }
+
+    /// Hands a discarded object to [`Manager::destroy()`]. Without a
+    /// [`Runtime`] the object is simply dropped.
+    fn destroy(&self, inner: ObjectInner<M>) {
+        self.emit(PoolEvent::ObjectDropped {
+            id: inner.id,
+            metrics: inner.metrics,
+        });
+        if let Some(destroyer) = &self.destroyer {
+            let _ = destroyer.send((self.manager(), inner.obj));
         }
     }
-    pub(crate) fn detach_object(&self, obj: &mut M::Type) {
-        let _ = self.users.fetch_sub(1, Ordering::Relaxed);
-        let mut slots = self.slots.lock().unwrap();
-        let add_permits = slots.size <= slots.max_size;
-        slots.size -= 1;
-        drop(slots);
-        if add_permits {
-            self.semaphore.add_permits(1);
+
+    /// Calls the [`PoolBuilder::on_event()`] callbacks.
+    fn emit(&self, event: PoolEvent) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            pool.name = self.name(),
+            pool.labels = ?self.labels,
+            event = ?event,
+            "pool event",
+        );
+        for callback in self.hooks.on_event.iter() {
+            callback(&event);
+        }
+    }
+
+    /// Creates the [`TimeoutError`] of a timeout which just occurred.
+    fn timeout_error(
+        &self,
+        timeout_type: TimeoutType,
+        timeout: Duration,
+        #[cfg(not(target_arch = "wasm32"))] elapsed: Duration,
+    ) -> TimeoutError {
+        TimeoutError {
+            timeout_type,
+            timeout,
+            #[cfg(not(target_arch = "wasm32"))]
+            elapsed,
+            status: self.status(),
+        }
+    }
+
+    /// Stores the error returned by [`Pool::last_error()`].
+    fn record_error(&self, stage: ErrorStage, kind: PoolErrorKind, message: Option<String>) {
+        *self.last_error.lock().unwrap() = Some(LastError {
+            stage,
+            kind,
+            message,
+            #[cfg(not(target_arch = "wasm32"))]
+            time: SystemTime::now(),
+        });
+    }
+
+    /// Stores an error returned by [`Manager::recycle()`].
+    fn record_recycle_error(&self, e: &RecycleError<M::Error>) {
+        let message = match e {
+            RecycleError::Message(msg) => Some(msg.to_string()),
+            RecycleError::Backend(e) => self.describe_error(e),
+        };
+        self.record_error(ErrorStage::Recycle, PoolErrorKind::Backend, message);
+    }
+
+    /// Describes an error of the [`Manager`] using the function attached
+    /// via [`PoolBuilder::describe_error()`].
+    fn describe_error(&self, e: &M::Error) -> Option<String> {
+        self.hooks.describe_error.as_ref().map(|f| f(e))
+    }
+
+    /// Counts a timeout, emits the corresponding event and calls the
+    /// [`PoolBuilder::on_timeout()`] callbacks.
+    fn timeout(&self, e: &TimeoutError) {
+        self.counters.timeout(e.timeout_type);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.rates.record(RateEvent::Timeout);
+        self.emit(PoolEvent::Timeout(e.timeout_type));
+        let stage = match e.timeout_type {
+            TimeoutType::Wait => None,
+            TimeoutType::Create => Some(ErrorStage::Create),
+            TimeoutType::Recycle => Some(ErrorStage::Recycle),
+        };
+        if let Some(stage) = stage {
+            self.record_error(stage, PoolErrorKind::Timeout, Some(e.to_string()));
+        }
+        for callback in self.hooks.on_timeout.iter() {
+            callback(e);
+        }
+    }
+    /// Removes an object which is taken via [`Object::take()`] from the
+    /// pool.
+    pub(crate) fn take_object(&self, inner: &mut ObjectInner<M>) {
+        // Apply on_detach hooks
+        self.hooks.on_detach.apply(inner);
+        self.release_lease(inner);
+        AtomicCounters::incr(&self.counters.detached);
+        if self.config.detach_mode == DetachMode::Shrink && !inner.overflow {
+            self.detach_and_shrink(inner);
+        } else {
+            self.detach_object(inner);
+        }
+    }
+
+    /// Detaches the object and decreases the `max_size` of the pool along
+    /// with its size. See [`DetachMode::Shrink`].
+    fn detach_and_shrink(&self, inner: &mut ObjectInner<M>) {
+        self.users.decr();
+        let lock = self.slots.lock();
+        self.slots.shrink(1);
+        if self.slots.max_size() > 0 {
+            // The permit of the slot is dropped along with it.
+            let _ = self.slots.max_size.fetch_sub(1, Ordering::SeqCst);
+            let _ = self.slots.revoked.fetch_add(1, Ordering::SeqCst);
         }
-        self.manager.detach(obj);
+        drop(lock);
+        let permits = self.slots.release(inner.weight);
+        self.add_permits(permits);
+        self.size_decreased.notify_waiters();
+        self.manager().detach(&mut inner.obj);
+        self.report_status();
+    }
+
+    pub(crate) fn detach_object(&self, inner: &mut ObjectInner<M>) {
+        self.users.decr();
+        if inner.overflow {
+            self.release_overflow();
+            self.manager().detach(&mut inner.obj);
+            self.report_status();
+            return;
+        }
+        self.slots.shrink(1);
+        let permits = self.slots.release(inner.weight);
+        self.add_permits(permits);
+        self.size_decreased.notify_waiters();
+        self.manager().detach(&mut inner.obj);
+        self.report_status();
+    }
+
+    /// Reserves room for an overflow object unless the
+    /// [`PoolConfig::max_overflow`] is reached.
+    fn reserve_overflow(&self) -> bool {
+        let mut n = self.overflow.load(Ordering::SeqCst);
+        while n < self.config.max_overflow {
+            match self
+                .overflow
+                .compare_exchange_weak(n, n + 1, Ordering::SeqCst, Ordering::SeqCst)
+            {
+                Ok(_) => return true,
+                Err(actual) => n = actual,
+            }
+        }
+        false
+    }
+
+    fn release_overflow(&self) {
+        let _ = self.overflow.fetch_sub(1, Ordering::SeqCst);
+        self.size_decreased.notify_waiters();
+    }
+
+    /// Checks whether there are no idle objects while holding the lock of
+    /// the slots.
+    ///
+    /// Operations holding that lock might take idle objects out of the
+    /// queue temporarily. Waiting for the lock makes sure they are back
+    /// before deciding to create a new object instead.
+    fn idle_is_empty(&self) -> bool {
+        let _lock = self.slots.lock();
+        self.idle.is_empty()
+    }
+
+    /// Finds a shared object which has room for another holder. See
+    /// [`Pool::get_shared()`].
+    fn try_share(&self) -> Option<SharedObject<M>>
+    where
+        M: 'static,
+        M::Type: Sync,
+    {
+        let mut shared = self.shared.lock().unwrap();
+        shared.retain(|obj| obj.strong_count() > 0);
+        shared
+            .iter()
+            .filter(|obj| obj.strong_count() < self.config.max_holders)
+            .min_by_key(|obj| obj.strong_count())
+            .and_then(Weak::upgrade)
+            .and_then(|obj| obj.downcast().ok())
+            .map(|inner| SharedObject { inner })
+    }
+
+    /// Asks the refill task to recycle more idle objects ahead of time if
+    /// the buffer isn't full. See [`PoolConfig::recycle_ahead`].
+    fn request_refill(&self) {
+        if let Some(refill) = &self.refill {
+            if self.idle.ready_len() < self.config.recycle_ahead {
+                // Fails if a request is pending already.
+                let _ = refill.try_send(());
+            }
+        }
+    }
+
+    /// Releases `n` permits which aren't accounted for in the `slots`
+    /// yet. Permits which have been revoked by [`Pool::resize()`] are
+    /// dropped instead.
+    fn release_permits(&self, n: usize) {
+        if n == 0 {
+            return;
+        }
+        let n = self.slots.release(n);
+        self.add_permits(n);
+    }
+
+    /// Adds `n` permits handing them to the [`PriorityQueue`] first.
+    fn add_permits(&self, n: usize) {
+        if n == 0 {
+            return;
+        }
+        let n = self.priority_waiters.release(n);
+        if n > 0 {
+            self.semaphore.add_permits(n);
+        }
+    }
+
+    fn try_acquire(&self) -> Result<Permit<'_, M>, TryAcquireError> {
+        self.semaphore.try_acquire()?.forget();
+        Ok(Permit {
+            pool: self,
+            count: 1,
+        })
+    }
+
+    fn try_acquire_many(&self, n: usize) -> Result<Permit<'_, M>, TryAcquireError> {
+        let n32 = u32::try_from(n).map_err(|_| TryAcquireError::NoPermits)?;
+        self.semaphore.try_acquire_many(n32)?.forget();
+        Ok(Permit {
+            pool: self,
+            count: n,
+        })
+    }
+
+    /// Checks whether the given object exceeded one of the configured limits
+    /// and needs to be discarded instead of being handed out again.
+    fn is_expired(&self, inner: &ObjectInner<M>) -> bool {
+        self.exceeds_limits(inner) || self.is_idle_expired(inner)
+    }
+
+    /// Checks whether the given object exceeded one of the hard limits,
+    /// i.e. everything but the [`PoolConfig::idle_timeout`].
+    fn exceeds_limits(&self, inner: &ObjectInner<M>) -> bool {
+        if inner.generation < self.generation.load(Ordering::Relaxed) {
+            return true;
+        }
+        // Pinned objects carry state which can't be restored so the
+        // configured limits don't apply to them.
+        if inner.pinned {
+            return false;
+        }
+        if let Some(max_uses) = self.config.max_uses {
+            if inner.metrics.use_count >= max_uses {
+                return true;
+            }
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(max_lifetime) = self.config.max_lifetime {
+            if inner.metrics.age() >= max_lifetime {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Checks whether the given object has been idle for longer than the
+    /// [`PoolConfig::idle_timeout`].
+    fn is_idle_expired(&self, inner: &ObjectInner<M>) -> bool {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(idle_timeout) = self.config.idle_timeout {
+            return !inner.pinned && inner.metrics.last_used() >= idle_timeout;
+        }
+        #[cfg(target_arch = "wasm32")]
+        let _ = inner;
+        false
+    }
+
+    /// Removes all idle objects which exceeded one of the configured limits.
+    ///
+    /// Objects exceeding the idle timeout are only removed as long as more
+    /// than [`PoolConfig::min_idle`] objects are idle. Otherwise they would
+    /// be replaced right away by the maintenance task.
+    pub(crate) fn remove_expired(&self) {
+        let lock = self.slots.lock();
+        let mut removed = self.idle.retain(|inner| !self.exceeds_limits(inner));
+        if self.config.idle_timeout.is_some() {
+            let mut excess = self.idle.len().saturating_sub(self.config.min_idle);
+            removed.extend(self.idle.retain(|inner| {
+                if excess == 0 || !self.is_idle_expired(inner) {
+                    return true;
+                }
+                excess -= 1;
+                false
+            }));
+        }
+        self.slots.shrink(removed.len());
+        drop(lock);
+        for mut inner in removed {
+            self.manager().detach(&mut inner.obj);
+            self.destroy(inner);
+        }
+    }
+
+    /// Removes the idle objects of the given `shard` which exceeded the idle
+    /// timeout. Objects are always returned to the back of a shard so the
+    /// front holds the ones which have been idle for the longest time. Like
+    /// with [`PoolInner::remove_expired()`] at least
+    /// [`PoolConfig::min_idle`] objects are kept.
+    ///
+    /// With [`QueueMode::Fifo`](super::QueueMode::Fifo) nothing is removed
+    /// here. Those objects are discarded when being checked out or by the
+    /// maintenance task.
+    fn evict_idle(&self, shard: usize) -> Vec<ObjectInner<M>> {
+        if self.config.idle_timeout.is_none() {
+            return Vec::new();
+        }
+        let mut excess = self.idle.len().saturating_sub(self.config.min_idle);
+        let evicted = self.idle.pop_front_while(shard, |inner| {
+            if self.exceeds_limits(inner) {
+                excess = excess.saturating_sub(1);
+                return true;
+            }
+            if excess == 0 || !self.is_idle_expired(inner) {
+                return false;
+            }
+            excess -= 1;
+            true
+        });
+        self.slots.shrink(evicted.len());
+        evicted
     }
 }
 
@@ -495,31 +2537,120 @@ impl<M: Manager> UnreadyObject<'_, M> {
 impl<M: Manager> Drop for UnreadyObject<'_, M> {
     fn drop(&mut self) {
         if let Some(mut inner) = self.inner.take() {
-            self.pool.slots.lock().unwrap().size -= 1;
-            self.pool.manager.detach(&mut inner.obj);
+            // The reservation of overflow objects is released by the caller.
+            if !inner.overflow {
+                self.pool.slots.shrink(1);
+                self.pool.size_decreased.notify_waiters();
+            }
+            self.pool.manager().detach(&mut inner.obj);
+            self.pool.destroy(inner);
         }
     }
 }
 
-async fn apply_timeout<O, E>(
-    runtime: Option<Runtime>,
+/// Limits all `timeouts` to the time remaining until the `deadline`.
+#[cfg(not(target_arch = "wasm32"))]
+fn timeouts_until(timeouts: &Timeouts, deadline: Instant) -> Timeouts {
+    let remaining = deadline.saturating_duration_since(clock::now());
+    let limit = |timeout: Option<Duration>| Some(timeout.map_or(remaining, |t| t.min(remaining)));
+    Timeouts {
+        wait: limit(timeouts.wait),
+        create: limit(timeouts.create),
+        recycle: limit(timeouts.recycle),
+    }
+}
+
+/// Slot reserved for an object which is about to be handed out.
+enum Slot<'a, M: Manager> {
+    /// The object occupies a slot of the pool.
+    Permit(Permit<'a, M>),
+    /// The object is created on top of the `max_size`. See
+    /// [`PoolConfig::max_overflow`].
+    Overflow,
+}
+
+/// Permits which have been taken from the semaphore or handed to a
+/// priority waiter. They are released via [`PoolInner::release_permits()`]
+/// when dropped.
+struct Permit<'a, M: Manager> {
+    pool: &'a PoolInner<M>,
+    count: usize,
+}
+
+impl<M: Manager> Permit<'_, M> {
+    fn forget(self) {
+        std::mem::forget(self)
+    }
+
+    /// Takes over the permits of `other`.
+    fn merge(&mut self, other: Self) {
+        self.count += other.count;
+        other.forget();
+    }
+
+    /// Releases `n` of the permits right away.
+    fn release(&mut self, n: usize) {
+        self.count -= n;
+        self.pool.release_permits(n);
+    }
+}
+
+impl<M: Manager> Drop for Permit<'_, M> {
+    fn drop(&mut self) {
+        self.pool.release_permits(self.count);
+    }
+}
+
+/// Registration of a task in the [`PriorityQueue`]. If a permit has been
+/// handed to the task but the task stopped waiting for it the permit is
+/// released again when this is dropped.
+struct Handoff<'a, M: Manager> {
+    receiver: Option<oneshot::Receiver<()>>,
+    pool: &'a PoolInner<M>,
+}
+
+impl<M: Manager> Drop for Handoff<'_, M> {
+    fn drop(&mut self) {
+        if let Some(mut receiver) = self.receiver.take() {
+            receiver.close();
+            if receiver.try_recv().is_ok() {
+                self.pool.release_permits(1);
+            }
+        }
+    }
+}
+
+async fn apply_timeout<M: Manager, O, E>(
+    pool: &PoolInner<M>,
     timeout_type: TimeoutType,
     duration: Option<Duration>,
     future: impl Future<Output = Result<O, impl Into<PoolError<E>>>>,
 ) -> Result<O, PoolError<E>> {
-    match (runtime, duration) {
+    match (pool.runtime, duration) {
         (_, None) => future.await.map_err(Into::into),
-        (Some(runtime), Some(duration)) => runtime
-            .timeout(duration, future)
-            .await
-            .ok_or(PoolError::Timeout(timeout_type))?
-            .map_err(Into::into),
+        (Some(runtime), Some(duration)) => {
+            #[cfg(not(target_arch = "wasm32"))]
+            let start = clock::now();
+            match runtime.timeout(duration, future).await {
+                Some(result) => result.map_err(Into::into),
+                None => Err(PoolError::Timeout(pool.timeout_error(
+                    timeout_type,
+                    duration,
+                    #[cfg(not(target_arch = "wasm32"))]
+                    clock::elapsed(start),
+                ))),
+            }
+        }
         (None, Some(_)) => Err(PoolError::NoRuntimeSpecified),
     }
 }
 
+/// The boxed future that should be returned by the function passed to
+/// [`Pool::retain_async()`].
+pub type RetainFuture<'a> = Pin<Box<dyn Future<Output = bool> + Send + 'a>>;
+
 #[derive(Debug)]
-/// This is the result returned by `Pool::retain`
+/// This is the result returned by `Pool::retain` and `Pool::retain_async`
 pub struct RetainResult<T> {
     /// Number of retained objects
     pub retained: usize,
@@ -0,0 +1,111 @@
+//! Queue of tasks waiting for an object with a priority.
+
+use std::{
+    cmp::Ordering as CmpOrdering,
+    collections::BinaryHeap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+use tokio::sync::oneshot;
+
+/// Queue of tasks waiting for a permit with a priority.
+///
+/// Permits which are released by the pool are handed to these waiters
+/// before they are added back to the semaphore. See
+/// [`Pool::get_with_priority()`](super::Pool::get_with_priority).
+#[derive(Debug, Default)]
+pub(crate) struct PriorityQueue {
+    /// Number of entries in the queue. This is used to avoid locking the
+    /// mutex when releasing permits while nobody waits with a priority.
+    len: AtomicUsize,
+    waiters: Mutex<Waiters>,
+}
+
+#[derive(Debug, Default)]
+struct Waiters {
+    heap: BinaryHeap<Waiter>,
+    next_seq: u64,
+}
+
+#[derive(Debug)]
+struct Waiter {
+    priority: u8,
+    seq: u64,
+    sender: oneshot::Sender<()>,
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == CmpOrdering::Equal
+    }
+}
+
+impl Eq for Waiter {}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Waiter {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        // Higher priorities first. Waiters with the same priority are
+        // served in the order they started waiting.
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl PriorityQueue {
+    /// Adds a new waiter to the queue. The returned receiver completes
+    /// once a permit has been handed to the waiter.
+    ///
+    /// The receiver fails if the queue is closed.
+    pub(crate) fn push(&self, priority: u8) -> oneshot::Receiver<()> {
+        let (sender, receiver) = oneshot::channel();
+        let mut waiters = self.waiters.lock().unwrap();
+        let seq = waiters.next_seq;
+        waiters.next_seq += 1;
+        waiters.heap.push(Waiter {
+            priority,
+            seq,
+            sender,
+        });
+        let _ = self.len.fetch_add(1, Ordering::SeqCst);
+        receiver
+    }
+
+    /// Hands up to `permits` permits to the waiters with the highest
+    /// priority.
+    ///
+    /// Returns the number of permits which were not handed out.
+    pub(crate) fn release(&self, mut permits: usize) -> usize {
+        if permits == 0 || self.len.load(Ordering::SeqCst) == 0 {
+            return permits;
+        }
+        let mut waiters = self.waiters.lock().unwrap();
+        while permits > 0 {
+            let Some(waiter) = waiters.heap.pop() else {
+                break;
+            };
+            let _ = self.len.fetch_sub(1, Ordering::SeqCst);
+            // Sending fails if the waiter has given up already.
+            if waiter.sender.send(()).is_ok() {
+                permits -= 1;
+            }
+        }
+        permits
+    }
+
+    /// Removes all waiters from the queue causing their receivers to fail.
+    pub(crate) fn close(&self) {
+        let mut waiters = self.waiters.lock().unwrap();
+        waiters.heap.clear();
+        self.len.store(0, Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,198 @@
+//! Exporting the [`Status`] of [`Pool`]s to a [`prometheus`] registry.
+//!
+//! # Example
+//!
+//! ```rust
+//! use deadpool::managed::{self, prometheus::Collector, Metrics, RecycleResult};
+//!
+//! struct Manager {}
+//!
+//! impl managed::Manager for Manager {
+//!     type Type = ();
+//!     type Error = ();
+//!
+//!     async fn create(&self) -> Result<(), ()> {
+//!         Ok(())
+//!     }
+//!     async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<()> {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let pool = managed::Pool::<Manager>::builder(Manager {})
+//!     .name("db")
+//!     .build()
+//!     .unwrap();
+//! let collector = Collector::new();
+//! collector.add(&pool);
+//! let registry = prometheus::Registry::new();
+//! registry.register(Box::new(collector)).unwrap();
+//! ```
+
+use std::{collections::HashMap, fmt, sync::Mutex};
+
+use prometheus::{
+    core::Desc,
+    proto::{self, LabelPair, MetricFamily, MetricType},
+};
+
+use crate::Status;
+
+use super::{Manager, Object, Pool};
+
+type StatusFn = Box<dyn Fn() -> Option<Status> + Send + Sync>;
+
+/// Name, help and value of a gauge.
+type Gauge = (&'static str, &'static str, fn(&Status) -> usize);
+
+const GAUGES: [Gauge; 5] = [
+    ("deadpool_max_size", "Maximum size of the pool", |status| {
+        status.max_size
+    }),
+    ("deadpool_size", "Current size of the pool", |status| {
+        status.size
+    }),
+    (
+        "deadpool_available",
+        "Number of available objects",
+        |status| status.available,
+    ),
+    (
+        "deadpool_waiting",
+        "Number of futures waiting for an object",
+        |status| status.waiting,
+    ),
+    (
+        "deadpool_creating",
+        "Number of objects being created",
+        |status| status.creating,
+    ),
+];
+
+/// [`prometheus::core::Collector`] exporting the [`Status`] of any number
+/// of [`Pool`]s.
+///
+/// The following gauges are exported with the [name] of the pool as `pool`
+/// label along with the [labels] of the pool:
+///
+/// - `deadpool_max_size`: [`Status::max_size`]
+/// - `deadpool_size`: [`Status::size`]
+/// - `deadpool_available`: [`Status::available`]
+/// - `deadpool_waiting`: [`Status::waiting`]
+/// - `deadpool_creating`: [`Status::creating`]
+///
+/// The [`Collector`] only holds weak references to the pools. Pools which
+/// have been dropped are removed automatically.
+///
+/// [labels]: super::PoolBuilder::label
+/// [name]: super::PoolBuilder::name
+pub struct Collector {
+    pools: Mutex<Vec<(Vec<LabelPair>, StatusFn)>>,
+    descs: Vec<Desc>,
+}
+
+impl Collector {
+    /// Creates a new [`Collector`] without any pools.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            pools: Mutex::new(Vec::new()),
+            descs: GAUGES
+                .iter()
+                .map(|(name, help, _)| {
+                    Desc::new(
+                        (*name).to_owned(),
+                        (*help).to_owned(),
+                        vec!["pool".to_owned()],
+                        HashMap::new(),
+                    )
+                    .expect("valid metric definition")
+                })
+                .collect(),
+        }
+    }
+
+    /// Adds the given [`Pool`] to this [`Collector`].
+    ///
+    /// The [`PoolBuilder::name()`] of the pool is used as `pool` label. Pools
+    /// without a name are labelled with an empty string. The keys of the
+    /// [`PoolBuilder::label()`]s must be valid Prometheus label names.
+    ///
+    /// [`PoolBuilder::label()`]: super::PoolBuilder::label
+    /// [`PoolBuilder::name()`]: super::PoolBuilder::name
+    pub fn add<M, W>(&self, pool: &Pool<M, W>)
+    where
+        M: Manager + 'static,
+        W: From<Object<M>>,
+    {
+        let name = ("pool", pool.name().unwrap_or_default());
+        let labels = std::iter::once(name)
+            .chain(
+                pool.labels()
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), value.as_str())),
+            )
+            .map(|(key, value)| {
+                let mut label = LabelPair::default();
+                label.set_name(key.to_owned());
+                label.set_value(value.to_owned());
+                label
+            })
+            .collect();
+        let pool = pool.weak();
+        self.pools
+            .lock()
+            .unwrap()
+            .push((labels, Box::new(move || Some(pool.upgrade()?.status()))));
+    }
+}
+
+impl Default for Collector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Collector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Collector")
+            .field("pools", &self.pools.lock().unwrap().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl prometheus::core::Collector for Collector {
+    fn desc(&self) -> Vec<&Desc> {
+        self.descs.iter().collect()
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        let mut metrics: [Vec<proto::Metric>; 5] = Default::default();
+        self.pools.lock().unwrap().retain(|(labels, status)| {
+            let Some(status) = status() else {
+                return false;
+            };
+            for ((_, _, value), metrics) in GAUGES.iter().zip(&mut metrics) {
+                let mut gauge = proto::Gauge::default();
+                gauge.set_value(value(&status) as f64);
+                let mut metric = proto::Metric::default();
+                metric.set_label(labels.clone());
+                metric.set_gauge(gauge);
+                metrics.push(metric);
+            }
+            true
+        });
+        GAUGES
+            .iter()
+            .zip(metrics)
+            .map(|((name, help, _), metrics)| {
+                let mut family = MetricFamily::default();
+                family.set_name((*name).to_owned());
+                family.set_help((*help).to_owned());
+                family.set_field_type(MetricType::GAUGE);
+                family.set_metric(metrics);
+                family
+            })
+            .collect()
+    }
+}
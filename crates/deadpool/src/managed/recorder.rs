@@ -0,0 +1,63 @@
+//! Reporting to the [`metrics`] crate facade. See the `metrics` feature.
+
+use std::{fmt, time::Duration};
+
+use metrics::{gauge, histogram, Gauge, Histogram, Label};
+
+use crate::Status;
+
+/// Handles of the metrics reported by a pool.
+///
+/// The handles are registered with the global recorder when the pool is
+/// built. All metrics carry the name of the pool as `pool` label along with
+/// the labels of the pool.
+pub(crate) struct Recorder {
+    max_size: Gauge,
+    size: Gauge,
+    available: Gauge,
+    waiting: Gauge,
+    wait_time: Histogram,
+    create_time: Histogram,
+}
+
+impl Recorder {
+    pub(crate) fn new(name: Option<&str>, labels: &[(String, String)]) -> Self {
+        let labels = std::iter::once(Label::new("pool", name.unwrap_or_default().to_owned()))
+            .chain(
+                labels
+                    .iter()
+                    .map(|(key, value)| Label::new(key.clone(), value.clone())),
+            )
+            .collect::<Vec<_>>();
+        Self {
+            max_size: gauge!("deadpool_max_size", labels.clone()),
+            size: gauge!("deadpool_size", labels.clone()),
+            available: gauge!("deadpool_available", labels.clone()),
+            waiting: gauge!("deadpool_waiting", labels.clone()),
+            wait_time: histogram!("deadpool_wait_time_seconds", labels.clone()),
+            create_time: histogram!("deadpool_create_time_seconds", labels),
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn status(&self, status: &Status) {
+        self.max_size.set(status.max_size as f64);
+        self.size.set(status.size as f64);
+        self.available.set(status.available as f64);
+        self.waiting.set(status.waiting as f64);
+    }
+
+    pub(crate) fn wait_time(&self, duration: Duration) {
+        self.wait_time.record(duration);
+    }
+
+    pub(crate) fn create_time(&self, duration: Duration) {
+        self.create_time.record(duration);
+    }
+}
+
+impl fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recorder").finish_non_exhaustive()
+    }
+}
@@ -19,6 +19,10 @@ pub use crate::{
     Runtime,
 };
 
+#[cfg(feature = "env")]
+#[cfg_attr(docsrs, doc(cfg(feature = "env")))]
+pub use crate::managed::FromEnvError;
+
 /// This macro creates all the type aliases usually reexported by
 /// deadpool-* crates. Crates that implement a deadpool manager should
 /// be considered stand alone crates and users of it should not need
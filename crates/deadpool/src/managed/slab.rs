@@ -0,0 +1,177 @@
+//! Arena storing the idle objects of the mutex protected shards.
+
+/// Arena of objects which are linked in the order they were inserted.
+///
+/// Every object is stored in an entry of a single `Vec` and keyed by the
+/// index of that entry. Entries of removed objects are put on a free list
+/// and reused by later inserts, so inserting doesn't allocate as long as the
+/// number of objects doesn't exceed the capacity. Objects can be removed
+/// from both ends and from the middle in constant time without moving any
+/// other object.
+#[derive(Debug)]
+pub(crate) struct Slab<T> {
+    entries: Vec<Entry<T>>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    free: Option<usize>,
+    len: usize,
+}
+
+#[derive(Debug)]
+enum Entry<T> {
+    Occupied {
+        value: T,
+        prev: Option<usize>,
+        next: Option<usize>,
+    },
+    Vacant {
+        next: Option<usize>,
+    },
+}
+
+impl<T> Slab<T> {
+    /// Creates an empty slab which can hold `capacity` objects without
+    /// allocating.
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+            head: None,
+            tail: None,
+            free: None,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of objects.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the object which has been inserted first.
+    pub(crate) fn front(&self) -> Option<&T> {
+        self.head.map(|key| self.get(key))
+    }
+
+    /// Inserts an object after all other objects.
+    pub(crate) fn push_back(&mut self, value: T) {
+        let entry = Entry::Occupied {
+            value,
+            prev: self.tail,
+            next: None,
+        };
+        let key = match self.free {
+            Some(key) => {
+                let Entry::Vacant { next } = self.entries[key] else {
+                    unreachable!("free list contains occupied entry");
+                };
+                self.free = next;
+                self.entries[key] = entry;
+                key
+            }
+            None => {
+                self.entries.push(entry);
+                self.entries.len() - 1
+            }
+        };
+        match self.tail {
+            Some(tail) => self.set_next(tail, Some(key)),
+            None => self.head = Some(key),
+        }
+        self.tail = Some(key);
+        self.len += 1;
+    }
+
+    /// Removes the object which has been inserted first.
+    pub(crate) fn pop_front(&mut self) -> Option<T> {
+        self.head.map(|key| self.remove(key))
+    }
+
+    /// Removes the object which has been inserted last.
+    pub(crate) fn pop_back(&mut self) -> Option<T> {
+        self.tail.map(|key| self.remove(key))
+    }
+
+    /// Removes the object with the given `key`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no object with the given `key`.
+    fn remove(&mut self, key: usize) -> T {
+        let entry = std::mem::replace(&mut self.entries[key], Entry::Vacant { next: self.free });
+        let Entry::Occupied { value, prev, next } = entry else {
+            panic!("no object with key {}", key);
+        };
+        match prev {
+            Some(prev) => self.set_next(prev, next),
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.set_prev(next, prev),
+            None => self.tail = prev,
+        }
+        self.free = Some(key);
+        self.len -= 1;
+        value
+    }
+
+    /// Removes all objects for which the `predicate` returns `false` and
+    /// passes them to `removed` in insertion order.
+    pub(crate) fn retain(
+        &mut self,
+        mut predicate: impl FnMut(&mut T) -> bool,
+        mut removed: impl FnMut(T),
+    ) {
+        let mut cursor = self.head;
+        while let Some(key) = cursor {
+            let Entry::Occupied { value, next, .. } = &mut self.entries[key] else {
+                unreachable!("list contains vacant entry");
+            };
+            cursor = *next;
+            if !predicate(value) {
+                removed(self.remove(key));
+            }
+        }
+    }
+
+    /// Reserves room for `additional` objects on top of the current ones.
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        let vacant = self.entries.len() - self.len;
+        self.entries.reserve(additional.saturating_sub(vacant));
+    }
+
+    /// Shrinks the capacity as much as possible while keeping room for at
+    /// least `capacity` objects.
+    ///
+    /// Occupied entries are moved to the front of the arena so this
+    /// invalidates the keys of all objects.
+    pub(crate) fn shrink_to(&mut self, capacity: usize) {
+        let capacity = capacity.max(self.len);
+        if self.entries.capacity() <= capacity {
+            return;
+        }
+        let mut compacted = Self::with_capacity(capacity);
+        while let Some(value) = self.pop_front() {
+            compacted.push_back(value);
+        }
+        *self = compacted;
+    }
+
+    fn get(&self, key: usize) -> &T {
+        match &self.entries[key] {
+            Entry::Occupied { value, .. } => value,
+            Entry::Vacant { .. } => unreachable!("list contains vacant entry"),
+        }
+    }
+
+    fn set_prev(&mut self, key: usize, key_prev: Option<usize>) {
+        if let Entry::Occupied { prev, .. } = &mut self.entries[key] {
+            *prev = key_prev;
+        }
+    }
+
+    fn set_next(&mut self, key: usize, key_next: Option<usize>) {
+        if let Entry::Occupied { next, .. } = &mut self.entries[key] {
+            *next = key_next;
+        }
+    }
+}
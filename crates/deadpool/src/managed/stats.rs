@@ -0,0 +1,291 @@
+//! Histograms of the durations of pool operations and rates of them.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::{sync::atomic::AtomicU64, time::Instant};
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::clock;
+
+/// Default upper bounds of the buckets of a [`Histogram`]. See
+/// [`PoolBuilder::histogram_buckets()`].
+///
+/// [`PoolBuilder::histogram_buckets()`]: super::PoolBuilder::histogram_buckets
+pub const DEFAULT_HISTOGRAM_BUCKETS: [Duration; 12] = [
+    Duration::from_millis(1),
+    Duration::from_millis(2),
+    Duration::from_millis(5),
+    Duration::from_millis(10),
+    Duration::from_millis(25),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+];
+
+/// Statistics of a [`Pool`] returned by [`Pool::stats()`].
+///
+/// Durations are not recorded on `wasm32` targets.
+///
+/// [`Pool`]: super::Pool
+/// [`Pool::stats()`]: super::Pool::stats
+#[derive(Clone, Debug)]
+pub struct Stats {
+    /// Time it took to retrieve an [`Object`] from the [`Pool`] including
+    /// waiting for a free slot, creating and recycling the [`Object`].
+    /// Failed and cancelled attempts are recorded, too.
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    pub wait_time: Histogram,
+
+    /// Duration of the calls to [`Manager::create()`].
+    ///
+    /// [`Manager::create()`]: super::Manager::create
+    pub create_time: Histogram,
+
+    /// Rates of pool operations over the last
+    /// [`PoolBuilder::stats_window()`].
+    ///
+    /// [`PoolBuilder::stats_window()`]: super::PoolBuilder::stats_window
+    pub rates: Rates,
+}
+
+/// Default duration of the window [`Rates`] are computed over. See
+/// [`PoolBuilder::stats_window()`].
+///
+/// [`PoolBuilder::stats_window()`]: super::PoolBuilder::stats_window
+pub const DEFAULT_STATS_WINDOW: Duration = Duration::from_secs(60);
+
+/// Rates of pool operations per second over a rolling window.
+///
+/// The rates are averaged over the `window` or the lifetime of the
+/// [`Pool`] if it is shorter than that. Rates are not recorded on `wasm32`
+/// targets.
+///
+/// [`Pool`]: super::Pool
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Rates {
+    /// Duration of the window the rates are computed over.
+    pub window: Duration,
+
+    /// Calls to [`Pool::get()`] per second including failed and cancelled
+    /// ones.
+    ///
+    /// [`Pool::get()`]: super::Pool::get
+    pub gets: f64,
+
+    /// Timeouts of any [`TimeoutType`] per second.
+    ///
+    /// [`TimeoutType`]: super::TimeoutType
+    pub timeouts: f64,
+
+    /// Successful calls to [`Manager::create()`] per second.
+    ///
+    /// [`Manager::create()`]: super::Manager::create
+    pub creates: f64,
+}
+
+/// Histogram of durations with fixed buckets.
+#[derive(Clone, Debug)]
+pub struct Histogram {
+    bounds: Vec<Duration>,
+    counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Returns the buckets of this [`Histogram`] as pairs of the inclusive
+    /// upper bound and the number of recorded durations which fell into
+    /// it. The upper bound of the last bucket is `None` and means infinity.
+    pub fn buckets(&self) -> impl Iterator<Item = (Option<Duration>, usize)> + '_ {
+        self.bounds
+            .iter()
+            .copied()
+            .map(Some)
+            .chain([None])
+            .zip(self.counts.iter().copied())
+    }
+
+    /// Returns the total number of recorded durations.
+    pub fn count(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Estimates the given `quantile` (e.g. `0.99`) by returning the upper
+    /// bound of the bucket it falls into.
+    ///
+    /// Returns `None` if no durations have been recorded or the quantile
+    /// falls into the last bucket which has no upper bound.
+    pub fn quantile(&self, quantile: f64) -> Option<Duration> {
+        let count = self.count();
+        if count == 0 {
+            return None;
+        }
+        let rank = ((quantile.clamp(0.0, 1.0) * count as f64).ceil() as usize).max(1);
+        let mut seen = 0;
+        for (bound, n) in self.buckets() {
+            seen += n;
+            if seen >= rank {
+                return bound;
+            }
+        }
+        None
+    }
+
+    /// Returns the durations which have been recorded since the `earlier`
+    /// snapshot of the same histogram was taken.
+    pub(crate) fn since(&self, earlier: &Histogram) -> Histogram {
+        Histogram {
+            bounds: self.bounds.clone(),
+            counts: self
+                .counts
+                .iter()
+                .zip(&earlier.counts)
+                .map(|(now, then)| now.saturating_sub(*then))
+                .collect(),
+        }
+    }
+}
+
+/// Atomic version of [`Histogram`] which is updated by the pool.
+#[derive(Debug)]
+pub(crate) struct AtomicHistogram {
+    bounds: Box<[Duration]>,
+    counts: Box<[AtomicUsize]>,
+}
+
+impl AtomicHistogram {
+    pub(crate) fn new(bounds: &[Duration]) -> Self {
+        let mut bounds = bounds.to_vec();
+        bounds.sort_unstable();
+        bounds.dedup();
+        Self {
+            counts: (0..=bounds.len()).map(|_| AtomicUsize::new(0)).collect(),
+            bounds: bounds.into(),
+        }
+    }
+
+    pub(crate) fn record(&self, duration: Duration) {
+        let index = self.bounds.partition_point(|bound| *bound < duration);
+        let _ = self.counts[index].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> Histogram {
+        Histogram {
+            bounds: self.bounds.to_vec(),
+            counts: self
+                .counts
+                .iter()
+                .map(|n| n.load(Ordering::Relaxed))
+                .collect(),
+        }
+    }
+}
+
+/// Events counted by [`AtomicRates`].
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum RateEvent {
+    Get,
+    Timeout,
+    Create,
+}
+
+/// Counts of a single second of the window of [`AtomicRates`].
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Default)]
+struct RateSlot {
+    /// Second since the creation of the [`AtomicRates`] the counts
+    /// belong to.
+    second: AtomicU64,
+    counts: [AtomicUsize; 3],
+}
+
+/// Ring buffer of per second counts which is updated by the pool and
+/// turned into [`Rates`].
+///
+/// The counts of a slot are reset when it is reused for a later second.
+/// Just like the other statistics they are not guaranteed to be
+/// consistent under heavy load.
+#[derive(Debug)]
+pub(crate) struct AtomicRates {
+    window: Duration,
+    #[cfg(not(target_arch = "wasm32"))]
+    start: Instant,
+    #[cfg(not(target_arch = "wasm32"))]
+    slots: Box<[RateSlot]>,
+}
+
+impl AtomicRates {
+    pub(crate) fn new(window: Duration) -> Self {
+        let seconds = window.as_secs() + u64::from(window.subsec_nanos() > 0);
+        let seconds = seconds.max(1);
+        Self {
+            window: Duration::from_secs(seconds),
+            #[cfg(not(target_arch = "wasm32"))]
+            start: clock::now(),
+            #[cfg(not(target_arch = "wasm32"))]
+            slots: (0..seconds).map(|_| RateSlot::default()).collect(),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn record(&self, event: RateEvent) {
+        let second = clock::elapsed(self.start).as_secs();
+        let slot = &self.slots[(second % self.slots.len() as u64) as usize];
+        let current = slot.second.load(Ordering::Relaxed);
+        if current != second
+            && slot
+                .second
+                .compare_exchange(current, second, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        {
+            for count in &slot.counts {
+                count.store(0, Ordering::Relaxed);
+            }
+        }
+        let _ = slot.counts[event as usize].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> Rates {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let elapsed = clock::elapsed(self.start);
+            let second = elapsed.as_secs();
+            let len = self.slots.len() as u64;
+            let mut counts = [0; 3];
+            for slot in self.slots.iter() {
+                let age = second.wrapping_sub(slot.second.load(Ordering::Relaxed));
+                if age < len {
+                    for (sum, count) in counts.iter_mut().zip(&slot.counts) {
+                        *sum += count.load(Ordering::Relaxed);
+                    }
+                }
+            }
+            // The slots cover the current second only partially.
+            let seconds =
+                (elapsed - Duration::from_secs((second + 1).saturating_sub(len))).as_secs_f64();
+            let rate = |count: usize| match seconds {
+                s if s > 0.0 => count as f64 / s,
+                _ => 0.0,
+            };
+            Rates {
+                window: self.window,
+                gets: rate(counts[RateEvent::Get as usize]),
+                timeouts: rate(counts[RateEvent::Timeout as usize]),
+                creates: rate(counts[RateEvent::Create as usize]),
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        Rates {
+            window: self.window,
+            ..Rates::default()
+        }
+    }
+}
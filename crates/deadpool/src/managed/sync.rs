@@ -0,0 +1,91 @@
+//! Synchronization primitives used by the idle queue.
+//!
+//! When compiled with `RUSTFLAGS="--cfg deadpool_loom"` the primitives of
+//! [`loom`](https://crates.io/crates/loom) are used instead so the
+//! concurrent checkout and return of objects can be verified by exploring
+//! all possible interleavings. The lock-free `ArrayQueue` and `SegQueue` of
+//! `crossbeam` aren't instrumented by `loom` and are replaced by
+//! mutex-protected queues in that case.
+//!
+//! Permits are managed by the semaphore of Tokio which can't be
+//! instrumented from the outside and is therefore not covered by the
+//! `loom` models.
+
+#[cfg(not(deadpool_loom))]
+pub(crate) use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex,
+};
+
+#[cfg(not(deadpool_loom))]
+pub(crate) use crossbeam_queue::{ArrayQueue, SegQueue};
+#[cfg(deadpool_loom)]
+pub(crate) use loom::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex,
+};
+
+/// Stand-in for the `SegQueue` of `crossbeam` which can be modelled by
+/// `loom`.
+#[cfg(deadpool_loom)]
+#[derive(Debug)]
+pub(crate) struct SegQueue<T>(Mutex<std::collections::VecDeque<T>>);
+
+#[cfg(deadpool_loom)]
+impl<T> SegQueue<T> {
+    pub(crate) fn new() -> Self {
+        Self(Mutex::new(std::collections::VecDeque::new()))
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.lock().unwrap().is_empty()
+    }
+
+    pub(crate) fn push(&self, value: T) {
+        self.0.lock().unwrap().push_back(value);
+    }
+
+    pub(crate) fn pop(&self) -> Option<T> {
+        self.0.lock().unwrap().pop_front()
+    }
+}
+
+/// Stand-in for the `ArrayQueue` of `crossbeam` which can be modelled by
+/// `loom`.
+#[cfg(deadpool_loom)]
+#[derive(Debug)]
+pub(crate) struct ArrayQueue<T> {
+    queue: Mutex<std::collections::VecDeque<T>>,
+    capacity: usize,
+}
+
+#[cfg(deadpool_loom)]
+impl<T> ArrayQueue<T> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            queue: Mutex::new(std::collections::VecDeque::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.queue.lock().unwrap().len()
+    }
+
+    pub(crate) fn push(&self, value: T) -> Result<(), T> {
+        let mut queue = self.queue.lock().unwrap();
+        if queue.len() == self.capacity {
+            return Err(value);
+        }
+        queue.push_back(value);
+        Ok(())
+    }
+
+    pub(crate) fn pop(&self) -> Option<T> {
+        self.queue.lock().unwrap().pop_front()
+    }
+}
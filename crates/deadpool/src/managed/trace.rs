@@ -0,0 +1,16 @@
+//! Helpers for the optional `tracing` instrumentation.
+
+/// Instruments the given future with a `tracing` span at debug level if
+/// the `tracing` feature is enabled. Otherwise the future is returned as
+/// is and the span arguments are not evaluated at all.
+macro_rules! instrument {
+    ($future:expr, $($span:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        let future = tracing::Instrument::instrument($future, tracing::debug_span!($($span)+));
+        #[cfg(not(feature = "tracing"))]
+        let future = $future;
+        future
+    }};
+}
+
+pub(crate) use instrument;
@@ -0,0 +1,89 @@
+//! Process-wide registry of named [`Pool`]s.
+//!
+//! Pools are registered either explicitly via [`register()`] or at build
+//! time via [`PoolBuilder::register()`]. The registry only keeps weak
+//! references, so registering a pool doesn't keep it alive and dropped pools
+//! disappear from the registry automatically.
+//!
+//! Registered pools are returned as [`DynPool`]s which makes it possible to
+//! enumerate pools of different [`Manager`]s, e.g. for exporting metrics,
+//! running health checks or resizing and closing pools from an admin
+//! endpoint:
+//!
+//! ```rust,ignore
+//! for (name, pool) in deadpool::registry::pools() {
+//!     println!("{}: {:?}", name, pool.status());
+//! }
+//! ```
+//!
+//! [`PoolBuilder::register()`]: crate::managed::PoolBuilder::register
+
+use std::{collections::BTreeMap, sync::Mutex};
+
+use crate::managed::{DynPool, Manager, Object, Pool, WeakPool};
+
+static REGISTRY: Mutex<BTreeMap<String, Box<dyn Entry>>> = Mutex::new(BTreeMap::new());
+
+/// Type-erased [`WeakPool`] stored in the registry.
+trait Entry: Send {
+    fn upgrade(&self) -> Option<DynPool>;
+}
+
+impl<M: Manager + 'static> Entry for WeakPool<M> {
+    fn upgrade(&self) -> Option<DynPool> {
+        Some(Box::new(WeakPool::upgrade(self)?))
+    }
+}
+
+/// Registers the given [`Pool`] under the given `name`.
+///
+/// A pool which was previously registered under the same `name` is
+/// replaced.
+pub fn register<M, W>(name: impl Into<String>, pool: &Pool<M, W>)
+where
+    M: Manager + 'static,
+    W: From<Object<M>>,
+{
+    let _ = REGISTRY
+        .lock()
+        .unwrap()
+        .insert(name.into(), Box::new(pool.weak()));
+}
+
+/// Removes the pool registered under the given `name` from the registry.
+///
+/// Returns `true` if a pool was registered under that `name` and is still
+/// alive.
+pub fn unregister(name: &str) -> bool {
+    REGISTRY
+        .lock()
+        .unwrap()
+        .remove(name)
+        .is_some_and(|entry| entry.upgrade().is_some())
+}
+
+/// Returns the pool registered under the given `name`.
+///
+/// Returns `None` if no pool is registered under that `name` or if the pool
+/// has been dropped already.
+#[must_use]
+pub fn get(name: &str) -> Option<DynPool> {
+    REGISTRY.lock().unwrap().get(name)?.upgrade()
+}
+
+/// Returns all registered pools which are still alive, ordered by name.
+///
+/// Entries of pools which have been dropped are removed from the registry.
+#[must_use]
+pub fn pools() -> Vec<(String, DynPool)> {
+    let mut registry = REGISTRY.lock().unwrap();
+    let mut pools = Vec::with_capacity(registry.len());
+    registry.retain(|name, entry| match entry.upgrade() {
+        Some(pool) => {
+            pools.push((name.clone(), pool));
+            true
+        }
+        None => false,
+    });
+    pools
+}
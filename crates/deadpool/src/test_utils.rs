@@ -0,0 +1,260 @@
+//! Utilities for testing code which uses a [`Pool`].
+//!
+//! The [`MockManager`] creates plain `usize` objects and lets tests script
+//! the outcome and latency of every call to [`Manager::create()`] and
+//! [`Manager::recycle()`]. This makes it possible to test timeouts,
+//! retries and failure handling of a [`Pool`] deterministically:
+//!
+//! ```rust
+//! use deadpool::{
+//!     managed::Pool,
+//!     test_utils::{MockManager, Step},
+//! };
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! let manager = MockManager::new();
+//! manager.push_create(Step::err());
+//! let pool: Pool<MockManager> = Pool::builder(manager).build().unwrap();
+//! assert!(pool.get().await.is_err());
+//! assert!(pool.get().await.is_ok());
+//! assert_eq!(pool.manager().create_calls(), 2);
+//! # }
+//! ```
+//!
+//! [`Manager::create()`]: crate::managed::Manager::create
+//! [`Manager::recycle()`]: crate::managed::Manager::recycle
+//! [`Pool`]: crate::managed::Pool
+
+use std::{
+    collections::VecDeque,
+    fmt,
+    future::pending,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use crate::{
+    managed::{Manager, Metrics, RecycleResult},
+    Runtime,
+};
+
+/// Error returned by the [`MockManager`] for [`Step::err()`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MockError;
+
+impl fmt::Display for MockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Mock error")
+    }
+}
+
+impl std::error::Error for MockError {}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Outcome {
+    Ok,
+    Err,
+    Pending,
+}
+
+/// Scripted outcome of a single call to [`Manager::create()`] or
+/// [`Manager::recycle()`] of a [`MockManager`].
+///
+/// [`Manager::create()`]: crate::managed::Manager::create
+/// [`Manager::recycle()`]: crate::managed::Manager::recycle
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Step {
+    outcome: Outcome,
+    delay: Option<Duration>,
+}
+
+impl Step {
+    /// The call succeeds.
+    #[must_use]
+    pub fn ok() -> Self {
+        Self {
+            outcome: Outcome::Ok,
+            delay: None,
+        }
+    }
+
+    /// The call fails with a [`MockError`].
+    #[must_use]
+    pub fn err() -> Self {
+        Self {
+            outcome: Outcome::Err,
+            delay: None,
+        }
+    }
+
+    /// The call never completes. This is useful for testing timeouts.
+    #[must_use]
+    pub fn pending() -> Self {
+        Self {
+            outcome: Outcome::Pending,
+            delay: None,
+        }
+    }
+
+    /// Delays the outcome of the call by the given `duration`.
+    ///
+    /// Delays are awaited using the [`Runtime`] of the [`MockManager`].
+    #[must_use]
+    pub fn delay(mut self, duration: Duration) -> Self {
+        self.delay = Some(duration);
+        self
+    }
+
+    async fn run(self, runtime: Option<Runtime>) -> Result<(), MockError> {
+        if let Some(delay) = self.delay {
+            runtime
+                .or_else(Runtime::detect)
+                .expect("Delaying a `Step` requires a `Runtime`")
+                .sleep(delay)
+                .await;
+        }
+        match self.outcome {
+            Outcome::Ok => Ok(()),
+            Outcome::Err => Err(MockError),
+            Outcome::Pending => pending().await,
+        }
+    }
+}
+
+impl Default for Step {
+    fn default() -> Self {
+        Self::ok()
+    }
+}
+
+#[derive(Debug, Default)]
+struct Script {
+    steps: Mutex<VecDeque<Step>>,
+    default: Mutex<Step>,
+}
+
+impl Script {
+    fn next(&self) -> Step {
+        let step = self.steps.lock().unwrap().pop_front();
+        step.unwrap_or_else(|| *self.default.lock().unwrap())
+    }
+}
+
+/// [`Manager`] with scriptable outcomes for testing.
+///
+/// Every call to [`Manager::create()`] and [`Manager::recycle()`] takes the
+/// next [`Step`] of the respective script. Once a script is exhausted its
+/// default [`Step`] is used which is [`Step::ok()`] unless changed.
+///
+/// Created objects are numbered starting from `0`.
+#[derive(Debug, Default)]
+pub struct MockManager {
+    runtime: Option<Runtime>,
+    create: Script,
+    recycle: Script,
+    create_calls: AtomicUsize,
+    created: AtomicUsize,
+    recycle_calls: AtomicUsize,
+    recycled: AtomicUsize,
+    destroyed: AtomicUsize,
+    detached: AtomicUsize,
+}
+
+impl MockManager {
+    /// Creates a new [`MockManager`] whose calls all succeed.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the [`Runtime`] used for awaiting the [`Step::delay()`].
+    ///
+    /// If not set, the runtime is detected via [`Runtime::detect()`].
+    #[must_use]
+    pub fn runtime(mut self, runtime: Runtime) -> Self {
+        self.runtime = Some(runtime);
+        self
+    }
+
+    /// Appends a [`Step`] to the script of [`Manager::create()`].
+    pub fn push_create(&self, step: Step) {
+        self.create.steps.lock().unwrap().push_back(step);
+    }
+
+    /// Appends a [`Step`] to the script of [`Manager::recycle()`].
+    pub fn push_recycle(&self, step: Step) {
+        self.recycle.steps.lock().unwrap().push_back(step);
+    }
+
+    /// Sets the [`Step`] used by [`Manager::create()`] once its script is
+    /// exhausted.
+    pub fn set_create_default(&self, step: Step) {
+        *self.create.default.lock().unwrap() = step;
+    }
+
+    /// Sets the [`Step`] used by [`Manager::recycle()`] once its script is
+    /// exhausted.
+    pub fn set_recycle_default(&self, step: Step) {
+        *self.recycle.default.lock().unwrap() = step;
+    }
+
+    /// Returns the number of calls to [`Manager::create()`].
+    pub fn create_calls(&self) -> usize {
+        self.create_calls.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of objects created successfully.
+    pub fn created(&self) -> usize {
+        self.created.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of calls to [`Manager::recycle()`].
+    pub fn recycle_calls(&self) -> usize {
+        self.recycle_calls.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of objects recycled successfully.
+    pub fn recycled(&self) -> usize {
+        self.recycled.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of calls to [`Manager::destroy()`].
+    pub fn destroyed(&self) -> usize {
+        self.destroyed.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of calls to [`Manager::detach()`].
+    pub fn detached(&self) -> usize {
+        self.detached.load(Ordering::Relaxed)
+    }
+}
+
+impl Manager for MockManager {
+    type Type = usize;
+    type Error = MockError;
+
+    async fn create(&self) -> Result<usize, MockError> {
+        let _ = self.create_calls.fetch_add(1, Ordering::Relaxed);
+        self.create.next().run(self.runtime).await?;
+        Ok(self.created.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<MockError> {
+        let _ = self.recycle_calls.fetch_add(1, Ordering::Relaxed);
+        self.recycle.next().run(self.runtime).await?;
+        let _ = self.recycled.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn destroy(&self, _: usize) {
+        let _ = self.destroyed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn detach(&self, _: &mut usize) {
+        let _ = self.detached.fetch_add(1, Ordering::Relaxed);
+    }
+}
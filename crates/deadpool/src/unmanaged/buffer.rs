@@ -0,0 +1,319 @@
+//! Pool of byte buffers.
+//!
+//! A [`BufferPool`] hands out empty buffers with at least the requested
+//! capacity and takes them back when they are dropped. Buffers are grouped
+//! into capacity classes which are powers of two between the
+//! [`BufferPoolConfig::min_capacity`] and the
+//! [`BufferPoolConfig::max_capacity`]. Every class is kept in its own
+//! unmanaged [`Pool`].
+//!
+//! Returned buffers are cleared before being reused. Buffers which grew
+//! beyond the [`BufferPoolConfig::max_capacity`] are dropped as are buffers
+//! which would make the pool retain more than the
+//! [`BufferPoolConfig::max_retained`] bytes.
+//!
+//! ```rust
+//! use deadpool::unmanaged::buffer::BufferPool;
+//!
+//! let pool: BufferPool = BufferPool::default();
+//! let mut buf = pool.get(1000);
+//! buf.extend_from_slice(b"Hello world!");
+//! drop(buf);
+//! assert_eq!(pool.retained(), 1024);
+//! assert!(pool.get(1000).is_empty());
+//! ```
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Weak,
+    },
+};
+
+use super::Pool;
+
+/// Buffer which can be pooled by a [`BufferPool`].
+pub trait Buffer: Send + 'static {
+    /// Creates a new empty buffer with at least the given `capacity`.
+    fn with_capacity(capacity: usize) -> Self;
+
+    /// Returns the number of bytes the buffer can hold without allocating.
+    fn capacity(&self) -> usize;
+
+    /// Clears the buffer keeping its capacity.
+    fn clear(&mut self);
+}
+
+impl Buffer for Vec<u8> {
+    fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity(capacity)
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    fn clear(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl Buffer for bytes::BytesMut {
+    fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity(capacity)
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    fn clear(&mut self) {
+        self.clear();
+    }
+}
+
+/// [`BufferPool`] configuration.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct BufferPoolConfig {
+    /// Capacity of the smallest buffers handed out.
+    ///
+    /// Default: `256`
+    pub min_capacity: usize,
+
+    /// Capacity of the largest buffers which are pooled. Larger buffers are
+    /// allocated on demand and dropped when they are returned.
+    ///
+    /// Default: `1 MiB`
+    pub max_capacity: usize,
+
+    /// Maximum number of bytes retained by the idle buffers of the pool.
+    ///
+    /// Default: `16 MiB`
+    pub max_retained: usize,
+}
+
+impl Default for BufferPoolConfig {
+    fn default() -> Self {
+        Self {
+            min_capacity: 256,
+            max_capacity: 1024 * 1024,
+            max_retained: 16 * 1024 * 1024,
+        }
+    }
+}
+
+/// Pool of byte buffers grouped by capacity. See the [module
+/// documentation](self) for details.
+///
+/// This struct can be cloned and transferred across thread boundaries and uses
+/// reference counting for its internal state.
+pub struct BufferPool<B: Buffer = Vec<u8>> {
+    inner: Arc<BufferPoolInner<B>>,
+}
+
+struct BufferPoolInner<B> {
+    config: BufferPoolConfig,
+    /// Capacity of the smallest buffer of every class.
+    capacities: Vec<usize>,
+    classes: Vec<Pool<B>>,
+    retained: AtomicUsize,
+}
+
+impl<B: Buffer> Clone for BufferPool<B> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<B: Buffer> fmt::Debug for BufferPool<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferPool")
+            .field("config", &self.inner.config)
+            .field("retained", &self.retained())
+            .finish()
+    }
+}
+
+impl<B: Buffer> Default for BufferPool<B> {
+    fn default() -> Self {
+        Self::new(BufferPoolConfig::default())
+    }
+}
+
+impl<B: Buffer> BufferPool<B> {
+    /// Creates a new empty [`BufferPool`] using the given
+    /// [`BufferPoolConfig`].
+    #[must_use]
+    pub fn new(config: BufferPoolConfig) -> Self {
+        let mut capacities = Vec::new();
+        let mut capacity = config.min_capacity.max(1);
+        while capacity <= config.max_capacity {
+            capacities.push(capacity);
+            capacity = match capacity.checked_mul(2) {
+                Some(capacity) => capacity,
+                None => break,
+            };
+        }
+        let classes = capacities
+            .iter()
+            .map(|capacity| Pool::new(config.max_retained / capacity))
+            .collect();
+        Self {
+            inner: Arc::new(BufferPoolInner {
+                config,
+                capacities,
+                classes,
+                retained: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Retrieves an empty buffer with at least the given `capacity`.
+    ///
+    /// This never waits. If no idle buffer of the matching capacity class is
+    /// available a new one is allocated.
+    pub fn get(&self, capacity: usize) -> PooledBuffer<B> {
+        let inner = self.inner.as_ref();
+        let class = inner.capacities.iter().position(|&c| c >= capacity);
+        let buf = match class {
+            Some(class) => match inner.classes[class].try_remove() {
+                Ok(buf) => {
+                    let _ = inner.retained.fetch_sub(buf.capacity(), Ordering::Relaxed);
+                    buf
+                }
+                Err(_) => B::with_capacity(inner.capacities[class]),
+            },
+            None => B::with_capacity(capacity),
+        };
+        PooledBuffer {
+            buf: Some(buf),
+            pool: Arc::downgrade(&self.inner),
+        }
+    }
+
+    /// Returns the number of bytes retained by the idle buffers of this
+    /// [`BufferPool`].
+    #[must_use]
+    pub fn retained(&self) -> usize {
+        self.inner.retained.load(Ordering::Relaxed)
+    }
+
+    /// Drops all idle buffers of this [`BufferPool`].
+    pub fn clear(&self) {
+        for pool in &self.inner.classes {
+            for buf in pool.remove_where(|_| true) {
+                let _ = self
+                    .inner
+                    .retained
+                    .fetch_sub(buf.capacity(), Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+impl<B: Buffer> BufferPoolInner<B> {
+    /// Puts a returned buffer into its capacity class unless it is too large
+    /// or the pool already retains too much memory.
+    fn put(&self, mut buf: B) {
+        let capacity = buf.capacity();
+        let Some(class) = self.capacities.iter().rposition(|&c| c <= capacity) else {
+            return;
+        };
+        if capacity > self.config.max_capacity || !self.reserve(capacity) {
+            return;
+        }
+        buf.clear();
+        if self.classes[class].try_add(buf).is_err() {
+            let _ = self.retained.fetch_sub(capacity, Ordering::Relaxed);
+        }
+    }
+
+    /// Adds `capacity` to the retained bytes unless that would exceed the
+    /// [`BufferPoolConfig::max_retained`].
+    fn reserve(&self, capacity: usize) -> bool {
+        let mut retained = self.retained.load(Ordering::Relaxed);
+        loop {
+            let Some(new) = retained
+                .checked_add(capacity)
+                .filter(|&new| new <= self.config.max_retained)
+            else {
+                return false;
+            };
+            match self.retained.compare_exchange_weak(
+                retained,
+                new,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return true,
+                Err(actual) => retained = actual,
+            }
+        }
+    }
+}
+
+/// Buffer retrieved from a [`BufferPool`] which implements [`Deref`] and
+/// [`DerefMut`].
+///
+/// The buffer is returned to the [`BufferPool`] when this is dropped.
+#[must_use]
+pub struct PooledBuffer<B: Buffer> {
+    buf: Option<B>,
+    pool: Weak<BufferPoolInner<B>>,
+}
+
+impl<B: Buffer> PooledBuffer<B> {
+    /// Takes the buffer out of the [`BufferPool`] permanently.
+    #[must_use]
+    pub fn take(mut this: Self) -> B {
+        this.buf.take().unwrap()
+    }
+}
+
+impl<B: Buffer> Drop for PooledBuffer<B> {
+    fn drop(&mut self) {
+        if let Some(buf) = self.buf.take() {
+            if let Some(pool) = self.pool.upgrade() {
+                pool.put(buf);
+            }
+        }
+    }
+}
+
+impl<B: Buffer + fmt::Debug> fmt::Debug for PooledBuffer<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PooledBuffer").field(&self.buf).finish()
+    }
+}
+
+impl<B: Buffer> Deref for PooledBuffer<B> {
+    type Target = B;
+    fn deref(&self) -> &B {
+        self.buf.as_ref().unwrap()
+    }
+}
+
+impl<B: Buffer> DerefMut for PooledBuffer<B> {
+    fn deref_mut(&mut self) -> &mut B {
+        self.buf.as_mut().unwrap()
+    }
+}
+
+impl<B: Buffer> AsRef<B> for PooledBuffer<B> {
+    fn as_ref(&self) -> &B {
+        self
+    }
+}
+
+impl<B: Buffer> AsMut<B> for PooledBuffer<B> {
+    fn as_mut(&mut self) -> &mut B {
+        self
+    }
+}
@@ -1,19 +1,41 @@
 use std::time::Duration;
 
+#[cfg(feature = "serde")]
+use crate::config::deserialize_duration;
 use crate::Runtime;
 
 /// Pool configuration.
+///
+/// With the `serde` feature enabled this can be deserialized e.g. from a
+/// configuration file. With the `env` feature enabled it can be read from
+/// environment variables via `PoolConfig::from_env()`.
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct PoolConfig {
     /// Maximum size of the pool.
+    ///
+    /// Default: `cpu_count * 4`
     pub max_size: usize,
 
     /// Timeout for [`Pool::get()`] operation.
     ///
+    /// Default: No timeout
+    ///
     /// [`Pool::get()`]: super::Pool::get
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "deserialize_duration")
+    )]
     pub timeout: Option<Duration>,
 
+    /// Queue mode of the pool.
+    ///
+    /// Determines the order of objects being queued and dequeued.
+    ///
+    /// Default: `Lifo`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub queue_mode: QueueMode,
+
     /// [`Runtime`] to be used.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub runtime: Option<Runtime>,
@@ -26,6 +48,7 @@ impl PoolConfig {
         Self {
             max_size,
             timeout: None,
+            queue_mode: QueueMode::default(),
             runtime: None,
         }
     }
@@ -38,3 +61,48 @@ impl Default for PoolConfig {
         Self::new(num_cpus::get_physical() * 4)
     }
 }
+
+/// Mode for dequeuing [`Object`]s from a [`Pool`].
+///
+/// Unlike the managed pool the unmanaged one defaults to
+/// [`QueueMode::Lifo`].
+///
+/// [`Object`]: super::Object
+/// [`Pool`]: super::Pool
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum QueueMode {
+    /// Dequeue the object that was least recently added (first in first out).
+    ///
+    /// This spreads the use evenly across all objects.
+    Fifo,
+    /// Dequeue the object that was most recently added (last in first out).
+    ///
+    /// This keeps reusing a small set of recently used objects which are
+    /// more likely to still be in the CPU cache.
+    #[default]
+    Lifo,
+}
+
+/// Policy for adding an object to a [`Pool`] which is already full.
+///
+/// See [`Pool::try_add_with()`] and [`Pool::add_with()`].
+///
+/// [`Pool`]: super::Pool
+/// [`Pool::try_add_with()`]: super::Pool::try_add_with
+/// [`Pool::add_with()`]: super::Pool::add_with
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum GrowthPolicy {
+    /// Don't add the object. This is what [`Pool::try_add()`] does.
+    ///
+    /// [`Pool::try_add()`]: super::Pool::try_add
+    #[default]
+    Reject,
+    /// Increase the maximum size of the pool by one to make room for the
+    /// object.
+    Grow,
+    /// Remove the oldest idle object from the pool and add the new object in
+    /// its place. The pool is left at its current size.
+    ReplaceOldest,
+}
@@ -0,0 +1,56 @@
+//! Reading the unmanaged [`PoolConfig`] from environment variables.
+
+use std::str::FromStr;
+
+use super::{PoolConfig, QueueMode};
+use crate::env::{EnvVars, FromEnvError};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "env")))]
+impl PoolConfig {
+    /// Reads a [`PoolConfig`] from environment variables starting with the
+    /// given `prefix`.
+    ///
+    /// Every field is read from the variable named like the field in upper
+    /// case. The timeout can be given in a human readable form like `500ms`
+    /// or `30s`. Fields whose variables aren't set keep their default value.
+    ///
+    /// ```env
+    /// POOL__MAX_SIZE=16
+    /// POOL__TIMEOUT=5s
+    /// POOL__QUEUE_MODE=fifo
+    /// ```
+    /// ```rust
+    /// # use deadpool::unmanaged::PoolConfig;
+    /// let config = PoolConfig::from_env("POOL").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`FromEnvError`] for details.
+    pub fn from_env(prefix: &str) -> Result<Self, FromEnvError> {
+        let env = EnvVars::new(prefix);
+        let mut cfg = Self::default();
+        if let Some(max_size) = env.parse("MAX_SIZE")? {
+            cfg.max_size = max_size;
+        }
+        cfg.timeout = env.duration("TIMEOUT")?;
+        if let Some(queue_mode) = env.parse("QUEUE_MODE")? {
+            cfg.queue_mode = queue_mode;
+        }
+        Ok(cfg)
+    }
+}
+
+impl FromStr for QueueMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("fifo") {
+            Ok(Self::Fifo)
+        } else if s.eq_ignore_ascii_case("lifo") {
+            Ok(Self::Lifo)
+        } else {
+            Err("expected `fifo` or `lifo`".to_owned())
+        }
+    }
+}
@@ -0,0 +1,68 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::clock;
+
+/// Statistics regarding an object returned by the pool
+///
+/// This mirrors the `Metrics` of the managed pool. As unmanaged pools
+/// don't create or recycle objects the age of an object is measured from
+/// the moment it was added to the pool.
+///
+/// With the `serde` feature enabled this implements `Serialize`. As
+/// [`Instant`]s can't be serialized the [`Metrics::age()`] and the
+/// [`Metrics::last_used()`] duration are serialized instead of the
+/// instants.
+#[derive(Clone, Copy, Debug)]
+#[must_use]
+pub struct Metrics {
+    #[cfg(not(target_arch = "wasm32"))]
+    /// The instant when this object was added to the pool
+    pub added: Instant,
+    #[cfg(not(target_arch = "wasm32"))]
+    /// The instant when this object was last returned to the pool
+    pub returned: Option<Instant>,
+    /// The number of times the object was handed out by the pool.
+    pub use_count: usize,
+}
+
+impl Metrics {
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Access the age of this object
+    pub fn age(&self) -> Duration {
+        clock::elapsed(self.added)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Get the time elapsed when this object was last used
+    pub fn last_used(&self) -> Duration {
+        clock::elapsed(self.returned.unwrap_or(self.added))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Metrics {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("Metrics", 3)?;
+        #[cfg(not(target_arch = "wasm32"))]
+        s.serialize_field("age", &self.age())?;
+        #[cfg(not(target_arch = "wasm32"))]
+        s.serialize_field("last_used", &self.last_used())?;
+        s.serialize_field("use_count", &self.use_count)?;
+        s.end()
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            added: clock::now(),
+            #[cfg(not(target_arch = "wasm32"))]
+            returned: None,
+            use_count: 0,
+        }
+    }
+}
@@ -2,7 +2,9 @@
 //!
 //! "Unmanaged" means that no manager is used to create and recycle objects.
 //! Objects either need to be created upfront or by adding them using the
-//! [`Pool::add()`] or [`Pool::try_add()`] methods.
+//! [`Pool::add()`] or [`Pool::try_add()`] methods. Alternatively a [`Pool`]
+//! can be given a factory which creates objects on demand, see
+//! [`Pool::with_factory()`].
 //!
 //! # Example
 //!
@@ -28,11 +30,18 @@
 //! }
 //! ```
 
+pub mod buffer;
 mod config;
+#[cfg(feature = "env")]
+mod env;
 mod errors;
+mod metrics;
 
 use std::{
+    collections::VecDeque,
     convert::TryInto,
+    fmt,
+    future::Future,
     ops::{Deref, DerefMut},
     sync::{
         atomic::{AtomicIsize, AtomicUsize, Ordering},
@@ -43,9 +52,20 @@ use std::{
 
 use tokio::sync::{Semaphore, TryAcquireError};
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::clock;
+use crate::BoxFuture;
 pub use crate::Status;
 
-pub use self::{config::PoolConfig, errors::PoolError};
+#[cfg(feature = "env")]
+#[cfg_attr(docsrs, doc(cfg(feature = "env")))]
+pub use crate::env::{EnvVars, FromEnvError};
+
+pub use self::{
+    config::{GrowthPolicy, PoolConfig, QueueMode},
+    errors::PoolError,
+    metrics::Metrics,
+};
 
 /// Wrapper around the actual pooled object which implements [`Deref`],
 /// [`DerefMut`] and [`Drop`] traits.
@@ -58,6 +78,9 @@ pub struct Object<T> {
     /// Actual pooled object.
     obj: Option<T>,
 
+    /// Statistics of the pooled object.
+    metrics: Metrics,
+
     /// Pool to return the pooled object to.
     pool: Weak<PoolInner<T>>,
 }
@@ -70,19 +93,32 @@ impl<T> Object<T> {
     pub fn take(mut this: Self) -> T {
         if let Some(pool) = this.pool.upgrade() {
             let _ = pool.size.fetch_sub(1, Ordering::Relaxed);
-            pool.size_semaphore.add_permits(1);
+            pool.release_slots(1);
         }
         this.obj.take().unwrap()
     }
+
+    /// Get object statistics
+    pub fn metrics(this: &Self) -> &Metrics {
+        &this.metrics
+    }
 }
 
 impl<T> Drop for Object<T> {
     fn drop(&mut self) {
         if let Some(obj) = self.obj.take() {
             if let Some(pool) = self.pool.upgrade() {
+                // Objects exceeding the size of a shrunk pool are dropped.
+                if pool.shrink_one() {
+                    return;
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    self.metrics.returned = Some(clock::now());
+                }
                 {
                     let mut queue = pool.queue.lock().unwrap();
-                    queue.push(obj);
+                    queue.push_back((obj, self.metrics));
                 }
                 let _ = pool.available.fetch_add(1, Ordering::Relaxed);
                 pool.semaphore.add_permits(1);
@@ -158,14 +194,44 @@ impl<T> Pool<T> {
     /// Create a new empty [`Pool`] using the given [`PoolConfig`].
     #[must_use]
     pub fn from_config(config: &PoolConfig) -> Self {
+        Self::build(config, None)
+    }
+
+    /// Create a new empty [`Pool`] using the given [`PoolConfig`] which
+    /// creates objects on demand using the given `factory`.
+    ///
+    /// The `factory` is called by [`Pool::get()`] and [`Pool::timeout_get()`]
+    /// if no idle object is available but the [`Pool`] hasn't reached its
+    /// maximum size yet. The created object is handed out right away and
+    /// returned to the [`Pool`] afterwards just like objects which have been
+    /// added via [`Pool::add()`]. The `timeout` of the [`PoolConfig`]
+    /// includes the time spent creating the object.
+    ///
+    /// [`Pool::try_get()`] never calls the `factory` as it can't wait for the
+    /// object to be created.
+    #[must_use]
+    pub fn with_factory<F, Fut>(config: &PoolConfig, factory: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        Self::build(config, Some(Factory(Box::new(move || Box::pin(factory())))))
+    }
+
+    fn build(config: &PoolConfig, factory: Option<Factory<T>>) -> Self {
         Self {
             inner: Arc::new(PoolInner {
                 config: *config,
-                queue: Mutex::new(Vec::with_capacity(config.max_size)),
+                queue: Mutex::new(VecDeque::with_capacity(config.max_size)),
+                max_size: AtomicUsize::new(config.max_size),
                 size: AtomicUsize::new(0),
                 size_semaphore: Semaphore::new(config.max_size),
+                revoked: Mutex::new(0),
                 available: AtomicIsize::new(0),
+                waiting: AtomicUsize::new(0),
+                creating: AtomicUsize::new(0),
                 semaphore: Semaphore::new(0),
+                factory,
             }),
         }
     }
@@ -193,16 +259,8 @@ impl<T> Pool<T> {
             TryAcquireError::NoPermits => PoolError::Timeout,
             TryAcquireError::Closed => PoolError::Closed,
         })?;
-        let obj = {
-            let mut queue = inner.queue.lock().unwrap();
-            queue.pop().unwrap()
-        };
         permit.forget();
-        let _ = inner.available.fetch_sub(1, Ordering::Relaxed);
-        Ok(Object {
-            pool: Arc::downgrade(&self.inner),
-            obj: Some(obj),
-        })
+        Ok(self.take_idle())
     }
 
     /// Retrieves an [`Object`] from this [`Pool`] using a different `timeout`
@@ -212,7 +270,11 @@ impl<T> Pool<T> {
     ///
     /// See [`PoolError`] for details.
     pub async fn timeout_get(&self, timeout: Option<Duration>) -> Result<Object<T>, PoolError> {
+        if let Some(obj) = self.create(timeout).await? {
+            return Ok(obj);
+        }
         let inner = self.inner.as_ref();
+        let _waiting = Counting::new(&inner.waiting);
         let permit = match (timeout, inner.config.runtime) {
             (None, _) => inner
                 .semaphore
@@ -232,16 +294,63 @@ impl<T> Pool<T> {
                 .map_err(|_| PoolError::Closed),
             (Some(_), None) => Err(PoolError::NoRuntimeSpecified),
         }?;
-        let obj = {
-            let mut queue = inner.queue.lock().unwrap();
-            queue.pop().unwrap()
+        permit.forget();
+        Ok(self.take_idle())
+    }
+
+    /// Creates a new object via the [`Factory`] if no idle object is
+    /// available and the [`Pool`] hasn't reached its maximum size yet.
+    async fn create(&self, timeout: Option<Duration>) -> Result<Option<Object<T>>, PoolError> {
+        let inner = self.inner.as_ref();
+        let Some(factory) = &inner.factory else {
+            return Ok(None);
+        };
+        if timeout.is_some_and(|t| t.as_nanos() == 0) || inner.semaphore.available_permits() > 0 {
+            return Ok(None);
+        }
+        // The permit is returned if creating the object times out or the
+        // future is dropped.
+        let Ok(permit) = inner.size_semaphore.try_acquire() else {
+            return Ok(None);
+        };
+        let _creating = Counting::new(&inner.creating);
+        let obj = match (timeout, inner.config.runtime) {
+            (None, _) => (factory.0)().await,
+            (Some(timeout), Some(runtime)) => runtime
+                .timeout(timeout, (factory.0)())
+                .await
+                .ok_or(PoolError::Timeout)?,
+            (Some(_), None) => return Err(PoolError::NoRuntimeSpecified),
         };
         permit.forget();
-        let _ = inner.available.fetch_sub(1, Ordering::Relaxed);
-        Ok(Object {
+        let _ = inner.size.fetch_add(1, Ordering::Relaxed);
+        let mut metrics = Metrics::default();
+        metrics.use_count += 1;
+        Ok(Some(Object {
+            obj: Some(obj),
+            metrics,
             pool: Arc::downgrade(&self.inner),
+        }))
+    }
+
+    /// Takes an idle object out of the queue. The caller must have acquired
+    /// a permit of the `semaphore`.
+    fn take_idle(&self) -> Object<T> {
+        let (obj, mut metrics) = {
+            let mut queue = self.inner.queue.lock().unwrap();
+            match self.inner.config.queue_mode {
+                QueueMode::Fifo => queue.pop_front(),
+                QueueMode::Lifo => queue.pop_back(),
+            }
+            .unwrap()
+        };
+        let _ = self.inner.available.fetch_sub(1, Ordering::Relaxed);
+        metrics.use_count += 1;
+        Object {
             obj: Some(obj),
-        })
+            metrics,
+            pool: Arc::downgrade(&self.inner),
+        }
     }
 
     /// Adds an `object` to this [`Pool`].
@@ -285,6 +394,84 @@ impl<T> Pool<T> {
         }
     }
 
+    /// Adds an `object` to this [`Pool`] and applies the given
+    /// [`GrowthPolicy`] if the [`Pool`] size has already reached its maximum.
+    ///
+    /// Unlike [`Pool::add()`] this only waits for a free slot if the
+    /// `policy` is [`GrowthPolicy::Reject`] or if it is
+    /// [`GrowthPolicy::ReplaceOldest`] and there is no idle object which could
+    /// be replaced.
+    ///
+    /// On success the object which was replaced, if any, is returned.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Pool`] has been closed a tuple containing the `object` and
+    /// the [`PoolError`] is returned instead.
+    pub async fn add_with(
+        &self,
+        object: T,
+        policy: GrowthPolicy,
+    ) -> Result<Option<T>, (T, PoolError)> {
+        match self.try_add_with(object, policy) {
+            Err((object, PoolError::Timeout)) => self.add(object).await.map(|()| None),
+            result => result,
+        }
+    }
+
+    /// Tries to add an `object` to this [`Pool`] and applies the given
+    /// [`GrowthPolicy`] if the [`Pool`] size has already reached its maximum.
+    ///
+    /// On success the object which was replaced, if any, is returned.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Pool`] has been closed, or it is full and the `policy` is
+    /// [`GrowthPolicy::Reject`], or it is full and the `policy` is
+    /// [`GrowthPolicy::ReplaceOldest`] but all objects are in use, then a
+    /// tuple containing the `object` and the [`PoolError`] is returned
+    /// instead.
+    pub fn try_add_with(
+        &self,
+        object: T,
+        policy: GrowthPolicy,
+    ) -> Result<Option<T>, (T, PoolError)> {
+        let object = match self.try_add(object) {
+            Ok(()) => return Ok(None),
+            Err((object, PoolError::Timeout)) => object,
+            Err(e) => return Err(e),
+        };
+        let inner = self.inner.as_ref();
+        match policy {
+            GrowthPolicy::Reject => Err((object, PoolError::Timeout)),
+            GrowthPolicy::Grow => {
+                {
+                    // Serializes with `resize()`.
+                    let _revoked = inner.revoked.lock().unwrap();
+                    if inner.size_semaphore.is_closed() {
+                        return Err((object, PoolError::Closed));
+                    }
+                    let _ = inner.max_size.fetch_add(1, Ordering::Relaxed);
+                }
+                self._add(object);
+                Ok(None)
+            }
+            GrowthPolicy::ReplaceOldest => {
+                let mut queue = inner.queue.lock().unwrap();
+                // See `remove_where()`.
+                match inner.semaphore.try_acquire() {
+                    Ok(_permit) => {
+                        let (oldest, _) = queue.pop_front().unwrap();
+                        queue.push_back((object, Metrics::default()));
+                        Ok(Some(oldest))
+                    }
+                    Err(TryAcquireError::NoPermits) => Err((object, PoolError::Timeout)),
+                    Err(TryAcquireError::Closed) => Err((object, PoolError::Closed)),
+                }
+            }
+        }
+    }
+
     /// Internal function which adds an `object` to this [`Pool`].
     ///
     /// Prior calling this it must be guaranteed that `size` doesn't exceed
@@ -294,7 +481,7 @@ impl<T> Pool<T> {
         let _ = self.inner.size.fetch_add(1, Ordering::Relaxed);
         {
             let mut queue = self.inner.queue.lock().unwrap();
-            queue.push(object);
+            queue.push_back((object, Metrics::default()));
         }
         let _ = self.inner.available.fetch_add(1, Ordering::Relaxed);
         self.inner.semaphore.add_permits(1);
@@ -316,6 +503,109 @@ impl<T> Pool<T> {
         self.timeout_get(timeout).await.map(Object::take)
     }
 
+    /// Removes all idle objects for which the `predicate` returns `true`
+    /// from this [`Pool`] and returns them.
+    ///
+    /// [`Object`]s which are currently in use are not passed to the
+    /// `predicate`. The size of this [`Pool`] is reduced by the number of
+    /// removed objects, so new objects can be added in their place.
+    ///
+    /// Idle objects which tasks are just about to retrieve via
+    /// [`Pool::get()`] are kept even if they match.
+    pub fn remove_where(&self, mut predicate: impl FnMut(&T) -> bool) -> Vec<T> {
+        let inner = self.inner.as_ref();
+        let mut removed = Vec::new();
+        {
+            let mut queue = inner.queue.lock().unwrap();
+            let idle = std::mem::take(&mut *queue);
+            for (obj, metrics) in idle {
+                // Every idle object is backed by a permit of the semaphore.
+                // Objects whose permit has already been taken by a waiting
+                // task must stay in the queue.
+                if predicate(&obj) {
+                    if let Ok(permit) = inner.semaphore.try_acquire() {
+                        permit.forget();
+                        removed.push(obj);
+                        continue;
+                    }
+                }
+                queue.push_back((obj, metrics));
+            }
+        }
+        let _ = inner
+            .available
+            .fetch_sub(removed.len() as isize, Ordering::Relaxed);
+        let _ = inner.size.fetch_sub(removed.len(), Ordering::Relaxed);
+        inner.release_slots(removed.len());
+        removed
+    }
+
+    /// Calls the given function for every idle object of this [`Pool`]
+    /// without retrieving it.
+    ///
+    /// This is meant for debugging and statistics. Objects which are
+    /// currently in use are not visited.
+    ///
+    /// **Caution:** This function blocks the entire pool while it is
+    /// running. Therefore the given function should not block.
+    pub fn visit_idle(&self, mut f: impl FnMut(&T, Metrics)) {
+        let queue = self.inner.queue.lock().unwrap();
+        for (obj, metrics) in queue.iter() {
+            f(obj, *metrics);
+        }
+    }
+
+    /// Resizes this [`Pool`] to the given `max_size`.
+    ///
+    /// Growing the [`Pool`] allows adding or creating more objects right
+    /// away. When shrinking it idle objects are dropped, least recently
+    /// returned first, until the [`Pool`] fits into the new `max_size`.
+    /// [`Object`]s which are currently in use are dropped when they are
+    /// returned to the [`Pool`] as long as it is still too large.
+    pub fn resize(&self, max_size: usize) {
+        let inner = self.inner.as_ref();
+        {
+            let mut revoked = inner.revoked.lock().unwrap();
+            let old_max_size = inner.max_size.swap(max_size, Ordering::Relaxed);
+            if max_size >= old_max_size {
+                let additional = max_size - old_max_size;
+                let paid = additional.min(*revoked);
+                *revoked -= paid;
+                inner.size_semaphore.add_permits(additional - paid);
+            } else {
+                // Free slots are taken away right away. Slots which are
+                // occupied are revoked as soon as they become free.
+                let mut shrink = old_max_size - max_size;
+                while shrink > 0 {
+                    match inner.size_semaphore.try_acquire() {
+                        Ok(permit) => permit.forget(),
+                        Err(_) => break,
+                    }
+                    shrink -= 1;
+                }
+                *revoked += shrink;
+            }
+        }
+        let mut removed = Vec::new();
+        {
+            let mut queue = inner.queue.lock().unwrap();
+            while !queue.is_empty() {
+                // See `remove_where()`.
+                let Ok(permit) = inner.semaphore.try_acquire() else {
+                    break;
+                };
+                if !inner.shrink_one() {
+                    break;
+                }
+                permit.forget();
+                removed.push(queue.pop_front().unwrap());
+            }
+        }
+        let _ = inner
+            .available
+            .fetch_sub(removed.len() as isize, Ordering::Relaxed);
+    }
+
     /// Closes this [`Pool`].
     ///
     /// All current and future tasks waiting for [`Object`]s will return
@@ -334,18 +624,15 @@ impl<T> Pool<T> {
     /// Retrieves [`Status`] of this [`Pool`].
     #[must_use]
     pub fn status(&self) -> Status {
-        let max_size = self.inner.config.max_size;
+        let max_size = self.inner.max_size.load(Ordering::Relaxed);
         let size = self.inner.size.load(Ordering::Relaxed);
         let available = self.inner.available.load(Ordering::Relaxed);
         Status {
             max_size,
             size,
-            available: if available > 0 { available as usize } else { 0 },
-            waiting: if available < 0 {
-                (-available) as usize
-            } else {
-                0
-            },
+            available: available.try_into().unwrap_or(0),
+            waiting: self.inner.waiting.load(Ordering::Relaxed),
+            creating: self.inner.creating.load(Ordering::Relaxed),
         }
     }
 }
@@ -353,23 +640,89 @@ impl<T> Pool<T> {
 #[derive(Debug)]
 struct PoolInner<T> {
     config: PoolConfig,
-    queue: Mutex<Vec<T>>,
+    queue: Mutex<VecDeque<(T, Metrics)>>,
     size: AtomicUsize,
+    /// Current maximum size. See [`Pool::resize()`].
+    max_size: AtomicUsize,
     /// This semaphore has as many permits as `max_size - size`. Every time
     /// an [`Object`] is added to the [`Pool`] a permit is removed from the
     /// semaphore and every time an [`Object`] is removed a permit is returned
     /// back.
     size_semaphore: Semaphore,
-    /// Number of available [`Object`]s in the [`Pool`]. If there are no
-    /// [`Object`]s in the [`Pool`] this number can become negative and store
-    /// the number of [`Future`]s waiting for an [`Object`].
+    /// Number of permits of the `size_semaphore` which still need to be
+    /// taken away after shrinking the [`Pool`].
+    revoked: Mutex<usize>,
+    /// Number of available [`Object`]s in the [`Pool`]. This number can
+    /// become negative for a short time while the [`Pool`] is being closed.
+    available: AtomicIsize,
+    /// Number of [`Future`]s waiting for an [`Object`].
     ///
     /// [`Future`]: std::future::Future
-    available: AtomicIsize,
+    waiting: AtomicUsize,
+    /// Number of [`Object`]s which are currently being created by the
+    /// [`Factory`].
+    creating: AtomicUsize,
     semaphore: Semaphore,
+    factory: Option<Factory<T>>,
+}
+
+/// Async closure passed to [`Pool::with_factory()`].
+struct Factory<T>(Box<dyn Fn() -> BoxFuture<T> + Send + Sync>);
+
+impl<T> fmt::Debug for Factory<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Factory")
+    }
+}
+
+/// Counts a task in the given counter as long as it is alive.
+struct Counting<'a>(&'a AtomicUsize);
+
+impl<'a> Counting<'a> {
+    fn new(counter: &'a AtomicUsize) -> Self {
+        let _ = counter.fetch_add(1, Ordering::Relaxed);
+        Self(counter)
+    }
+}
+
+impl Drop for Counting<'_> {
+    fn drop(&mut self) {
+        let _ = self.0.fetch_sub(1, Ordering::Relaxed);
+    }
 }
 
 impl<T> PoolInner<T> {
+    /// Reduces the size of this [`Pool`] by one if it exceeds its maximum
+    /// size. The caller must drop the object it is holding in this case.
+    fn shrink_one(&self) -> bool {
+        let mut size = self.size.load(Ordering::Relaxed);
+        while size > self.max_size.load(Ordering::Relaxed) {
+            match self.size.compare_exchange_weak(
+                size,
+                size - 1,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    self.release_slots(1);
+                    return true;
+                }
+                Err(actual) => size = actual,
+            }
+        }
+        false
+    }
+
+    /// Returns `n` permits to the `size_semaphore` after objects have been
+    /// removed from this [`Pool`]. Permits which have been revoked by
+    /// [`Pool::resize()`] are taken away instead.
+    fn release_slots(&self, n: usize) {
+        let mut revoked = self.revoked.lock().unwrap();
+        let paid = n.min(*revoked);
+        *revoked -= paid;
+        self.size_semaphore.add_permits(n - paid);
+    }
+
     /// Cleans up internals of this [`Pool`].
     ///
     /// This method is called after closing the [`Pool`] and whenever an
@@ -408,16 +761,24 @@ where
     /// Creates a new [`Pool`] from the given [`ExactSizeIterator`] of
     /// [`Object`]s.
     fn from(iter: I) -> Self {
-        let queue = iter.into_iter().collect::<Vec<_>>();
+        let queue = iter
+            .into_iter()
+            .map(|obj| (obj, Metrics::default()))
+            .collect::<VecDeque<_>>();
         let len = queue.len();
         Self {
             inner: Arc::new(PoolInner {
                 queue: Mutex::new(queue),
                 config: PoolConfig::new(len),
+                max_size: AtomicUsize::new(len),
                 size: AtomicUsize::new(len),
                 size_semaphore: Semaphore::new(0),
+                revoked: Mutex::new(0),
                 available: AtomicIsize::new(len.try_into().unwrap()),
+                waiting: AtomicUsize::new(0),
+                creating: AtomicUsize::new(0),
                 semaphore: Semaphore::new(len),
+                factory: None,
             }),
         }
     }
@@ -1,10 +1,17 @@
 #![cfg(feature = "managed")]
 
-use std::{convert::Infallible, time::Duration};
+use std::{
+    convert::Infallible,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
 
 use tokio::time;
 
-use deadpool::managed::{self, Metrics, Object, PoolError, RecycleResult, Timeouts};
+use deadpool::managed::{
+    self, BuildError, InvalidConfigError, Metrics, Object, PoolConfig, PoolError, RecycleResult,
+    RetryConfig, Timeouts,
+};
 
 type Pool = managed::Pool<Manager>;
 
@@ -70,6 +77,81 @@ async fn basic() {
     assert_eq!(status.waiting, 0);
 }
 
+#[tokio::test]
+async fn object_metrics() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    for _ in 0..2 {
+        drop(pool.get().await.unwrap());
+    }
+    let obj = pool.get().await.unwrap();
+    let metrics = Object::metrics(&obj);
+    assert_eq!(metrics.use_count, 3);
+    assert_eq!(metrics.recycle_count, 2);
+}
+
+#[tokio::test]
+async fn id_generator() {
+    let next = AtomicUsize::new(0);
+    let pool = Pool::builder(Manager {})
+        .max_size(2)
+        .id_generator(move || 7 << 16 | next.fetch_add(1, Ordering::Relaxed))
+        .build()
+        .unwrap();
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    assert_eq!(Object::id(&obj0), 0x7_0000);
+    assert_eq!(Object::id(&obj1), 0x7_0001);
+}
+
+#[test]
+fn invalid_config() {
+    let build = |config| Pool::builder(Manager {}).config(config).build();
+    assert!(matches!(
+        build(PoolConfig::new(0)),
+        Err(BuildError::InvalidConfig(InvalidConfigError::ZeroMaxSize))
+    ));
+    assert!(matches!(
+        build(PoolConfig {
+            timeouts: Timeouts::wait_millis(0),
+            ..PoolConfig::new(1)
+        }),
+        Err(BuildError::InvalidConfig(
+            InvalidConfigError::ZeroWaitTimeout
+        ))
+    ));
+    assert!(matches!(
+        build(PoolConfig {
+            min_idle: 2,
+            ..PoolConfig::new(1)
+        }),
+        Err(BuildError::InvalidConfig(
+            InvalidConfigError::MinIdleExceedsMaxSize {
+                min_idle: 2,
+                max_size: 1,
+            }
+        ))
+    ));
+    assert!(matches!(
+        build(PoolConfig {
+            create_retry: Some(RetryConfig::new(0, Duration::from_millis(10))),
+            ..PoolConfig::new(1)
+        }),
+        Err(BuildError::InvalidConfig(
+            InvalidConfigError::ZeroRetryAttempts
+        ))
+    ));
+    assert!(matches!(
+        build(PoolConfig {
+            create_retry: Some(RetryConfig::new(3, Duration::from_secs(60))),
+            ..PoolConfig::new(1)
+        }),
+        Err(BuildError::InvalidConfig(
+            InvalidConfigError::RetryBaseDelayExceedsMaxDelay
+        ))
+    ));
+    assert!(build(PoolConfig::new(1)).is_ok());
+}
+
 #[tokio::test]
 async fn closing() {
     let mgr = Manager {};
@@ -108,6 +190,66 @@ async fn closing() {
     assert_eq!(pool.status().waiting, 0);
 }
 
+#[tokio::test]
+async fn try_get() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+
+    let obj = pool.try_get().await.unwrap().unwrap();
+    assert!(pool.try_get().await.unwrap().is_none());
+    assert_eq!(pool.status().waiting, 0);
+    drop(obj);
+    assert!(pool.try_get().await.unwrap().is_some());
+
+    pool.close();
+    assert!(matches!(pool.try_get().await, Err(PoolError::Closed)));
+}
+
+#[tokio::test]
+async fn try_get_no_timeout() {
+    static TIMEOUTS: AtomicUsize = AtomicUsize::new(0);
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(1)
+        .on_timeout(|_| {
+            let _ = TIMEOUTS.fetch_add(1, Ordering::Relaxed);
+        })
+        .build()
+        .unwrap();
+
+    let _obj = pool.get().await.unwrap();
+    assert!(pool.try_get().await.unwrap().is_none());
+    // Nothing being available isn't a timeout.
+    assert_eq!(pool.counters().wait_timeouts, 0);
+    assert_eq!(TIMEOUTS.load(Ordering::Relaxed), 0);
+}
+
+#[tokio::test]
+async fn wait_for_available() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(2).build().unwrap();
+    pool.wait_for_available(2).await.unwrap();
+
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    let join_handle = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.wait_for_available(2).await })
+    };
+    drop(obj0);
+    tokio::task::yield_now().await;
+    assert!(!join_handle.is_finished());
+    drop(obj1);
+    join_handle.await.unwrap().unwrap();
+    assert_eq!(pool.status().available, 2);
+
+    pool.close();
+    assert!(matches!(
+        pool.wait_for_available(1).await,
+        Err(PoolError::Closed)
+    ));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn concurrent() {
     let mgr = Manager {};
@@ -226,3 +368,203 @@ async fn retain_fnmut() {
     }
     assert_eq!(pool.status().size, 0);
 }
+
+#[tokio::test]
+async fn visit_idle() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(4).build().unwrap();
+    {
+        let _a = pool.get().await.unwrap();
+        let _b = pool.get().await.unwrap();
+    }
+    let mut obj = pool.get().await.unwrap();
+    *obj = 42;
+    let mut visited = Vec::new();
+    pool.visit_idle(|obj, metrics| visited.push((*obj, metrics.use_count)));
+    assert_eq!(visited, [(0, 1)]);
+
+    drop(obj);
+    let mut visited = Vec::new();
+    pool.visit_idle(|obj, metrics| visited.push((*obj, metrics.use_count)));
+    visited.sort_unstable();
+    assert_eq!(visited, [(0, 1), (42, 2)]);
+    let status = pool.status();
+    assert_eq!(status.size, 2);
+    assert_eq!(status.available, 2);
+}
+
+#[tokio::test]
+async fn retain_async() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(4).build().unwrap();
+    {
+        let mut objs = Vec::new();
+        for i in 0..4 {
+            let mut obj = pool.get().await.unwrap();
+            *obj = i;
+            objs.push(obj);
+        }
+    }
+    let mut checked = 0;
+    let retain_result = pool
+        .retain_async(|obj, _| {
+            checked += 1;
+            let even = *obj % 2 == 0;
+            Box::pin(async move {
+                tokio::task::yield_now().await;
+                even
+            })
+        })
+        .await;
+    assert_eq!(checked, 4);
+    assert_eq!(retain_result.retained, 2);
+    let mut removed = retain_result.removed;
+    removed.sort_unstable();
+    assert_eq!(removed, vec![1, 3]);
+    assert_eq!(pool.status().size, 2);
+    assert_eq!(pool.status().available, 2);
+    assert_eq!(*pool.get().await.unwrap() % 2, 0);
+}
+
+#[tokio::test]
+async fn warm_up() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(4).build().unwrap();
+    assert_eq!(pool.warm_up(2).await.unwrap(), 2);
+    assert_eq!(pool.status().size, 2);
+    assert_eq!(pool.status().available, 2);
+    // Existing idle objects are taken into account
+    assert_eq!(pool.warm_up(3).await.unwrap(), 1);
+    assert_eq!(pool.status().size, 3);
+    // The max_size is never exceeded
+    assert_eq!(pool.warm_up(8).await.unwrap(), 1);
+    assert_eq!(pool.status().size, 4);
+    assert_eq!(pool.status().available, 4);
+}
+
+#[tokio::test]
+async fn fair_waiting() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+    let obj = pool.get().await.unwrap();
+    let order = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let join_handles = (0..4)
+        .map(|i| {
+            let pool = pool.clone();
+            let order = order.clone();
+            tokio::spawn(async move {
+                let _obj = pool.get().await.unwrap();
+                order.lock().unwrap().push(i);
+                tokio::task::yield_now().await;
+            })
+        })
+        .collect::<Vec<_>>();
+    // Make sure all tasks are waiting in the order they were spawned
+    for _ in 0..4 {
+        tokio::task::yield_now().await;
+    }
+    assert_eq!(pool.status().waiting, 4);
+    drop(obj);
+    for join_handle in join_handles {
+        join_handle.await.unwrap();
+    }
+    assert_eq!(*order.lock().unwrap(), vec![0, 1, 2, 3]);
+}
+
+#[tokio::test]
+async fn max_waiters() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(1)
+        .max_waiters(Some(1))
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    let join_handle = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.get().await.map(drop) })
+    };
+    tokio::task::yield_now().await;
+    assert_eq!(pool.status().waiting, 1);
+    assert!(matches!(pool.get().await, Err(PoolError::QueueFull)));
+    assert_eq!(pool.status().waiting, 1);
+    drop(obj);
+    join_handle.await.unwrap().unwrap();
+    // The queue is empty again
+    let obj = pool.get().await.unwrap();
+    let join_handle = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.get().await.map(drop) })
+    };
+    tokio::task::yield_now().await;
+    drop(obj);
+    join_handle.await.unwrap().unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn shards() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(8).shards(4).build().unwrap();
+    let join_handles = (0..32)
+        .map(|_| {
+            let pool = pool.clone();
+            tokio::spawn(async move {
+                for _ in 0..100 {
+                    let _obj = pool.get().await.unwrap();
+                    tokio::task::yield_now().await;
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for join_handle in join_handles {
+        join_handle.await.unwrap();
+    }
+    let status = pool.status();
+    assert!(status.size <= 8);
+    assert_eq!(status.available, status.size);
+    assert_eq!(pool.retain(|_, _| false).removed.len(), status.size);
+    assert_eq!(pool.status().size, 0);
+}
+
+#[tokio::test]
+async fn weak_pool() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let weak = pool.weak();
+    let cloned = weak.clone();
+    {
+        let upgraded = weak.upgrade().unwrap();
+        let _obj = upgraded.get().await.unwrap();
+        assert_eq!(pool.status().size, 1);
+    }
+    drop(pool);
+    assert!(weak.upgrade().is_none());
+    assert!(cloned.upgrade().is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn status_while_getting() {
+    let pool = Pool::builder(Manager {}).max_size(4).build().unwrap();
+    let workers: Vec<_> = (0..8)
+        .map(|_| {
+            let pool = pool.clone();
+            tokio::spawn(async move {
+                for _ in 0..1000 {
+                    drop(pool.get().await.unwrap());
+                }
+            })
+        })
+        .collect();
+    for _ in 0..1000 {
+        let status = pool.status();
+        assert_eq!(status.max_size, 4);
+        assert!(status.size <= status.max_size);
+        assert!(status.available <= status.size);
+        tokio::task::yield_now().await;
+    }
+    for worker in workers {
+        worker.await.unwrap();
+    }
+    let status = pool.status();
+    assert_eq!(status.available, status.size);
+    assert_eq!(status.waiting, 0);
+}
@@ -0,0 +1,143 @@
+#![cfg(all(feature = "test-utils", feature = "rt_tokio_1"))]
+
+use std::time::Duration;
+
+use deadpool::{
+    managed::{self, Acquire, Object},
+    test_utils::{MockManager, Step},
+    Runtime,
+};
+use tokio::time::timeout;
+
+type Pool = managed::Pool<MockManager>;
+
+fn pool(manager: MockManager, max_size: usize) -> Pool {
+    Pool::builder(manager)
+        .max_size(max_size)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap()
+}
+
+/// Gives up on `acquire` after it had the chance to make some progress.
+async fn abandon(acquire: Acquire<'_, MockManager>) {
+    assert!(timeout(Duration::from_millis(10), acquire).await.is_err());
+}
+
+#[test]
+fn acquire_is_send() {
+    fn assert_send<T: Send>() {}
+    assert_send::<Acquire<'static, MockManager>>();
+    assert_send::<Acquire<'static, MockManager, Object<MockManager>>>();
+}
+
+#[tokio::test]
+async fn acquire() {
+    let pool = pool(MockManager::new(), 1);
+    let obj = pool.acquire().await.unwrap();
+    assert_eq!(*obj, 0);
+    drop(obj);
+    assert_eq!(*pool.acquire().await.unwrap(), 0);
+    assert_eq!(pool.manager().created(), 1);
+}
+
+#[tokio::test]
+async fn drop_while_waiting() {
+    let pool = pool(MockManager::new(), 1);
+    let obj = pool.get().await.unwrap();
+    abandon(pool.acquire()).await;
+    let status = pool.status();
+    assert_eq!(status.waiting, 0);
+    assert_eq!(status.available, 0);
+    drop(obj);
+    assert_eq!(pool.status().available, 1);
+    assert_eq!(*pool.get().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn drop_while_creating() {
+    let manager = MockManager::new();
+    manager.push_create(Step::pending());
+    let pool = pool(manager, 1);
+    abandon(pool.acquire()).await;
+    let status = pool.status();
+    assert_eq!(status.size, 0);
+    assert_eq!(status.creating, 0);
+    assert_eq!(status.waiting, 0);
+    assert_eq!(pool.manager().create_calls(), 1);
+    assert_eq!(pool.manager().created(), 0);
+    assert_eq!(*pool.get().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn drop_while_recycling() {
+    let manager = MockManager::new();
+    manager.push_recycle(Step::pending());
+    let pool = pool(manager, 1);
+    drop(pool.get().await.unwrap());
+    abandon(pool.acquire()).await;
+    let status = pool.status();
+    assert_eq!(status.size, 0);
+    assert_eq!(status.waiting, 0);
+    assert_eq!(pool.manager().recycle_calls(), 1);
+    assert_eq!(pool.manager().detached(), 1);
+    assert_eq!(*pool.get().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn drop_many() {
+    let manager = MockManager::new();
+    manager.set_create_default(Step::ok().delay(Duration::from_millis(5)));
+    let pool = pool(manager, 4);
+    for _ in 0..16 {
+        let acquires = (0..8).map(|_| pool.acquire()).collect::<Vec<_>>();
+        for acquire in acquires {
+            let _ = timeout(Duration::from_millis(1), acquire).await;
+        }
+        let status = pool.status();
+        assert_eq!(status.waiting, 0);
+        assert_eq!(status.creating, 0);
+        assert_eq!(status.available, status.size);
+    }
+    // All slots are still usable at the same time.
+    let wait = Duration::from_secs(1);
+    let mut objs = Vec::new();
+    for _ in 0..4 {
+        objs.push(timeout(wait, pool.get()).await.unwrap().unwrap());
+    }
+    assert_eq!(pool.status().size, 4);
+}
+
+#[tokio::test]
+async fn status_while_creating() {
+    let manager = MockManager::new();
+    manager.push_create(Step::pending());
+    let pool = pool(manager, 2);
+    let creating = tokio::spawn({
+        let pool = pool.clone();
+        async move { pool.get().await.map(drop) }
+    });
+    while pool.status().creating == 0 {
+        tokio::task::yield_now().await;
+    }
+    // Tasks creating an object are neither waiting nor holding an object.
+    let status = pool.status();
+    assert_eq!(status.creating, 1);
+    assert_eq!(status.waiting, 0);
+    assert_eq!(status.size, 0);
+    let obj = pool.get().await.unwrap();
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 0);
+    assert_eq!(status.waiting, 0);
+    // Only tasks waiting for a permit count as waiting.
+    let mut waiting = std::pin::pin!(pool.get());
+    assert!(timeout(Duration::from_millis(10), &mut waiting)
+        .await
+        .is_err());
+    assert_eq!(pool.status().waiting, 1);
+    drop(obj);
+    assert!(waiting.await.is_ok());
+    assert_eq!(pool.status().waiting, 0);
+    creating.abort();
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "managed")]
+
+use std::convert::Infallible;
+
+use deadpool::managed::{self, Metrics, Object, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        Ok(0)
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+fn pool(max_size: usize) -> Pool {
+    Pool::builder(Manager {})
+        .max_size(max_size)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn add() {
+    let pool = pool(2);
+    let id = pool.add(42).unwrap();
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(pool.status().available, 1);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 42);
+    assert_eq!(Object::id(&obj), id);
+    assert_eq!(Object::metrics(&obj).recycle_count, 1);
+    assert_eq!(pool.counters().created, 0);
+}
+
+#[tokio::test]
+async fn add_full() {
+    let pool = pool(1);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(pool.add(42), Err(42));
+    drop(obj);
+    assert_eq!(pool.add(42), Err(42));
+    assert_eq!(pool.status().size, 1);
+}
+
+#[tokio::test]
+async fn add_closed() {
+    let pool = pool(1);
+    pool.close();
+    assert_eq!(pool.add(42), Err(42));
+    assert_eq!(pool.status().size, 0);
+}
+
+#[tokio::test]
+async fn hand_over() {
+    let old_pool = pool(2);
+    let obj = old_pool.get().await.unwrap();
+    let new_pool = pool(2);
+    new_pool.add(Object::take(obj)).unwrap();
+    assert_eq!(old_pool.status().size, 0);
+    assert_eq!(new_pool.status().size, 1);
+    let _obj = new_pool.get().await.unwrap();
+    assert_eq!(new_pool.status().size, 1);
+}
@@ -0,0 +1,88 @@
+#![cfg(all(feature = "managed", not(feature = "debug-leases")))]
+
+//! Checks that getting and returning idle objects doesn't allocate.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+use deadpool::managed::{self, Metrics, QueueMode, RecycleResult};
+
+/// Global allocator counting the allocations of the current thread so
+/// tests running in parallel don't interfere.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+struct Manager;
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+const ITERATIONS: usize = 1024;
+
+async fn allocations(queue_mode: QueueMode, shards: usize) -> usize {
+    let pool = managed::Pool::<Manager>::builder(Manager)
+        .max_size(4)
+        .shards(shards)
+        .queue_mode(queue_mode)
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    let before = ALLOCATIONS.with(Cell::get);
+    for _ in 0..ITERATIONS {
+        let obj0 = pool.get().await.unwrap();
+        let obj1 = pool.get().await.unwrap();
+        drop(obj0);
+        drop(pool.get().await.unwrap());
+        drop(obj1);
+    }
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn lifo() {
+    assert_eq!(allocations(QueueMode::Lifo, 1).await, 0);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn lifo_sharded() {
+    assert_eq!(allocations(QueueMode::Lifo, 2).await, 0);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn fifo() {
+    assert_eq!(allocations(QueueMode::Fifo, 1).await, 0);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn fifo_sharded() {
+    assert_eq!(allocations(QueueMode::Fifo, 2).await, 0);
+}
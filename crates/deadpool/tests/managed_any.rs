@@ -0,0 +1,68 @@
+#![cfg(feature = "managed")]
+
+use std::{convert::Infallible, time::Duration};
+
+use deadpool::managed::{self, DynPool, HealthProbe, Metrics, RecycleResult};
+
+struct NumberManager {}
+
+impl managed::Manager for NumberManager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        Ok(0)
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+struct StringManager {}
+
+impl managed::Manager for StringManager {
+    type Type = String;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<String, Infallible> {
+        Ok(String::new())
+    }
+
+    async fn recycle(&self, _: &mut String, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn registry() {
+    let numbers = managed::Pool::<NumberManager>::builder(NumberManager {})
+        .name("numbers")
+        .max_size(2)
+        .build()
+        .unwrap();
+    let strings = managed::Pool::<StringManager>::builder(StringManager {})
+        .name("strings")
+        .max_size(4)
+        .build()
+        .unwrap();
+    drop(numbers.get().await.unwrap());
+    let registry: Vec<DynPool> = vec![Box::new(numbers.clone()), Box::new(strings.clone())];
+
+    let names = registry.iter().map(|pool| pool.name()).collect::<Vec<_>>();
+    assert_eq!(names, [Some("numbers"), Some("strings")]);
+    assert_eq!(registry[0].status().size, 1);
+    assert_eq!(registry[0].counters().created, 1);
+
+    registry[1].resize(8);
+    assert_eq!(strings.status().max_size, 8);
+
+    let health = registry[1].health(Duration::from_secs(1)).await;
+    assert_eq!(health.probe, HealthProbe::Created);
+
+    for pool in &registry {
+        pool.close();
+    }
+    assert!(numbers.is_closed());
+    assert!(registry[1].is_closed());
+}
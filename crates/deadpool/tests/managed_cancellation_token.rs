@@ -0,0 +1,94 @@
+#![cfg(all(feature = "managed", feature = "tokio-util"))]
+
+use std::{convert::Infallible, time::Duration};
+
+use deadpool::managed::{self, Metrics, PoolError, RecycleResult};
+use tokio_util::sync::CancellationToken;
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {
+    slow_create: bool,
+}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        if self.slow_create {
+            tokio::time::sleep(Duration::MAX).await;
+        }
+        Ok(())
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn cancelled_before() {
+    let pool = Pool::builder(Manager { slow_create: false })
+        .max_size(1)
+        .build()
+        .unwrap();
+    let token = CancellationToken::new();
+    token.cancel();
+    assert!(matches!(
+        pool.get_cancellable(&token).await,
+        Err(PoolError::Cancelled)
+    ));
+    assert_eq!(pool.status().size, 0);
+}
+
+#[tokio::test]
+async fn cancelled_while_waiting() {
+    let pool = Pool::builder(Manager { slow_create: false })
+        .max_size(1)
+        .build()
+        .unwrap();
+    let _obj = pool.get().await.unwrap();
+    let token = CancellationToken::new();
+    let join_handle = {
+        let pool = pool.clone();
+        let token = token.clone();
+        tokio::spawn(async move { pool.get_cancellable(&token).await.map(drop) })
+    };
+    tokio::task::yield_now().await;
+    assert_eq!(pool.status().waiting, 1);
+    token.cancel();
+    assert!(matches!(
+        join_handle.await.unwrap(),
+        Err(PoolError::Cancelled)
+    ));
+    assert_eq!(pool.status().waiting, 0);
+}
+
+#[tokio::test]
+async fn cancelled_while_creating() {
+    let pool = Pool::builder(Manager { slow_create: true })
+        .max_size(1)
+        .build()
+        .unwrap();
+    let token = CancellationToken::new();
+    let join_handle = {
+        let pool = pool.clone();
+        let token = token.clone();
+        tokio::spawn(async move { pool.get_cancellable(&token).await.map(drop) })
+    };
+    tokio::task::yield_now().await;
+    // The object being created doesn't count towards the size, yet, and
+    // the task creating it isn't waiting for a permit.
+    assert_eq!(pool.status().size, 0);
+    assert_eq!(pool.status().waiting, 0);
+    assert_eq!(pool.status().creating, 1);
+    token.cancel();
+    assert!(matches!(
+        join_handle.await.unwrap(),
+        Err(PoolError::Cancelled)
+    ));
+    assert_eq!(pool.status().size, 0);
+    assert_eq!(pool.status().waiting, 0);
+    assert_eq!(pool.status().creating, 0);
+}
@@ -0,0 +1,84 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    convert::Infallible,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use tokio::time;
+
+use deadpool::{
+    managed::{self, Metrics, Object, PoolError, RecycleResult, TimeoutError, TimeoutType},
+    Runtime,
+};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    created: AtomicUsize,
+}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        Ok(self.created.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn metrics() {
+    let pool = Pool::builder(Manager::default())
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    time::advance(Duration::from_secs(5)).await;
+    assert_eq!(Object::metrics(&obj).age(), Duration::from_secs(5));
+    drop(obj);
+    time::advance(Duration::from_secs(3)).await;
+    let obj = pool.get().await.unwrap();
+    assert_eq!(Object::metrics(&obj).age(), Duration::from_secs(8));
+    assert_eq!(Object::metrics(&obj).last_used(), Duration::ZERO);
+}
+
+#[tokio::test(start_paused = true)]
+async fn max_lifetime() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .max_lifetime(Some(Duration::from_secs(60)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    time::advance(Duration::from_secs(59)).await;
+    assert_eq!(*pool.get().await.unwrap(), 0);
+    time::advance(Duration::from_secs(1)).await;
+    assert_eq!(*pool.get().await.unwrap(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn wait_timeout() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .wait_timeout(Some(Duration::from_secs(30)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let _obj = pool.get().await.unwrap();
+    match pool.get().await {
+        Err(PoolError::Timeout(TimeoutError {
+            timeout_type: TimeoutType::Wait,
+            elapsed,
+            ..
+        })) => assert_eq!(elapsed, Duration::from_secs(30)),
+        _ => panic!("expected a wait timeout"),
+    }
+}
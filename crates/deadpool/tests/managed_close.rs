@@ -0,0 +1,86 @@
+#![cfg(feature = "managed")]
+
+use std::{convert::Infallible, time::Duration};
+
+use deadpool::managed::{self, Metrics, PoolError, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn close_graceful_idle() {
+    let pool = Pool::builder(Manager {}).max_size(2).build().unwrap();
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.status().size, 1);
+    pool.close_graceful(None).await.unwrap();
+    assert!(pool.is_closed());
+    assert_eq!(pool.status().size, 0);
+    assert!(matches!(pool.get().await, Err(PoolError::Closed)));
+}
+
+#[tokio::test]
+async fn close_graceful_waits() {
+    let pool = Pool::builder(Manager {}).max_size(2).build().unwrap();
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    let join_handle = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        drop(obj0);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        drop(obj1);
+    });
+    pool.close_graceful(None).await.unwrap();
+    assert_eq!(pool.status().size, 0);
+    join_handle.await.unwrap();
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn close_graceful_timeout() {
+    let pool = Pool::builder(Manager {})
+        .max_size(2)
+        .runtime(deadpool::Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    assert!(matches!(
+        pool.close_graceful(Some(Duration::from_millis(10))).await,
+        Err(PoolError::Timeout(managed::TimeoutError {
+            timeout_type: managed::TimeoutType::Wait,
+            ..
+        }))
+    ));
+    assert!(pool.is_closed());
+    assert_eq!(pool.status().size, 1);
+    drop(obj);
+    assert_eq!(pool.status().size, 0);
+}
+
+#[tokio::test]
+async fn drain() {
+    let pool = Pool::builder(Manager {}).max_size(3).build().unwrap();
+    let obj0 = pool.get().await.unwrap();
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.status().size, 2);
+    assert_eq!(pool.drain().len(), 1);
+    assert!(pool.is_closed());
+    assert_eq!(pool.status().size, 1);
+    drop(obj0);
+    assert_eq!(pool.status().size, 0);
+    assert!(pool.drain().is_empty());
+}
@@ -1,20 +1,30 @@
 #![cfg(all(feature = "managed", feature = "serde"))]
 
-use std::{collections::HashMap, env, time::Duration};
+use std::{
+    collections::HashMap,
+    env,
+    sync::{Mutex, MutexGuard},
+    time::Duration,
+};
 
 use config::Config;
 use serde::{Deserialize, Serialize};
 
 use deadpool::managed::PoolConfig;
 
+/// Tests modifying the environment must not run concurrently.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
 struct Env {
     backup: HashMap<String, Option<String>>,
+    _lock: MutexGuard<'static, ()>,
 }
 
 impl Env {
     pub fn new() -> Self {
         Self {
             backup: HashMap::new(),
+            _lock: ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner()),
         }
     }
     pub fn set(&mut self, name: &str, value: &str) {
@@ -62,3 +72,47 @@ fn from_env() {
     assert_eq!(cfg.pool.timeouts.create, Some(Duration::from_secs(2)));
     assert_eq!(cfg.pool.timeouts.recycle, Some(Duration::from_secs(3)));
 }
+
+#[test]
+fn from_env_human_readable() {
+    let mut env = Env::new();
+    env.set("POOL__MAX_SIZE", "42");
+    env.set("POOL__TIMEOUTS__WAIT", "500ms");
+    env.set("POOL__TIMEOUTS__CREATE", "2s");
+    env.set("POOL__IDLE_TIMEOUT", "1m 30s");
+
+    let cfg = Config::builder()
+        .add_source(config::Environment::default().separator("__"))
+        .build()
+        .unwrap()
+        .try_deserialize::<TestConfig>()
+        .unwrap();
+
+    assert_eq!(cfg.pool.timeouts.wait, Some(Duration::from_millis(500)));
+    assert_eq!(cfg.pool.timeouts.create, Some(Duration::from_secs(2)));
+    assert_eq!(cfg.pool.timeouts.recycle, None);
+    assert_eq!(cfg.pool.idle_timeout, Some(Duration::from_secs(90)));
+}
+
+#[test]
+fn from_json() {
+    let cfg = serde_json::from_str::<PoolConfig>(
+        r#"{
+            "max_size": 4,
+            "timeouts": {
+                "wait": "30s",
+                "create": { "secs": 1, "nanos": 500 },
+                "recycle": null
+            }
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(cfg.timeouts.wait, Some(Duration::from_secs(30)));
+    assert_eq!(cfg.timeouts.create, Some(Duration::new(1, 500)));
+    assert_eq!(cfg.timeouts.recycle, None);
+
+    assert!(serde_json::from_str::<PoolConfig>(
+        r#"{ "max_size": 4, "timeouts": { "wait": "soon" } }"#
+    )
+    .is_err());
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "managed")]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use deadpool::managed::{
+    self, Metrics, Object, PoolError, RecycleResult, TimeoutError, TimeoutType, Timeouts,
+};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    broken: AtomicBool,
+}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        if self.broken.load(Ordering::Relaxed) {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<()> {
+        if self.broken.load(Ordering::Relaxed) {
+            Err(().into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[tokio::test]
+async fn counters() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    let counters = pool.counters();
+    assert_eq!(counters.created, 1);
+    assert_eq!(counters.recycled, 1);
+
+    pool.manager().broken.store(true, Ordering::Relaxed);
+    assert!(matches!(pool.get().await, Err(PoolError::Backend(()))));
+    let counters = pool.counters();
+    assert_eq!(counters.recycle_failed, 1);
+    assert_eq!(counters.create_failed, 1);
+
+    pool.manager().broken.store(false, Ordering::Relaxed);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(Object::take(obj), ());
+    assert_eq!(pool.counters().created, 2);
+    assert_eq!(pool.counters().detached, 1);
+}
+
+#[tokio::test]
+async fn timeouts() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .build()
+        .unwrap();
+    let _obj = pool.get().await.unwrap();
+    let timeouts = Timeouts {
+        wait: Some(Duration::ZERO),
+        ..pool.timeouts()
+    };
+    assert!(matches!(
+        pool.timeout_get(&timeouts).await,
+        Err(PoolError::Timeout(TimeoutError {
+            timeout_type: TimeoutType::Wait,
+            ..
+        }))
+    ));
+    let counters = pool.counters();
+    assert_eq!(counters.wait_timeouts, 1);
+    assert_eq!(counters.timeouts(TimeoutType::Wait), 1);
+    assert_eq!(counters.timeouts(TimeoutType::Create), 0);
+}
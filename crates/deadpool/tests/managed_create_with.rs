@@ -0,0 +1,72 @@
+#![cfg(feature = "managed")]
+
+use std::{any::Any, convert::Infallible};
+
+use deadpool::managed::{self, Metrics, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+struct Role(&'static str);
+
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = String;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<String, Infallible> {
+        Ok("default".to_string())
+    }
+
+    async fn create_with(&self, ctx: &(dyn Any + Send + Sync)) -> Result<String, Infallible> {
+        match ctx.downcast_ref::<Role>() {
+            Some(role) => Ok(role.0.to_string()),
+            None => self.create().await,
+        }
+    }
+
+    async fn recycle(&self, _conn: &mut String, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn create_with() {
+    let pool = Pool::builder(Manager {}).max_size(2).build().unwrap();
+    let obj0 = pool.get_with(&Role("admin")).await.unwrap();
+    let obj1 = pool.get_with(&42).await.unwrap();
+    assert_eq!(*obj0, "admin");
+    assert_eq!(*obj1, "default");
+}
+
+#[tokio::test]
+async fn idle_objects_are_reused() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    drop(pool.get().await.unwrap());
+    let obj = pool.get_with(&Role("admin")).await.unwrap();
+    assert_eq!(*obj, "default");
+}
+
+struct DefaultManager {}
+
+impl managed::Manager for DefaultManager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn default_create_with() {
+    let pool = managed::Pool::<DefaultManager>::builder(DefaultManager {})
+        .max_size(1)
+        .build()
+        .unwrap();
+    assert!(pool.get_with(&Role("admin")).await.is_ok());
+}
@@ -86,7 +86,8 @@ async fn pool_drained() {
     task::yield_now().await;
     assert_eq!(pool.status().size, 0);
     assert_eq!(pool.status().available, 0);
-    assert_eq!(pool.status().waiting, 1);
+    assert_eq!(pool.status().waiting, 0);
+    assert_eq!(pool.status().creating, 1);
 
     // let second task wait for the connection
     let pool_clone = pool.clone();
@@ -94,14 +95,16 @@ async fn pool_drained() {
     task::yield_now().await;
     assert_eq!(pool.status().size, 0);
     assert_eq!(pool.status().available, 0);
-    assert_eq!(pool.status().waiting, 2);
+    assert_eq!(pool.status().waiting, 1);
 
-    // first task receives an error
+    // first task receives an error and the second one starts creating
     rc.create_err();
     assert!(get_1.await.unwrap().is_err());
+    task::yield_now().await;
     assert_eq!(pool.status().size, 0);
     assert_eq!(pool.status().available, 0);
-    assert_eq!(pool.status().waiting, 1);
+    assert_eq!(pool.status().waiting, 0);
+    assert_eq!(pool.status().creating, 1);
 
     // the second task should now be able to create an object
     rc.create_ok();
@@ -0,0 +1,40 @@
+#![cfg(all(feature = "managed", feature = "debug-leases"))]
+
+use std::{backtrace::BacktraceStatus, convert::Infallible};
+
+use deadpool::managed::{self, Metrics, Object, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn leases() {
+    let pool = Pool::builder(Manager {}).max_size(2).build().unwrap();
+    assert!(pool.leases().is_empty());
+    let obj1 = pool.get().await.unwrap();
+    let obj2 = pool.get().await.unwrap();
+    let leases = pool.leases();
+    assert_eq!(
+        leases.iter().map(|lease| lease.id).collect::<Vec<_>>(),
+        [Object::id(&obj1), Object::id(&obj2)]
+    );
+    assert_eq!(leases[0].backtrace.status(), BacktraceStatus::Captured);
+    drop(obj1);
+    assert_eq!(pool.leases().len(), 1);
+    assert_eq!(Object::take(obj2), ());
+    assert!(pool.leases().is_empty());
+}
@@ -0,0 +1,99 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    convert::Infallible,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use deadpool::{
+    managed::{self, Metrics, RecycleResult},
+    Runtime,
+};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    next_id: Mutex<usize>,
+    destroyed: Arc<Mutex<Vec<usize>>>,
+}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        let mut next_id = self.next_id.lock().unwrap();
+        *next_id += 1;
+        Ok(*next_id)
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+
+    async fn destroy(&self, obj: usize) {
+        tokio::task::yield_now().await;
+        self.destroyed.lock().unwrap().push(obj);
+    }
+}
+
+async fn settle() {
+    tokio::time::sleep(Duration::from_millis(10)).await;
+}
+
+#[tokio::test]
+async fn close() {
+    let manager = Manager::default();
+    let destroyed = manager.destroyed.clone();
+    let pool = Pool::builder(manager)
+        .max_size(2)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let obj1 = pool.get().await.unwrap();
+    drop(pool.get().await.unwrap());
+    pool.close();
+    settle().await;
+    assert_eq!(*destroyed.lock().unwrap(), vec![2]);
+    drop(obj1);
+    settle().await;
+    let mut destroyed = destroyed.lock().unwrap().clone();
+    destroyed.sort_unstable();
+    assert_eq!(destroyed, vec![1, 2]);
+}
+
+#[tokio::test]
+async fn take() {
+    let manager = Manager::default();
+    let destroyed = manager.destroyed.clone();
+    let pool = Pool::builder(manager)
+        .max_size(1)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    assert_eq!(managed::Object::take(obj), 1);
+    pool.close();
+    settle().await;
+    assert!(destroyed.lock().unwrap().is_empty());
+}
+
+#[test]
+fn no_runtime() {
+    let manager = Manager::default();
+    let destroyed = manager.destroyed.clone();
+    // Built outside of a Tokio runtime so no runtime is detected.
+    let pool = Pool::builder(manager).max_size(1).build().unwrap();
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            drop(pool.get().await.unwrap());
+            pool.close();
+            settle().await;
+        });
+    assert!(destroyed.lock().unwrap().is_empty());
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    convert::Infallible,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use deadpool::managed::{self, DetachMode, Metrics, Object, PoolConfig, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    created: AtomicUsize,
+}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        Ok(self.created.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+fn pool(mode: DetachMode) -> Pool {
+    Pool::builder(Manager::default())
+        .max_size(2)
+        .detach_mode(mode)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn default_mode() {
+    assert_eq!(PoolConfig::default().detach_mode, DetachMode::Release);
+}
+
+#[tokio::test]
+async fn release() {
+    let pool = pool(DetachMode::Release);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(Object::take(obj), 0);
+    let status = pool.status();
+    assert_eq!(status.max_size, 2);
+    assert_eq!(status.size, 0);
+    assert_eq!(status.available, 0);
+    let _obj1 = pool.get().await.unwrap();
+    let _obj2 = pool.get().await.unwrap();
+    assert_eq!(pool.status().size, 2);
+}
+
+#[tokio::test]
+async fn shrink() {
+    let pool = pool(DetachMode::Shrink);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(Object::take(obj), 0);
+    let status = pool.status();
+    assert_eq!(status.max_size, 1);
+    assert_eq!(status.size, 0);
+    let _obj = pool.get().await.unwrap();
+    assert!(pool.try_get().await.unwrap().is_none());
+    pool.resize(2);
+    assert!(pool.try_get().await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn shrink_while_resizing() {
+    let pool = pool(DetachMode::Shrink);
+    let obj1 = pool.get().await.unwrap();
+    let obj2 = pool.get().await.unwrap();
+    pool.resize(1);
+    assert_eq!(Object::take(obj1), 0);
+    assert_eq!(pool.status().max_size, 0);
+    drop(obj2);
+    let status = pool.status();
+    assert_eq!(status.size, 0);
+    assert!(pool.try_get().await.unwrap().is_none());
+    pool.resize(1);
+    assert!(pool.try_get().await.unwrap().is_some());
+}
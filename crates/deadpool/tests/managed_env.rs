@@ -0,0 +1,71 @@
+#![cfg(all(feature = "managed", feature = "env"))]
+
+use std::{env, time::Duration};
+
+use deadpool::managed::{DetachMode, FromEnvError, PoolConfig, QueueMode, RecycleTimeoutMode};
+
+// Every test uses its own prefix so they can run concurrently.
+
+#[test]
+fn from_env() {
+    env::set_var("ENV_TEST_A__MAX_SIZE", "42");
+    env::set_var("ENV_TEST_A__TIMEOUTS__WAIT", "500ms");
+    env::set_var("ENV_TEST_A__TIMEOUTS__CREATE__SECS", "2");
+    env::set_var("ENV_TEST_A__QUEUE_MODE", "lifo");
+    env::set_var("ENV_TEST_A__IDLE_TIMEOUT", "1m 30s");
+    env::set_var("ENV_TEST_A__RECYCLE_ON_RETURN", "yes");
+    env::set_var("ENV_TEST_A__CREATE_RETRY__MAX_ATTEMPTS", "3");
+    env::set_var("ENV_TEST_A__CREATE_RETRY__BASE_DELAY", "100ms");
+    env::set_var("ENV_TEST_A__CREATE_RETRY__MAX_DELAY", "5s");
+    env::set_var("ENV_TEST_A__RECYCLE_TIMEOUT_MODE", "return");
+    env::set_var("ENV_TEST_A__DETACH_MODE", "shrink");
+
+    let cfg = PoolConfig::from_env("ENV_TEST_A").unwrap();
+    assert_eq!(cfg.max_size, 42);
+    assert_eq!(cfg.timeouts.wait, Some(Duration::from_millis(500)));
+    assert_eq!(cfg.timeouts.create, Some(Duration::from_secs(2)));
+    assert_eq!(cfg.timeouts.recycle, None);
+    assert!(matches!(cfg.queue_mode, QueueMode::Lifo));
+    assert_eq!(cfg.idle_timeout, Some(Duration::from_secs(90)));
+    assert!(cfg.recycle_on_return);
+    let retry = cfg.create_retry.unwrap();
+    assert_eq!(retry.max_attempts, 3);
+    assert_eq!(retry.base_delay, Duration::from_millis(100));
+    assert_eq!(retry.max_delay, Duration::from_secs(5));
+    assert!(retry.jitter);
+    assert!(cfg.autoscale.is_none());
+    assert_eq!(cfg.recycle_timeout_mode, RecycleTimeoutMode::Return);
+    assert_eq!(cfg.detach_mode, DetachMode::Shrink);
+}
+
+#[test]
+fn from_env_defaults() {
+    let cfg = PoolConfig::from_env("ENV_TEST_B").unwrap();
+    let default = PoolConfig::default();
+    assert_eq!(cfg.max_size, default.max_size);
+    assert_eq!(cfg.timeouts.wait, None);
+    assert_eq!(cfg.shards, 1);
+    assert_eq!(cfg.max_holders, 1);
+}
+
+#[test]
+fn from_env_invalid() {
+    env::set_var("ENV_TEST_C__MAX_SIZE", "many");
+    assert!(matches!(
+        PoolConfig::from_env("ENV_TEST_C"),
+        Err(FromEnvError::Invalid { name, value, .. })
+            if name == "ENV_TEST_C__MAX_SIZE" && value == "many"
+    ));
+}
+
+#[test]
+fn from_env_missing() {
+    env::set_var("ENV_TEST_D__AUTOSCALE__MIN_SIZE", "1");
+    env::set_var("ENV_TEST_D__AUTOSCALE__MAX_SIZE", "8");
+    assert_eq!(
+        PoolConfig::from_env("ENV_TEST_D").unwrap_err(),
+        FromEnvError::Missing {
+            name: "ENV_TEST_D__AUTOSCALE__INTERVAL".into()
+        }
+    );
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use deadpool::managed::{self, Metrics, PoolEvent, RecycleResult, TimeoutType, Timeouts};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    broken: AtomicBool,
+}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<()> {
+        if self.broken.load(Ordering::Relaxed) {
+            Err(().into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Created(usize),
+    Recycled(usize),
+    RecycleFailed(usize),
+    Dropped(usize),
+    Timeout,
+}
+
+#[tokio::test]
+async fn events() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let pool = {
+        let events = events.clone();
+        Pool::builder(Manager::default())
+            .max_size(1)
+            .on_event(move |event| {
+                let event = match *event {
+                    PoolEvent::ObjectCreated { id } => Event::Created(id),
+                    PoolEvent::ObjectRecycled { id, .. } => Event::Recycled(id),
+                    PoolEvent::RecycleFailed { id, .. } => Event::RecycleFailed(id),
+                    PoolEvent::ObjectDropped { id, .. } => Event::Dropped(id),
+                    PoolEvent::Timeout(TimeoutType::Wait) => Event::Timeout,
+                    event => panic!("unexpected event: {:?}", event),
+                };
+                events.lock().unwrap().push(event);
+            })
+            .build()
+            .unwrap()
+    };
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    pool.manager().broken.store(true, Ordering::Relaxed);
+    let obj = pool.get().await.unwrap();
+    let timeouts = Timeouts {
+        wait: Some(Duration::ZERO),
+        ..pool.timeouts()
+    };
+    assert!(pool.timeout_get(&timeouts).await.is_err());
+    drop(obj);
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec![
+            Event::Created(0),
+            Event::Recycled(0),
+            Event::RecycleFailed(0),
+            Event::Dropped(0),
+            Event::Created(1),
+            Event::Timeout,
+        ]
+    );
+}
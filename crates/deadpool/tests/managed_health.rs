@@ -0,0 +1,119 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use deadpool::managed::{self, HealthProbe, Metrics, PoolErrorKind, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    fail: AtomicBool,
+    hang: AtomicBool,
+}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        if self.hang.load(Ordering::Relaxed) {
+            std::future::pending::<()>().await;
+        }
+        if self.fail.load(Ordering::Relaxed) {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+#[tokio::test]
+async fn created_and_recycled() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .build()
+        .unwrap();
+
+    let health = pool.health(TIMEOUT).await;
+    assert_eq!(health.probe, HealthProbe::Created);
+    assert!(health.is_healthy());
+    assert_eq!(health.status.size, 1);
+    assert_eq!(health.status.available, 1);
+
+    let health = pool.health(TIMEOUT).await;
+    assert_eq!(health.probe, HealthProbe::Recycled);
+    assert_eq!(health.status.size, 1);
+    assert_eq!(pool.counters().recycled, 1);
+
+    // The probe doesn't count as a use of the object.
+    let obj = pool.get().await.unwrap();
+    assert_eq!(managed::Object::metrics(&obj).use_count, 1);
+}
+
+#[tokio::test]
+async fn busy() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .build()
+        .unwrap();
+    let _obj = pool.get().await.unwrap();
+
+    let health = pool.health(TIMEOUT).await;
+    assert_eq!(health.probe, HealthProbe::Busy);
+    assert!(health.is_healthy());
+    assert_eq!(pool.counters().created, 1);
+}
+
+#[tokio::test]
+async fn failed() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .build()
+        .unwrap();
+    pool.manager().fail.store(true, Ordering::Relaxed);
+
+    let health = pool.health(TIMEOUT).await;
+    assert_eq!(health.probe, HealthProbe::Failed(PoolErrorKind::Backend));
+    assert!(!health.is_healthy());
+    assert!(health.last_error.is_some());
+    assert_eq!(health.status.size, 0);
+}
+
+#[tokio::test]
+async fn closed() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .build()
+        .unwrap();
+    pool.close();
+
+    let health = pool.health(TIMEOUT).await;
+    assert_eq!(health.probe, HealthProbe::Closed);
+    assert!(!health.is_healthy());
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn timeout() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .runtime(deadpool::Runtime::Tokio1)
+        .build()
+        .unwrap();
+    pool.manager().hang.store(true, Ordering::Relaxed);
+
+    let timeout = Duration::from_millis(10);
+    let health = pool.health(timeout).await;
+    assert_eq!(health.probe, HealthProbe::Failed(PoolErrorKind::Timeout));
+    assert!(health.elapsed >= timeout);
+    assert_eq!(health.status.size, 0);
+}
@@ -1,8 +1,14 @@
 #![cfg(feature = "managed")]
 
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 
-use deadpool::managed::{Hook, HookError, Manager, Metrics, Pool, RecycleResult};
+use deadpool::managed::{
+    AcquireHook, Hook, HookError, HookStage, Manager, Metrics, Object, Pool, PoolError,
+    RecycleResult,
+};
 
 struct Computer {
     next_id: AtomicUsize,
@@ -81,6 +87,23 @@ async fn post_create_err_abort() {
     assert_eq!(*obj2, 4);
 }
 
+#[tokio::test]
+async fn post_create_discard() {
+    let manager = Computer::new(0);
+    let pool = Pool::<Computer>::builder(manager)
+        .max_size(3)
+        .post_create(Hook::sync_fn(|obj, _| {
+            (*obj % 2 == 0).then_some(()).ok_or(HookError::Discard)
+        }))
+        .build()
+        .unwrap();
+    let obj1 = pool.get().await.unwrap();
+    assert_eq!(*obj1, 0);
+    let obj2 = pool.get().await.unwrap();
+    assert_eq!(*obj2, 2);
+    assert_eq!(pool.status().size, 2);
+}
+
 #[tokio::test]
 async fn pre_recycle_ok() {
     let manager = Computer::new(42);
@@ -182,3 +205,173 @@ async fn post_recycle_err_continue() {
     assert_eq!(pool.status().available, 1);
     assert_eq!(pool.status().size, 1);
 }
+
+#[tokio::test]
+async fn on_acquire_ok() {
+    let manager = Computer::new(42);
+    let pool = Pool::<Computer>::builder(manager)
+        .max_size(1)
+        .on_acquire(AcquireHook::sync_fn(|obj, metrics| {
+            *obj += metrics.use_count;
+            Ok(())
+        }))
+        .build()
+        .unwrap();
+    assert_eq!(*pool.get().await.unwrap(), 43);
+    assert_eq!(*pool.get().await.unwrap(), 45);
+}
+
+#[tokio::test]
+async fn on_acquire_metrics() {
+    let manager = Computer::new(0);
+    let pool = Pool::<Computer>::builder(manager)
+        .max_size(1)
+        .on_acquire(AcquireHook::sync_fn(|_, metrics| {
+            metrics.recycle_count = 0;
+            Ok(())
+        }))
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    let obj = pool.get().await.unwrap();
+    assert_eq!(Object::metrics(&obj).use_count, 2);
+    assert_eq!(Object::metrics(&obj).recycle_count, 0);
+}
+
+#[tokio::test]
+async fn on_acquire_err_returned() {
+    let manager = Computer::new(0);
+    let pool = Pool::<Computer>::builder(manager)
+        .max_size(1)
+        .on_acquire(AcquireHook::sync_fn(|_, metrics| {
+            if metrics.use_count == 1 {
+                Err(HookError::message("Fail!"))
+            } else {
+                Ok(())
+            }
+        }))
+        .build()
+        .unwrap();
+    let Err(PoolError::OnAcquireHook(e)) = pool.get().await else {
+        panic!("expected an `on_acquire` hook error");
+    };
+    assert_eq!(e.context().unwrap().stage, HookStage::OnAcquire);
+    assert!(matches!(e.into_inner(), HookError::Message(_)));
+    // The object has been returned to the pool.
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(pool.status().available, 1);
+    assert_eq!(*pool.get().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn pre_return_err_discard() {
+    let manager = Computer::new(0);
+    let pool = Pool::<Computer>::builder(manager)
+        .max_size(1)
+        .pre_return(|obj, _| {
+            if *obj == 0 {
+                Err(HookError::message("Mid-transaction"))
+            } else {
+                Ok(())
+            }
+        })
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.status().size, 0);
+    assert_eq!(*pool.get().await.unwrap(), 1);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(*pool.get().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn on_detach() {
+    let detached = Arc::new(AtomicUsize::new(0));
+    let manager = Computer::new(0);
+    let pool = Pool::<Computer>::builder(manager)
+        .max_size(1)
+        .on_detach({
+            let detached = detached.clone();
+            move |obj, _| {
+                let _ = detached.fetch_add(*obj + 1, Ordering::Relaxed);
+            }
+        })
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    assert_eq!(detached.load(Ordering::Relaxed), 0);
+    let obj = Object::take(pool.get().await.unwrap());
+    assert_eq!(obj, 0);
+    assert_eq!(detached.load(Ordering::Relaxed), 1);
+    assert_eq!(pool.status().size, 0);
+}
+
+#[tokio::test]
+async fn pre_recycle_abort() {
+    let manager = Computer::new(0);
+    let pool = Pool::<Computer>::builder(manager)
+        .max_size(2)
+        .pre_recycle(Hook::sync_fn(|obj, _| {
+            if *obj == 0 {
+                Err(HookError::abort(HookError::message("Abort!")))
+            } else {
+                Ok(())
+            }
+        }))
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    let Err(PoolError::RecycleHook(e)) = pool.get().await else {
+        panic!("expected a recycle hook error");
+    };
+    let context = e.context().unwrap();
+    assert_eq!(context.stage, HookStage::PreRecycle);
+    assert_eq!(context.status.size, 1);
+    assert!(matches!(e.into_inner(), HookError::Message(_)));
+    assert_eq!(pool.status().size, 0);
+    assert_eq!(*pool.get().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn on_acquire_discard() {
+    let manager = Computer::new(0);
+    let pool = Pool::<Computer>::builder(manager)
+        .max_size(2)
+        .on_acquire(AcquireHook::sync_fn(|obj, _| {
+            (*obj % 2 == 1).then_some(()).ok_or(HookError::Discard)
+        }))
+        .build()
+        .unwrap();
+    // The discarded object is replaced by another one.
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 1);
+    assert_eq!(pool.status().size, 1);
+    let obj2 = pool.get().await.unwrap();
+    assert_eq!(*obj2, 3);
+    assert_eq!(pool.status().size, 2);
+    assert_eq!(pool.status().available, 0);
+}
+
+#[tokio::test]
+async fn on_acquire_abort() {
+    let manager = Computer::new(0);
+    let pool = Pool::<Computer>::builder(manager)
+        .max_size(1)
+        .on_acquire(AcquireHook::sync_fn(|_, metrics| {
+            if metrics.use_count == 1 {
+                Err(HookError::abort(HookError::message("Abort!")))
+            } else {
+                Ok(())
+            }
+        }))
+        .build()
+        .unwrap();
+    let Err(PoolError::OnAcquireHook(e)) = pool.get().await else {
+        panic!("expected an `on_acquire` hook error");
+    };
+    assert_eq!(e.context().unwrap().stage, HookStage::OnAcquire);
+    assert!(matches!(e.into_inner(), HookError::Message(_)));
+    // The object has been returned to the pool.
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(*pool.get().await.unwrap(), 0);
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "managed")]
+
+use std::convert::Infallible;
+
+use deadpool::managed::{
+    keyed::{self, KeyedManager},
+    Metrics, PoolError, RecycleResult, TimeoutError, TimeoutType, Timeouts,
+};
+
+type Pool = keyed::KeyedPool<&'static str, Manager>;
+
+struct Manager {}
+
+impl KeyedManager<&'static str> for Manager {
+    type Type = String;
+    type Error = Infallible;
+
+    async fn create(&self, key: &&'static str) -> Result<String, Infallible> {
+        Ok(key.to_string())
+    }
+
+    async fn recycle(
+        &self,
+        _key: &&'static str,
+        _conn: &mut String,
+        _: &Metrics,
+    ) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn basic() {
+    let pool = Pool::builder(Manager {}).max_size(2).build().unwrap();
+    let a = pool.get(&"a").await.unwrap();
+    let b = pool.get(&"b").await.unwrap();
+    assert_eq!(*a, "a");
+    assert_eq!(*b, "b");
+    assert_eq!(pool.len(), 2);
+    drop(a);
+    let status = pool.status(&"a").unwrap();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 1);
+    assert!(pool.status(&"c").is_none());
+}
+
+#[tokio::test]
+async fn max_size_per_key() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let _a = pool.get(&"a").await.unwrap();
+    let _b = pool.get(&"b").await.unwrap();
+    let timeouts = Timeouts::wait_millis(0);
+    let result = pool.pool(&"a").unwrap().timeout_get(&timeouts).await;
+    assert!(matches!(
+        result,
+        Err(PoolError::Timeout(TimeoutError {
+            timeout_type: TimeoutType::Wait,
+            ..
+        }))
+    ));
+}
+
+#[tokio::test]
+async fn remove_idle() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let a = pool.get(&"a").await.unwrap();
+    let b = pool.get(&"b").await.unwrap();
+    drop(b);
+    // Pools with objects are kept.
+    assert_eq!(pool.remove_idle(), 0);
+    let _ = deadpool::managed::Object::take(a);
+    assert_eq!(pool.remove_idle(), 1);
+    assert_eq!(pool.len(), 1);
+    assert!(pool.status(&"a").is_none());
+}
+
+#[tokio::test]
+async fn close() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let _a = pool.get(&"a").await.unwrap();
+    pool.close();
+    assert!(pool.is_closed());
+    assert!(pool.is_empty());
+    assert!(matches!(pool.get(&"a").await, Err(PoolError::Closed)));
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn idle_timeout() {
+    use std::time::Duration;
+
+    let pool = Pool::builder(Manager {})
+        .max_size(1)
+        .idle_timeout(Some(Duration::from_millis(10)))
+        .maintenance_interval(Some(Duration::from_millis(5)))
+        .runtime(deadpool::Runtime::Tokio1)
+        .build()
+        .unwrap();
+    drop(pool.get(&"a").await.unwrap());
+    assert_eq!(pool.len(), 1);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(pool.is_empty());
+}
@@ -0,0 +1,66 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    convert::Infallible,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use deadpool::{
+    managed::{self, Metrics, PoolEvent, RecycleResult},
+    Runtime,
+};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn lease_exceeded() {
+    let exceeded = Arc::new(Mutex::new(Vec::new()));
+    let pool = {
+        let exceeded = exceeded.clone();
+        Pool::builder(Manager {})
+            .max_size(2)
+            .lease_timeout(Some(Duration::from_millis(20)))
+            .runtime(Runtime::Tokio1)
+            .on_event(move |event| {
+                if let PoolEvent::LeaseExceeded { id, held } = *event {
+                    assert!(held >= Duration::from_millis(20));
+                    exceeded.lock().unwrap().push(id);
+                }
+            })
+            .build()
+            .unwrap()
+    };
+    let leaked = pool.get().await.unwrap();
+    // Objects returned in time are not reported.
+    drop(pool.get().await.unwrap());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(*exceeded.lock().unwrap(), [managed::Object::id(&leaked)]);
+    assert_eq!(pool.counters().leases_exceeded, 1);
+    drop(leaked);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(pool.counters().leases_exceeded, 1);
+}
+
+#[test]
+fn lease_timeout_requires_runtime() {
+    assert!(Pool::builder(Manager {})
+        .lease_timeout(Some(Duration::from_millis(20)))
+        .build()
+        .is_err());
+}
@@ -0,0 +1,276 @@
+#![cfg(feature = "managed")]
+
+#[cfg(feature = "rt_tokio_1")]
+use std::time::Duration;
+use std::{
+    convert::Infallible,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use deadpool::managed::{self, Metrics, Object, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    created: AtomicUsize,
+}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        Ok(self.created.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn recycle(&self, _conn: &mut usize, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test(start_paused = true)]
+async fn max_lifetime_checkout() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .max_lifetime(Some(Duration::from_millis(10)))
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 0);
+    drop(obj);
+    assert_eq!(*pool.get().await.unwrap(), 0);
+    tokio::time::advance(Duration::from_millis(20)).await;
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(*pool.get().await.unwrap(), 1);
+    assert_eq!(pool.status().size, 1);
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test(start_paused = true)]
+async fn max_lifetime_return() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .max_lifetime(Some(Duration::from_millis(10)))
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    tokio::time::advance(Duration::from_millis(20)).await;
+    drop(obj);
+    assert_eq!(pool.status().size, 0);
+    assert_eq!(pool.status().available, 0);
+    assert_eq!(*pool.get().await.unwrap(), 1);
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test(start_paused = true)]
+async fn idle_timeout_checkout() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .idle_timeout(Some(Duration::from_millis(10)))
+        // Keep the maintenance task from removing the expired object.
+        .maintenance_interval(Some(Duration::from_secs(60)))
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    // Holding the object longer than the idle timeout must not count
+    // as being idle.
+    tokio::time::advance(Duration::from_millis(20)).await;
+    drop(obj);
+    assert_eq!(*pool.get().await.unwrap(), 0);
+    tokio::time::advance(Duration::from_millis(20)).await;
+    assert_eq!(*pool.get().await.unwrap(), 1);
+    assert_eq!(pool.status().size, 1);
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test(start_paused = true)]
+async fn idle_timeout_return() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(2)
+        .queue_mode(managed::QueueMode::Lifo)
+        .idle_timeout(Some(Duration::from_millis(10)))
+        // Keep the maintenance task from removing the expired objects.
+        .maintenance_interval(Some(Duration::from_secs(60)))
+        .build()
+        .unwrap();
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    drop(obj0);
+    tokio::time::advance(Duration::from_millis(20)).await;
+    assert_eq!(pool.status().size, 2);
+    // Returning an object evicts the ones that have been idle for too long.
+    drop(obj1);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(pool.status().available, 1);
+    assert_eq!(*pool.get().await.unwrap(), 1);
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test(start_paused = true)]
+async fn idle_timeout_return_fifo() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(2)
+        .queue_mode(managed::QueueMode::Fifo)
+        .idle_timeout(Some(Duration::from_millis(10)))
+        // Keep the maintenance task from removing the expired objects.
+        .maintenance_interval(Some(Duration::from_secs(60)))
+        .build()
+        .unwrap();
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    drop(obj0);
+    tokio::time::advance(Duration::from_millis(20)).await;
+    // Expired objects are kept in place when returning an object so the
+    // order of the queue is preserved. They are discarded on checkout.
+    drop(obj1);
+    assert_eq!(pool.status().size, 2);
+    assert_eq!(*pool.get().await.unwrap(), 1);
+    assert_eq!(pool.status().size, 1);
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test(start_paused = true)]
+async fn idle_timeout_min_idle() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(3)
+        .min_idle(2)
+        .idle_timeout(Some(Duration::from_millis(10)))
+        .build()
+        .unwrap();
+    let objs = [
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+    ];
+    drop(objs);
+    assert_eq!(pool.status().size, 3);
+    // The maintenance task removes idle objects until `min_idle` is reached
+    // but doesn't replace the remaining ones.
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(pool.status().size, 2);
+    assert_eq!(pool.manager().created.load(Ordering::Relaxed), 3);
+}
+
+#[tokio::test]
+async fn max_uses() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .max_uses(Some(2))
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 0);
+    assert_eq!(managed::Object::metrics(&obj).use_count, 1);
+    drop(obj);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 0);
+    assert_eq!(managed::Object::metrics(&obj).use_count, 2);
+    drop(obj);
+    assert_eq!(pool.status().size, 0);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 1);
+    assert_eq!(managed::Object::metrics(&obj).use_count, 1);
+}
+
+#[tokio::test]
+async fn invalidate_all() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(2)
+        .build()
+        .unwrap();
+    let obj0 = pool.get().await.unwrap();
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.status().size, 2);
+    pool.invalidate_all();
+    // Objects in use are discarded when being returned
+    drop(obj0);
+    assert_eq!(pool.status().size, 1);
+    // Idle objects are discarded when being handed out
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 2);
+    assert_eq!(pool.status().size, 1);
+    drop(obj);
+    assert_eq!(*pool.get().await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn purge_idle() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(3)
+        .build()
+        .unwrap();
+    let obj0 = pool.get().await.unwrap();
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.status().size, 2);
+    assert_eq!(pool.purge_idle(), 1);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(pool.status().available, 0);
+    // Objects in use are not affected
+    drop(obj0);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(*pool.get().await.unwrap(), 0);
+    assert_eq!(pool.purge_idle(), 1);
+    assert_eq!(pool.purge_idle(), 0);
+    assert_eq!(*pool.get().await.unwrap(), 2);
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test(start_paused = true)]
+async fn pinned() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .max_lifetime(Some(Duration::from_millis(10)))
+        .max_uses(Some(1))
+        .build()
+        .unwrap();
+    let mut obj = pool.get().await.unwrap();
+    Object::pin(&mut obj);
+    assert!(Object::is_pinned(&obj));
+    tokio::time::advance(Duration::from_millis(20)).await;
+    drop(obj);
+    assert_eq!(pool.status().size, 1);
+    let mut obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 0);
+    Object::unpin(&mut obj);
+    drop(obj);
+    assert_eq!(pool.status().size, 0);
+    assert_eq!(*pool.get().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn pinned_invalidate_all() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .build()
+        .unwrap();
+    let mut obj = pool.get().await.unwrap();
+    Object::pin(&mut obj);
+    pool.invalidate_all();
+    drop(obj);
+    assert_eq!(*pool.get().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn replace() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .max_uses(Some(2))
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    let mut obj = pool.get().await.unwrap();
+    let id = Object::id(&obj);
+    assert_eq!(Object::metrics(&obj).use_count, 2);
+    assert_eq!(Object::replace(&mut obj).await.unwrap(), 0);
+    assert_eq!(*obj, 1);
+    assert_eq!(Object::id(&obj), id);
+    assert_eq!(Object::metrics(&obj).use_count, 1);
+    assert_eq!(pool.status().size, 1);
+    // The replaced object doesn't exceed the `max_uses` anymore
+    drop(obj);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 1);
+    assert_eq!(pool.status().size, 1);
+}
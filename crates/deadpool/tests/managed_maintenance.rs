@@ -0,0 +1,237 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    convert::Infallible,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use deadpool::{
+    managed::{self, AutoscaleConfig, BuildError, Metrics, RecycleResult},
+    Runtime,
+};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    dropped: Arc<AtomicBool>,
+}
+
+impl Drop for Manager {
+    fn drop(&mut self) {
+        self.dropped.store(true, Ordering::Relaxed);
+    }
+}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[test]
+fn requires_runtime() {
+    let result = Pool::builder(Manager::default())
+        .maintenance_interval(Some(Duration::from_millis(5)))
+        .build();
+    assert!(matches!(result, Err(BuildError::NoRuntimeSpecified)));
+}
+
+#[tokio::test]
+async fn removes_expired() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(2)
+        .max_lifetime(Some(Duration::from_millis(10)))
+        .maintenance_interval(Some(Duration::from_millis(5)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    drop(obj0);
+    drop(obj1);
+    assert_eq!(pool.status().size, 2);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(pool.status().size, 0);
+    assert_eq!(pool.status().available, 0);
+}
+
+#[cfg(feature = "rt_smol_2")]
+#[test]
+fn removes_expired_smol_2() {
+    smol::block_on(async {
+        let pool = Pool::builder(Manager::default())
+            .max_size(2)
+            .max_lifetime(Some(Duration::from_millis(10)))
+            .maintenance_interval(Some(Duration::from_millis(5)))
+            .runtime(Runtime::Smol2)
+            .build()
+            .unwrap();
+        drop(pool.get().await.unwrap());
+        assert_eq!(pool.status().size, 1);
+        smol::Timer::after(Duration::from_millis(50)).await;
+        assert_eq!(pool.status().size, 0);
+    });
+}
+
+#[tokio::test]
+async fn stops_when_dropped() {
+    let manager = Manager::default();
+    let dropped = manager.dropped.clone();
+    let pool = Pool::builder(manager)
+        .maintenance_interval(Some(Duration::from_millis(5)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert!(!dropped.load(Ordering::Relaxed));
+    drop(pool);
+    assert!(dropped.load(Ordering::Relaxed));
+}
+
+#[tokio::test]
+async fn min_idle() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(3)
+        .min_idle(2)
+        .maintenance_interval(Some(Duration::from_millis(5)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    assert_eq!(pool.status().size, 0);
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    assert_eq!(pool.status().size, 2);
+    assert_eq!(pool.status().available, 2);
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    // The `max_size` limits the number of idle objects created
+    assert_eq!(pool.status().size, 3);
+    assert_eq!(pool.status().available, 1);
+    drop(obj0);
+    drop(obj1);
+    assert_eq!(pool.status().size, 3);
+    assert_eq!(pool.status().available, 3);
+}
+
+#[tokio::test]
+async fn warm_up_on_create() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(3)
+        .warm_up_on_create(true)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert_eq!(pool.status().size, 3);
+    assert_eq!(pool.status().available, 3);
+}
+
+struct BreakingManager {}
+
+impl managed::Manager for BreakingManager {
+    type Type = bool;
+    type Error = ();
+
+    async fn create(&self) -> Result<bool, ()> {
+        Ok(true)
+    }
+
+    async fn recycle(&self, healthy: &mut bool, _: &Metrics) -> RecycleResult<()> {
+        if *healthy {
+            Ok(())
+        } else {
+            Err(managed::RecycleError::message("broken"))
+        }
+    }
+}
+
+#[tokio::test]
+async fn health_check() {
+    let pool = managed::Pool::<BreakingManager>::builder(BreakingManager {})
+        .max_size(3)
+        .health_check_interval(Some(Duration::from_millis(5)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let obj0 = pool.get().await.unwrap();
+    let mut obj1 = pool.get().await.unwrap();
+    let obj2 = pool.get().await.unwrap();
+    *obj1 = false;
+    drop(obj0);
+    drop(obj1);
+    drop(obj2);
+    assert_eq!(pool.status().size, 3);
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    assert_eq!(pool.status().size, 2);
+    assert_eq!(pool.status().available, 2);
+    // Healthy objects are not considered being used
+    assert_eq!(
+        managed::Object::metrics(&pool.get().await.unwrap()).recycle_count,
+        1
+    );
+}
+
+#[tokio::test]
+async fn autoscale() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .autoscale(Some(AutoscaleConfig::new(1, 3, Duration::from_millis(10))))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let obj0 = pool.get().await.unwrap();
+    let join_handle = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.get().await.unwrap() })
+    };
+    // The waiting task makes the autoscaler grow the pool
+    let obj1 = tokio::time::timeout(Duration::from_millis(100), join_handle)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(pool.status().max_size, 2);
+    assert_eq!(pool.status().size, 2);
+    drop(obj0);
+    drop(obj1);
+    // Less than half of the pool is in use which makes the autoscaler
+    // shrink the pool back to its lower bound.
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(pool.status().max_size, 1);
+    assert_eq!(pool.status().size, 1);
+}
+
+#[tokio::test]
+async fn autoscale_target_wait() {
+    let mut config = AutoscaleConfig::new(1, 3, Duration::from_millis(50));
+    config.target_wait = Some(Duration::from_secs(5));
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .autoscale(Some(config))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let obj0 = pool.get().await.unwrap();
+    let join_handle = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.get().await.unwrap() })
+    };
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    drop(obj0);
+    let _obj1 = join_handle.await.unwrap();
+    // The task waited for less than the target wait time which keeps the
+    // autoscaler from growing the pool.
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(pool.status().max_size, 1);
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    convert::Infallible,
+    sync::{Arc, Mutex},
+};
+
+use deadpool::managed::{self, MappedObject, Metrics, Object, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Debug)]
+struct Manager {}
+
+#[derive(Debug, Default)]
+struct Connection {
+    client: Client,
+    session: Option<Session>,
+}
+
+/// Handle which shares its state with all of its clones.
+#[derive(Clone, Debug, Default)]
+struct Client(Arc<Mutex<Vec<u8>>>);
+
+#[derive(Debug)]
+struct Session(String);
+
+impl managed::Manager for Manager {
+    type Type = Connection;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<Connection, Infallible> {
+        Ok(Connection::default())
+    }
+
+    async fn recycle(&self, _: &mut Connection, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn map() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let obj = pool.get().await.unwrap();
+    let id = Object::id(&obj);
+    let client = Object::map(obj, |conn| conn.client.clone());
+    client.0.lock().unwrap().push(42);
+    assert_eq!(Object::id(MappedObject::object(&client)), id);
+    assert_eq!(pool.status().available, 0);
+    drop(client);
+    assert_eq!(pool.status().available, 1);
+    assert_eq!(
+        *pool.get().await.unwrap().client.0.lock().unwrap(),
+        vec![42]
+    );
+}
+
+#[tokio::test]
+async fn map_captures() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let prefix = String::from("user:");
+    let mut key = Object::map(pool.get().await.unwrap(), |conn| {
+        format!("{}{}", prefix, conn.client.0.lock().unwrap().len())
+    });
+    key.push('!');
+    assert_eq!(&*key, "user:0!");
+}
+
+#[tokio::test]
+async fn try_map() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let obj = pool.get().await.unwrap();
+    let mut obj = Object::try_map(obj, |conn| conn.session.take()).unwrap_err();
+    obj.session = Some(Session("session".into()));
+    let mut session = Object::try_map(obj, |conn| conn.session.take()).unwrap();
+    session.0.make_ascii_uppercase();
+    assert_eq!(session.0, "SESSION");
+    let obj = MappedObject::into_object(session);
+    assert!(obj.session.is_none());
+}
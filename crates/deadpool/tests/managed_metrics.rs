@@ -0,0 +1,81 @@
+#![cfg(all(feature = "managed", feature = "metrics"))]
+
+use std::convert::Infallible;
+
+use deadpool::managed::{self, Metrics, RecycleResult};
+use metrics_util::{
+    debugging::{DebugValue, DebuggingRecorder},
+    CompositeKey, MetricKind,
+};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+fn gauge(snapshot: &[(CompositeKey, DebugValue)], name: &str) -> f64 {
+    snapshot
+        .iter()
+        .find_map(|(key, value)| match value {
+            DebugValue::Gauge(value) if key.key().name() == name => Some(value.into_inner()),
+            _ => None,
+        })
+        .unwrap()
+}
+
+#[tokio::test]
+async fn metrics() {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    let pool = metrics::with_local_recorder(&recorder, || {
+        Pool::builder(Manager {})
+            .name("test")
+            .label("env", "ci")
+            .max_size(2)
+            .build()
+            .unwrap()
+    });
+    assert_eq!(pool.labels(), [("env".to_owned(), "ci".to_owned())]);
+    let obj = pool.get().await.unwrap();
+
+    let snapshot = snapshotter.snapshot().into_vec();
+    let snapshot = snapshot
+        .into_iter()
+        .map(|(key, _, _, value)| (key, value))
+        .collect::<Vec<_>>();
+    assert!(snapshot.iter().all(|(key, _)| key
+        .key()
+        .labels()
+        .map(|label| (label.key(), label.value()))
+        .eq([("pool", "test"), ("env", "ci")])));
+    assert_eq!(gauge(&snapshot, "deadpool_max_size"), 2.0);
+    assert_eq!(gauge(&snapshot, "deadpool_size"), 1.0);
+    assert_eq!(gauge(&snapshot, "deadpool_available"), 0.0);
+    assert_eq!(gauge(&snapshot, "deadpool_waiting"), 0.0);
+    assert!(snapshot
+        .iter()
+        .any(|(key, value)| key.kind() == MetricKind::Histogram
+            && key.key().name() == "deadpool_wait_time_seconds"
+            && matches!(value, DebugValue::Histogram(values) if values.len() == 1)));
+
+    drop(obj);
+    let snapshot = snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .map(|(key, _, _, value)| (key, value))
+        .collect::<Vec<_>>();
+    assert_eq!(gauge(&snapshot, "deadpool_available"), 1.0);
+}
@@ -0,0 +1,105 @@
+#![cfg(all(feature = "managed", feature = "opentelemetry"))]
+
+use std::{convert::Infallible, time::Duration};
+
+use deadpool::managed::{self, Metrics, RecycleResult, Timeouts};
+use opentelemetry::metrics::MeterProvider as _;
+use opentelemetry_sdk::metrics::{
+    data::{AggregatedMetrics, MetricData},
+    InMemoryMetricExporter, PeriodicReader, SdkMeterProvider,
+};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+/// Returns the values of the given instrument as `(state, value)` pairs.
+fn values(exporter: &InMemoryMetricExporter, instrument: &str) -> Vec<(String, i64)> {
+    let metrics = exporter.get_finished_metrics().unwrap();
+    let metric = metrics
+        .last()
+        .unwrap()
+        .scope_metrics()
+        .flat_map(|scope| scope.metrics())
+        .find(|metric| metric.name() == instrument)
+        .unwrap();
+    let mut values = match metric.data() {
+        AggregatedMetrics::I64(MetricData::Sum(sum)) => sum
+            .data_points()
+            .map(|point| {
+                assert!(point
+                    .attributes()
+                    .any(|kv| kv.key.as_str() == "pool.name" && kv.value.as_str() == "db"));
+                let state = point
+                    .attributes()
+                    .find(|kv| kv.key.as_str() == "state")
+                    .map(|kv| kv.value.to_string())
+                    .unwrap_or_default();
+                (state, point.value())
+            })
+            .collect::<Vec<_>>(),
+        AggregatedMetrics::U64(MetricData::Sum(sum)) => sum
+            .data_points()
+            .map(|point| (String::new(), point.value() as i64))
+            .collect(),
+        data => panic!("unexpected data: {:?}", data),
+    };
+    values.sort();
+    values
+}
+
+#[tokio::test]
+async fn instruments() {
+    let exporter = InMemoryMetricExporter::default();
+    let provider = SdkMeterProvider::builder()
+        .with_reader(PeriodicReader::builder(exporter.clone()).build())
+        .build();
+    let pool = Pool::builder(Manager {})
+        .name("db")
+        .max_size(2)
+        .build()
+        .unwrap();
+    managed::opentelemetry::register(&provider.meter("test"), &pool);
+
+    let obj1 = pool.get().await.unwrap();
+    let obj2 = pool.get().await.unwrap();
+    drop(obj2);
+    let timeouts = Timeouts {
+        wait: Some(Duration::ZERO),
+        ..pool.timeouts()
+    };
+    let _obj2 = pool.timeout_get(&timeouts).await.unwrap();
+    assert!(pool.timeout_get(&timeouts).await.is_err());
+    drop(obj1);
+
+    provider.force_flush().unwrap();
+    assert_eq!(
+        values(&exporter, "db.client.connections.usage"),
+        [("idle".to_owned(), 1), ("used".to_owned(), 1)]
+    );
+    assert_eq!(
+        values(&exporter, "db.client.connections.max"),
+        [(String::new(), 2)]
+    );
+    assert_eq!(
+        values(&exporter, "db.client.connections.pending_requests"),
+        [(String::new(), 0)]
+    );
+    assert_eq!(
+        values(&exporter, "db.client.connections.timeouts"),
+        [(String::new(), 1)]
+    );
+}
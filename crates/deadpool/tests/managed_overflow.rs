@@ -0,0 +1,97 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    convert::Infallible,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use deadpool::managed::{
+    self, Metrics, PoolError, RecycleResult, TimeoutError, TimeoutType, Timeouts,
+};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    created: AtomicUsize,
+}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        Ok(self.created.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn recycle(&self, _conn: &mut usize, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn overflow() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .max_overflow(1)
+        .build()
+        .unwrap();
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    assert_eq!(*obj0, 0);
+    assert_eq!(*obj1, 1);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(pool.status().waiting, 0);
+    assert!(matches!(
+        pool.timeout_get(&Timeouts::wait_millis(0)).await,
+        Err(PoolError::Timeout(TimeoutError {
+            timeout_type: TimeoutType::Wait,
+            ..
+        }))
+    ));
+    // Overflow objects are dropped when being returned
+    drop(obj1);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(pool.status().available, 0);
+    assert_eq!(*pool.get().await.unwrap(), 2);
+    drop(obj0);
+    assert_eq!(pool.status().available, 1);
+    assert_eq!(*pool.get().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn overflow_take() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .max_overflow(1)
+        .build()
+        .unwrap();
+    let _obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    assert_eq!(managed::Object::take(obj1), 1);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(*pool.get().await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn overflow_waiting() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .max_overflow(1)
+        .build()
+        .unwrap();
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    let waiter = tokio::spawn({
+        let pool = pool.clone();
+        async move { *pool.get().await.unwrap() }
+    });
+    while pool.status().waiting == 0 {
+        tokio::task::yield_now().await;
+    }
+    // The waiting task must not be overtaken by an overflow object
+    drop(obj1);
+    assert!(pool.try_get().await.unwrap().is_none());
+    drop(obj0);
+    assert_eq!(waiter.await.unwrap(), 0);
+}
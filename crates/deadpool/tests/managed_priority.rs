@@ -0,0 +1,98 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    convert::Infallible,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use deadpool::managed::{self, Metrics, PoolError, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn priority_order() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let obj = pool.get().await.unwrap();
+    let order = Arc::new(Mutex::new(Vec::new()));
+    let priorities = [None, Some(1), Some(5), Some(1), None];
+    let join_handles = priorities
+        .into_iter()
+        .enumerate()
+        .map(|(i, priority)| {
+            let pool = pool.clone();
+            let order = order.clone();
+            tokio::spawn(async move {
+                let _obj = match priority {
+                    Some(priority) => pool.get_with_priority(priority).await.unwrap(),
+                    None => pool.get().await.unwrap(),
+                };
+                order.lock().unwrap().push(i);
+                tokio::task::yield_now().await;
+            })
+        })
+        .collect::<Vec<_>>();
+    // Make sure all tasks are waiting in the order they were spawned
+    for _ in 0..priorities.len() {
+        tokio::task::yield_now().await;
+    }
+    assert_eq!(pool.status().waiting, priorities.len());
+    drop(obj);
+    for join_handle in join_handles {
+        join_handle.await.unwrap();
+    }
+    assert_eq!(*order.lock().unwrap(), vec![2, 1, 3, 0, 4]);
+}
+
+#[tokio::test]
+async fn priority_closed() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let _obj = pool.get().await.unwrap();
+    let join_handle = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.get_with_priority(1).await })
+    };
+    tokio::task::yield_now().await;
+    pool.close();
+    assert!(matches!(join_handle.await.unwrap(), Err(PoolError::Closed)));
+}
+
+#[tokio::test]
+async fn priority_cancelled() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let obj = pool.get().await.unwrap();
+    let join_handle = {
+        let pool = pool.clone();
+        tokio::spawn(async move {
+            let _ = pool.get_with_priority(1).await;
+        })
+    };
+    tokio::task::yield_now().await;
+    // The object is handed to the waiting task which is cancelled before
+    // it gets a chance to take it.
+    drop(obj);
+    join_handle.abort();
+    assert!(join_handle.await.unwrap_err().is_cancelled());
+    let obj = tokio::time::timeout(Duration::from_millis(100), pool.get())
+        .await
+        .expect("the permit of the cancelled task was lost")
+        .unwrap();
+    drop(obj);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(pool.status().available, 1);
+}
@@ -0,0 +1,78 @@
+#![cfg(all(feature = "managed", feature = "prometheus"))]
+
+use std::convert::Infallible;
+
+use deadpool::managed::{self, prometheus::Collector, Metrics, RecycleResult};
+use prometheus::Registry;
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+fn gauge(registry: &Registry, name: &str, pool: &str) -> Option<i64> {
+    registry
+        .gather()
+        .iter()
+        .filter(|family| family.name() == name)
+        .flat_map(|family| family.get_metric())
+        .find(|metric| metric.get_label()[0].value() == pool)
+        .map(|metric| metric.get_gauge().get_value() as i64)
+}
+
+#[tokio::test]
+async fn collector() {
+    let a = Pool::builder(Manager {})
+        .name("a")
+        .label("env", "test")
+        .max_size(2)
+        .build()
+        .unwrap();
+    let b = Pool::builder(Manager {})
+        .name("b")
+        .max_size(3)
+        .build()
+        .unwrap();
+    let collector = Collector::new();
+    collector.add(&a);
+    collector.add(&b);
+    let registry = Registry::new();
+    registry.register(Box::new(collector)).unwrap();
+
+    let _obj = a.get().await.unwrap();
+    assert_eq!(gauge(&registry, "deadpool_max_size", "a"), Some(2));
+    assert_eq!(gauge(&registry, "deadpool_size", "a"), Some(1));
+    assert_eq!(gauge(&registry, "deadpool_available", "a"), Some(0));
+    assert_eq!(gauge(&registry, "deadpool_max_size", "b"), Some(3));
+    assert_eq!(gauge(&registry, "deadpool_size", "b"), Some(0));
+    let labels = registry
+        .gather()
+        .iter()
+        .flat_map(|family| family.get_metric())
+        .map(|metric| {
+            metric
+                .get_label()
+                .iter()
+                .map(|label| format!("{}={}", label.name(), label.value()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert!(labels.contains(&vec!["pool=a".to_owned(), "env=test".to_owned()]));
+    assert!(labels.contains(&vec!["pool=b".to_owned()]));
+
+    drop(b);
+    assert_eq!(gauge(&registry, "deadpool_max_size", "a"), Some(2));
+    assert_eq!(gauge(&registry, "deadpool_max_size", "b"), None);
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "managed")]
+
+use std::convert::Infallible;
+
+use deadpool::managed::{self, Metrics, Object, QueueMode, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+async fn create_pool(queue_mode: QueueMode) -> Pool {
+    let pool = Pool::builder(Manager {})
+        .max_size(3)
+        .queue_mode(queue_mode)
+        .build()
+        .unwrap();
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    let obj2 = pool.get().await.unwrap();
+    assert_eq!(Object::id(&obj0), 0);
+    assert_eq!(Object::id(&obj1), 1);
+    assert_eq!(Object::id(&obj2), 2);
+    drop(obj0);
+    drop(obj1);
+    drop(obj2);
+    pool
+}
+
+#[tokio::test]
+async fn fifo() {
+    let pool = create_pool(QueueMode::Fifo).await;
+    let obj = pool.get().await.unwrap();
+    assert_eq!(Object::id(&obj), 0);
+    drop(obj);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(Object::id(&obj), 1);
+}
+
+#[tokio::test]
+async fn lifo() {
+    let pool = create_pool(QueueMode::Lifo).await;
+    let obj = pool.get().await.unwrap();
+    assert_eq!(Object::id(&obj), 2);
+    drop(obj);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(Object::id(&obj), 2);
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn fifo_idle_timeout() {
+    use std::time::Duration;
+
+    let pool = Pool::builder(Manager {})
+        .max_size(3)
+        .queue_mode(QueueMode::Fifo)
+        .idle_timeout(Some(Duration::from_secs(60)))
+        .build()
+        .unwrap();
+    let objs = [
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+    ];
+    // Returning objects must not change the order in which they are handed
+    // out again.
+    drop(objs);
+    let objs = [
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+    ];
+    let ids: Vec<_> = objs.iter().map(Object::id).collect();
+    assert_eq!(ids, [0, 1, 2]);
+}
+
+#[tokio::test]
+async fn lifo_retain() {
+    let pool = create_pool(QueueMode::Lifo).await;
+    // Objects are visited from the one which has been idle the longest.
+    let mut visited = 0;
+    let removed = pool
+        .retain(|_, _| {
+            visited += 1;
+            visited != 2
+        })
+        .removed;
+    assert_eq!(removed.len(), 1);
+    assert_eq!(pool.status().size, 2);
+    let obj2 = pool.get().await.unwrap();
+    let obj0 = pool.get().await.unwrap();
+    assert_eq!(Object::id(&obj2), 2);
+    assert_eq!(Object::id(&obj0), 0);
+}
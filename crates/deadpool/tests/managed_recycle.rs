@@ -0,0 +1,132 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use deadpool::{
+    managed::{self, BuildError, Metrics, RecycleError, RecycleResult},
+    Runtime,
+};
+
+type Pool = managed::Pool<Manager>;
+type Object = managed::Object<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    recycled: AtomicUsize,
+    broken: AtomicBool,
+}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+        let _ = self.recycled.fetch_add(1, Ordering::Relaxed);
+        if self.broken.load(Ordering::Relaxed) {
+            return Err(RecycleError::Backend(()));
+        }
+        Ok(())
+    }
+}
+
+fn pool() -> Pool {
+    Pool::builder(Manager::default())
+        .max_size(1)
+        .recycle_on_return(true)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn requires_runtime() {
+    let result = Pool::builder(Manager::default())
+        .recycle_on_return(true)
+        .build();
+    assert!(matches!(result, Err(BuildError::NoRuntimeSpecified)));
+}
+
+#[tokio::test]
+async fn recycled_on_return() {
+    let pool = pool();
+    let recycled = || pool.manager().recycled.load(Ordering::Relaxed);
+    drop(pool.get().await.unwrap());
+    let obj = pool.get().await.unwrap();
+    assert_eq!(recycled(), 1);
+    assert_eq!(Object::metrics(&obj).recycle_count, 1);
+    drop(obj);
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert_eq!(recycled(), 2);
+    assert_eq!(pool.status().available, 1);
+}
+
+#[tokio::test]
+async fn waits_for_recycling() {
+    let pool = pool();
+    drop(pool.get().await.unwrap());
+    // The slot only becomes available once the object has been recycled.
+    assert_eq!(pool.status().available, 0);
+    let _obj = pool.get().await.unwrap();
+    assert_eq!(pool.manager().recycled.load(Ordering::Relaxed), 1);
+    assert_eq!(pool.status().size, 1);
+}
+
+#[tokio::test]
+async fn broken_on_return() {
+    let pool = pool();
+    pool.manager().broken.store(true, Ordering::Relaxed);
+    let obj = pool.get().await.unwrap();
+    let id = Object::id(&obj);
+    drop(obj);
+    let obj = pool.get().await.unwrap();
+    assert_ne!(Object::id(&obj), id);
+    assert_eq!(pool.status().size, 1);
+}
+
+mod recycle_ahead {
+    use std::{sync::atomic::Ordering, time::Duration};
+
+    use deadpool::{managed::BuildError, Runtime};
+
+    use super::{Manager, Pool};
+
+    fn pool() -> Pool {
+        Pool::builder(Manager::default())
+            .max_size(2)
+            .recycle_ahead(1)
+            .runtime(Runtime::Tokio1)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn requires_runtime() {
+        let result = Pool::builder(Manager::default()).recycle_ahead(1).build();
+        assert!(matches!(result, Err(BuildError::NoRuntimeSpecified)));
+    }
+
+    #[tokio::test]
+    async fn refills_buffer() {
+        let pool = pool();
+        let recycled = || pool.manager().recycled.load(Ordering::Relaxed);
+        assert_eq!(pool.warm_up(2).await.unwrap(), 2);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(recycled(), 1);
+        // The object from the buffer is handed out without recycling it.
+        let _obj = pool.get().await.unwrap();
+        assert_eq!(recycled(), 1);
+        // The buffer is refilled with the other idle object.
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(recycled(), 2);
+        let _obj = pool.get().await.unwrap();
+        assert_eq!(recycled(), 2);
+        assert_eq!(pool.status().size, 2);
+    }
+}
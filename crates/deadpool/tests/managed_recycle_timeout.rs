@@ -0,0 +1,77 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use deadpool::{
+    managed::{self, Metrics, PoolConfig, RecycleResult, RecycleTimeoutMode},
+    Runtime,
+};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    created: AtomicUsize,
+}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        Ok(self.created.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<()> {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        Ok(())
+    }
+}
+
+fn pool(mode: RecycleTimeoutMode) -> Pool {
+    Pool::builder(Manager::default())
+        .max_size(1)
+        .recycle_timeout(Some(Duration::from_millis(10)))
+        .recycle_timeout_mode(mode)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn default_mode() {
+    assert_eq!(
+        PoolConfig::default().recycle_timeout_mode,
+        RecycleTimeoutMode::Discard
+    );
+}
+
+#[tokio::test]
+async fn discard() {
+    let pool = pool(RecycleTimeoutMode::Discard);
+    drop(pool.get().await.unwrap());
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 1);
+    let counters = pool.counters();
+    assert_eq!(counters.recycle_timeouts, 1);
+    assert_eq!(counters.recycle_failed, 1);
+    assert_eq!(counters.recycle_timeouts_returned, 0);
+    assert_eq!(counters.created, 2);
+}
+
+#[tokio::test]
+async fn return_anyway() {
+    let pool = pool(RecycleTimeoutMode::Return);
+    drop(pool.get().await.unwrap());
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 0);
+    let counters = pool.counters();
+    assert_eq!(counters.recycle_timeouts, 1);
+    assert_eq!(counters.recycle_failed, 0);
+    assert_eq!(counters.recycle_timeouts_returned, 1);
+    assert_eq!(counters.recycled, 0);
+    assert_eq!(counters.created, 1);
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "registry")]
+
+use deadpool::{
+    managed::{self, Metrics, RecycleResult},
+    registry,
+};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager;
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn register_on_build() {
+    let pool = Pool::builder(Manager)
+        .max_size(2)
+        .register("registry_build")
+        .build()
+        .unwrap();
+    let _obj = pool.get().await.unwrap();
+    let registered = registry::get("registry_build").unwrap();
+    assert_eq!(registered.status().size, 1);
+    registered.resize(5);
+    assert_eq!(pool.status().max_size, 5);
+    registered.close();
+    assert!(pool.is_closed());
+}
+
+#[test]
+fn weak_reference() {
+    let pool = Pool::builder(Manager).build().unwrap();
+    registry::register("registry_weak", &pool);
+    assert!(registry::get("registry_weak").is_some());
+    drop(pool);
+    assert!(registry::get("registry_weak").is_none());
+    assert!(registry::pools()
+        .iter()
+        .all(|(name, _)| name != "registry_weak"));
+}
+
+#[test]
+fn pools() {
+    let a = Pool::builder(Manager).name("a").build().unwrap();
+    let b = Pool::builder(Manager).name("b").build().unwrap();
+    registry::register("registry_pools_a", &a);
+    registry::register("registry_pools_b", &b);
+    let names: Vec<_> = registry::pools()
+        .into_iter()
+        .filter(|(name, _)| name.starts_with("registry_pools_"))
+        .map(|(name, pool)| (name, pool.name().map(String::from)))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            ("registry_pools_a".to_string(), Some("a".to_string())),
+            ("registry_pools_b".to_string(), Some("b".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn unregister() {
+    let pool = Pool::builder(Manager).build().unwrap();
+    registry::register("registry_unregister", &pool);
+    assert!(registry::unregister("registry_unregister"));
+    assert!(registry::get("registry_unregister").is_none());
+    assert!(!registry::unregister("registry_unregister"));
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "managed")]
+
+use std::convert::Infallible;
+
+use deadpool::managed::{self, Metrics, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {
+    endpoint: &'static str,
+}
+
+impl managed::Manager for Manager {
+    type Type = &'static str;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<&'static str, Infallible> {
+        Ok(self.endpoint)
+    }
+
+    async fn recycle(&self, _: &mut &'static str, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn replace_manager() {
+    let pool = Pool::builder(Manager { endpoint: "a" })
+        .max_size(2)
+        .build()
+        .unwrap();
+    let in_use = pool.get().await.unwrap();
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.status().size, 2);
+
+    let old = pool.replace_manager(Manager { endpoint: "b" });
+    assert_eq!(old.endpoint, "a");
+    assert_eq!(pool.manager().endpoint, "b");
+
+    // The idle object is discarded on checkout
+    assert_eq!(*pool.get().await.unwrap(), "b");
+    // The object which was in use is discarded when returned
+    drop(in_use);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(*pool.get().await.unwrap(), "b");
+}
+
+#[test]
+fn builder_manager() {
+    let builder = Pool::builder(Manager { endpoint: "a" });
+    assert_eq!(builder.manager().endpoint, "a");
+    let pool = builder.max_size(1).build().unwrap();
+    assert_eq!(pool.manager().endpoint, "a");
+}
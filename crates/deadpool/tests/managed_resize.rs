@@ -1,6 +1,11 @@
 #![cfg(feature = "managed")]
 
-use std::convert::Infallible;
+use std::{
+    convert::Infallible,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use tokio::sync::Semaphore;
 
 use deadpool::managed::{self, Metrics, Object, RecycleResult};
 
@@ -68,6 +73,28 @@ async fn resize_pool_shrink() {
     assert_eq!(pool.status().size, 1);
 }
 
+#[tokio::test]
+async fn resize_pool_shrink_idle() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(3).build().unwrap();
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    let obj2 = pool.get().await.unwrap();
+    drop(obj0);
+    drop(obj1);
+    drop(obj2);
+    assert_eq!(pool.status().size, 3);
+    assert_eq!(pool.status().available, 3);
+    pool.resize(1);
+    // Surplus idle objects are dropped right away
+    assert_eq!(pool.status().max_size, 1);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(pool.status().available, 1);
+    let _obj0 = pool.get().await.unwrap();
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(pool.status().available, 0);
+}
+
 #[tokio::test]
 async fn resize_pool_grow() {
     let mgr = Manager {};
@@ -102,10 +129,26 @@ async fn resize_pool_shrink_grow() {
     assert_eq!(pool.status().size, 1);
 }
 
+#[tokio::test]
+async fn resize_pool_shrink_unused() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(3).build().unwrap();
+    let obj0 = pool.get().await.unwrap();
+    pool.resize(1);
+    assert!(pool
+        .timeout_get(&managed::Timeouts::wait_millis(0))
+        .await
+        .is_err());
+    drop(obj0);
+    let _obj0 = pool.get().await.unwrap();
+    assert_eq!(pool.status().size, 1);
+}
+
 #[tokio::test]
 async fn resize_pool_grow_concurrent() {
     let mgr = Manager {};
-    let pool = Pool::builder(mgr).max_size(0).build().unwrap();
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+    pool.resize(0);
     let join_handle = {
         let pool = pool.clone();
         tokio::spawn(async move { pool.get().await })
@@ -146,3 +189,82 @@ async fn close_resize() {
     assert_eq!(pool.status().size, 0);
     assert_eq!(pool.status().max_size, 0);
 }
+
+#[tokio::test]
+async fn resize_pool_shrink_grow_in_use() {
+    let pool = Pool::builder(Manager {}).max_size(2).build().unwrap();
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    pool.resize(1);
+    pool.resize(2);
+    drop(obj0);
+    drop(obj1);
+    let _obj0 = pool.get().await.unwrap();
+    let _obj1 = pool.get().await.unwrap();
+    assert!(pool.try_get().await.unwrap().is_none());
+    assert_eq!(pool.status().size, 2);
+}
+
+/// Manager whose `create` waits until it is allowed to proceed.
+struct GatedManager {
+    gate: Semaphore,
+    fail: AtomicBool,
+}
+
+impl managed::Manager for GatedManager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        self.gate.acquire().await.unwrap().forget();
+        if self.fail.swap(false, Ordering::Relaxed) {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn resize_pool_shrink_while_creating() {
+    let manager = GatedManager {
+        gate: Semaphore::new(0),
+        fail: AtomicBool::new(false),
+    };
+    let pool = managed::Pool::<GatedManager>::builder(manager)
+        .max_size(2)
+        .build()
+        .unwrap();
+    let creating: Vec<_> = (0..2)
+        .map(|_| {
+            let pool = pool.clone();
+            tokio::spawn(async move { pool.get().await })
+        })
+        .collect();
+    while pool.status().creating < 2 {
+        tokio::task::yield_now().await;
+    }
+    pool.resize(1);
+    let manager = pool.manager();
+    manager.fail.store(true, Ordering::Relaxed);
+    manager.gate.add_permits(2);
+    let objs: Vec<_> = join_all(creating).await;
+    assert_eq!(objs.iter().filter(|obj| obj.is_err()).count(), 1);
+    drop(objs);
+    // The permit of the failed creation must not have been handed back.
+    manager.gate.add_permits(2);
+    let _obj = pool.get().await.unwrap();
+    assert!(pool.try_get().await.unwrap().is_none());
+    assert_eq!(pool.status().max_size, 1);
+}
+
+async fn join_all<T>(handles: Vec<tokio::task::JoinHandle<T>>) -> Vec<T> {
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        results.push(handle.await.unwrap());
+    }
+    results
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "managed")]
+
+use std::sync::Mutex;
+
+use deadpool::managed::{self, Metrics, PoolErrorKind, RecycleResult, Retryable};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Debug)]
+enum Error {
+    Transient,
+    Permanent,
+}
+
+impl Retryable for Error {
+    fn is_retryable(&self) -> bool {
+        matches!(self, Self::Transient)
+    }
+}
+
+struct Manager {
+    errors: Mutex<Vec<Error>>,
+}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Error;
+
+    async fn create(&self) -> Result<(), Error> {
+        match self.errors.lock().unwrap().pop() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<Error> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn retryable() {
+    let manager = Manager {
+        errors: Mutex::new(vec![Error::Permanent, Error::Transient]),
+    };
+    let pool = Pool::builder(manager).max_size(1).build().unwrap();
+
+    let Err(e) = pool.get().await else {
+        panic!("expected an error");
+    };
+    assert_eq!(e.kind(), PoolErrorKind::Backend);
+    assert!(e.is_retryable());
+
+    let Err(e) = pool.get().await else {
+        panic!("expected an error");
+    };
+    assert_eq!(e.kind(), PoolErrorKind::Backend);
+    assert!(!e.is_retryable());
+
+    drop(pool.get().await.unwrap());
+    pool.close();
+    let Err(e) = pool.get().await else {
+        panic!("expected an error");
+    };
+    assert_eq!(e.kind(), PoolErrorKind::Closed);
+    assert!(!e.is_retryable());
+}
@@ -0,0 +1,50 @@
+#![cfg(all(feature = "managed", feature = "serde"))]
+
+use std::convert::Infallible;
+
+use deadpool::managed::{self, Metrics, Object, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn serialize() {
+    let pool = Pool::builder(Manager {}).max_size(2).build().unwrap();
+    let obj = pool.get().await.unwrap();
+
+    let status = serde_json::to_value(pool.status()).unwrap();
+    assert_eq!(
+        status,
+        serde_json::json!({
+            "max_size": 2,
+            "size": 1,
+            "available": 0,
+            "waiting": 0,
+            "creating": 0,
+        })
+    );
+
+    let counters = serde_json::to_value(pool.counters()).unwrap();
+    assert_eq!(counters["created"], 1);
+    assert_eq!(counters["recycled"], 0);
+
+    let metrics = serde_json::to_value(Object::metrics(&obj)).unwrap();
+    assert_eq!(metrics["use_count"], 1);
+    assert_eq!(metrics["recycle_count"], 0);
+    assert!(metrics["age"]["secs"].is_u64());
+    assert!(metrics["last_used"]["nanos"].is_u64());
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    convert::Infallible,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use deadpool::managed::{self, Metrics, Object, RecycleResult, SharedObject};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    created: AtomicUsize,
+}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        Ok(self.created.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn recycle(&self, _conn: &mut usize, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn shared() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(2)
+        .max_holders(2)
+        .build()
+        .unwrap();
+    let obj0 = pool.get_shared().await.unwrap();
+    let obj1 = pool.get_shared().await.unwrap();
+    let obj2 = pool.get_shared().await.unwrap();
+    assert_eq!(*obj0, 0);
+    assert_eq!(*obj1, 0);
+    assert_eq!(*obj2, 1);
+    assert_eq!(SharedObject::holders(&obj0), 2);
+    assert_eq!(pool.status().size, 2);
+    assert_eq!(pool.status().available, 0);
+    // The object with the fewest holders is shared first.
+    drop(obj0);
+    let obj3 = pool.get_shared().await.unwrap();
+    let obj4 = pool.get_shared().await.unwrap();
+    assert_eq!(Object::id(SharedObject::object(&obj3)), 0);
+    assert_eq!(Object::id(SharedObject::object(&obj4)), 1);
+}
+
+#[tokio::test]
+async fn returned_by_last_holder() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .max_holders(4)
+        .build()
+        .unwrap();
+    let obj0 = pool.get_shared().await.unwrap();
+    let obj1 = obj0.clone();
+    drop(obj0);
+    assert_eq!(pool.status().available, 0);
+    drop(obj1);
+    assert_eq!(pool.status().available, 1);
+    // The object is no longer shared and can be retrieved exclusively.
+    assert_eq!(*pool.get().await.unwrap(), 0);
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "managed")]
+
+use std::{convert::Infallible, time::Duration};
+
+use deadpool::managed::{self, Metrics, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        Ok(())
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn stats() {
+    let pool = Pool::builder(Manager {})
+        .max_size(1)
+        .histogram_buckets([Duration::from_millis(100), Duration::from_millis(10)])
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    let stats = pool.stats();
+
+    let create_time = stats.create_time.buckets().collect::<Vec<_>>();
+    assert_eq!(
+        create_time,
+        vec![
+            (Some(Duration::from_millis(10)), 0),
+            (Some(Duration::from_millis(100)), 1),
+            (None, 0),
+        ]
+    );
+    assert_eq!(
+        stats.create_time.quantile(0.99),
+        Some(Duration::from_millis(100))
+    );
+
+    assert_eq!(stats.wait_time.count(), 2);
+    assert_eq!(
+        stats.wait_time.quantile(0.5),
+        Some(Duration::from_millis(10))
+    );
+    assert_eq!(
+        stats.wait_time.quantile(1.0),
+        Some(Duration::from_millis(100))
+    );
+}
+
+#[tokio::test]
+async fn rates() {
+    let pool = Pool::builder(Manager {})
+        .max_size(1)
+        .stats_window(Duration::from_millis(1500))
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    let rates = pool.stats().rates;
+    assert_eq!(rates.window, Duration::from_secs(2));
+    assert!(rates.gets > 0.0);
+    assert_eq!(rates.gets, rates.creates * 2.0);
+    assert_eq!(rates.timeouts, 0.0);
+}
+
+#[tokio::test]
+async fn empty() {
+    let pool = Pool::builder(Manager {}).build().unwrap();
+    let stats = pool.stats();
+    assert_eq!(stats.wait_time.count(), 0);
+    assert_eq!(stats.wait_time.quantile(0.99), None);
+    assert_eq!(stats.rates.window, managed::DEFAULT_STATS_WINDOW);
+    assert_eq!(stats.rates.gets, 0.0);
+}
@@ -0,0 +1,89 @@
+#![cfg(all(feature = "test-utils", feature = "rt_tokio_1"))]
+
+use std::time::{Duration, Instant};
+
+use deadpool::{
+    managed::{self, Object, PoolError, TimeoutError, TimeoutType},
+    test_utils::{MockError, MockManager, Step},
+    Runtime,
+};
+
+type Pool = managed::Pool<MockManager>;
+
+fn pool(manager: MockManager) -> Pool {
+    Pool::builder(manager)
+        .max_size(1)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn create_script() {
+    let manager = MockManager::new();
+    manager.push_create(Step::err());
+    manager.push_create(Step::ok());
+    manager.set_create_default(Step::err());
+    let pool = pool(manager);
+    assert!(matches!(
+        pool.get().await,
+        Err(PoolError::Backend(MockError))
+    ));
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 0);
+    let _ = Object::take(obj);
+    assert!(pool.get().await.is_err());
+    let manager = pool.manager();
+    assert_eq!(manager.create_calls(), 3);
+    assert_eq!(manager.created(), 1);
+    assert_eq!(manager.detached(), 1);
+}
+
+#[tokio::test]
+async fn recycle_script() {
+    let manager = MockManager::new();
+    manager.push_recycle(Step::err());
+    let pool = pool(manager);
+    drop(pool.get().await.unwrap());
+    // Recycling the first object fails so a new one is created.
+    assert_eq!(*pool.get().await.unwrap(), 1);
+    assert_eq!(*pool.get().await.unwrap(), 1);
+    let manager = pool.manager();
+    assert_eq!(manager.recycle_calls(), 2);
+    assert_eq!(manager.recycled(), 1);
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert_eq!(manager.destroyed(), 1);
+}
+
+#[tokio::test]
+async fn create_timeout() {
+    let manager = MockManager::new();
+    manager.push_create(Step::pending());
+    manager.push_create(Step::ok().delay(Duration::from_secs(10)));
+    let pool = Pool::builder(manager)
+        .max_size(1)
+        .create_timeout(Some(Duration::from_millis(10)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    for _ in 0..2 {
+        assert!(matches!(
+            pool.get().await,
+            Err(PoolError::Timeout(TimeoutError {
+                timeout_type: TimeoutType::Create,
+                ..
+            }))
+        ));
+    }
+    assert!(pool.get().await.is_ok());
+}
+
+#[tokio::test]
+async fn delay() {
+    let manager = MockManager::new().runtime(Runtime::Tokio1);
+    manager.push_create(Step::ok().delay(Duration::from_millis(20)));
+    let pool = pool(manager);
+    let start = Instant::now();
+    drop(pool.get().await.unwrap());
+    assert!(start.elapsed() >= Duration::from_millis(20));
+}
@@ -1,13 +1,20 @@
-#![cfg(all(
-    feature = "managed",
-    any(feature = "rt_tokio_1", feature = "rt_async-std_1")
-))]
+#![cfg(feature = "managed")]
 
-use std::{convert::Infallible, future::Future, pin::Pin, task, time::Duration};
+use std::{
+    convert::Infallible,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task,
+    time::{Duration, Instant},
+};
 
 use deadpool::{
-    managed::{self, Metrics, Object, PoolConfig, PoolError, RecycleResult, Timeouts},
-    Runtime,
+    managed::{
+        self, Metrics, Object, PoolConfig, PoolError, RecycleResult, TimeoutError, TimeoutType,
+        Timeouts,
+    },
+    BoxFuture, CustomRuntime, Runtime,
 };
 
 type Pool = managed::Pool<Manager, Object<Manager>>;
@@ -45,7 +52,7 @@ async fn test_managed_timeout(runtime: Runtime) {
         max_size: 16,
         timeouts: Timeouts {
             create: Some(Duration::from_millis(0)),
-            wait: Some(Duration::from_millis(0)),
+            wait: None,
             recycle: Some(Duration::from_millis(0)),
         },
         ..Default::default()
@@ -59,14 +66,165 @@ async fn test_managed_timeout(runtime: Runtime) {
     assert!(matches!(pool.get().await, Err(PoolError::Timeout(_))));
 }
 
+async fn test_managed_timeout_override(runtime: Runtime) {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(16)
+        .runtime(runtime)
+        .build()
+        .unwrap();
+
+    let timeouts = Timeouts {
+        create: Some(Duration::from_millis(0)),
+        ..pool.timeouts()
+    };
+    assert!(matches!(
+        pool.timeout_get(&timeouts).await,
+        Err(PoolError::Timeout(TimeoutError {
+            timeout_type: TimeoutType::Create,
+            ..
+        }))
+    ));
+}
+
+async fn test_managed_timeout_details(runtime: Runtime) {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(2)
+        .create_timeout(Some(Duration::from_millis(10)))
+        .runtime(runtime)
+        .build()
+        .unwrap();
+
+    let Err(PoolError::Timeout(e)) = pool.get().await else {
+        panic!("expected a timeout");
+    };
+    assert!(matches!(e.timeout_type, TimeoutType::Create));
+    assert_eq!(e.timeout, Duration::from_millis(10));
+    assert!(e.elapsed >= Duration::from_millis(10));
+    assert_eq!(e.status.max_size, 2);
+    assert_eq!(e.status.size, 0);
+    assert_eq!(e.status.waiting, 0);
+}
+
+async fn test_managed_on_timeout(runtime: Runtime) {
+    let timeouts = Arc::new(Mutex::new(Vec::new()));
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(1)
+        .create_timeout(Some(Duration::from_millis(10)))
+        .on_timeout({
+            let timeouts = timeouts.clone();
+            move |e| timeouts.lock().unwrap().push(*e)
+        })
+        .runtime(runtime)
+        .build()
+        .unwrap();
+
+    assert!(pool.get().await.is_err());
+    let timeouts = timeouts.lock().unwrap();
+    assert_eq!(timeouts.len(), 1);
+    assert!(matches!(timeouts[0].timeout_type, TimeoutType::Create));
+    assert_eq!(timeouts[0].status.max_size, 1);
+}
+
+async fn test_managed_deadline(runtime: Runtime) {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(16)
+        .runtime(runtime)
+        .build()
+        .unwrap();
+
+    let deadline = Instant::now() + Duration::from_millis(10);
+    assert!(matches!(
+        pool.get_deadline(deadline).await,
+        Err(PoolError::Timeout(TimeoutError {
+            timeout_type: TimeoutType::Create,
+            ..
+        }))
+    ));
+    assert!(Instant::now() >= deadline);
+    assert!(matches!(
+        pool.get_deadline(Instant::now()).await,
+        Err(PoolError::Timeout(TimeoutError {
+            timeout_type: TimeoutType::Create,
+            ..
+        }))
+    ));
+}
+
 #[cfg(feature = "rt_tokio_1")]
 #[tokio::test]
 async fn rt_tokio_1() {
     test_managed_timeout(Runtime::Tokio1).await;
+    test_managed_timeout_override(Runtime::Tokio1).await;
+    test_managed_timeout_details(Runtime::Tokio1).await;
+    test_managed_on_timeout(Runtime::Tokio1).await;
+    test_managed_deadline(Runtime::Tokio1).await;
 }
 
 #[cfg(feature = "rt_async-std_1")]
 #[async_std::test]
 async fn rt_async_std_1() {
     test_managed_timeout(Runtime::AsyncStd1).await;
+    test_managed_timeout_override(Runtime::AsyncStd1).await;
+    test_managed_timeout_details(Runtime::AsyncStd1).await;
+    test_managed_on_timeout(Runtime::AsyncStd1).await;
+    test_managed_deadline(Runtime::AsyncStd1).await;
+}
+
+#[cfg(feature = "rt_smol_2")]
+#[test]
+fn rt_smol_2() {
+    smol::block_on(async {
+        test_managed_timeout(Runtime::Smol2).await;
+        test_managed_timeout_override(Runtime::Smol2).await;
+        test_managed_timeout_details(Runtime::Smol2).await;
+        test_managed_on_timeout(Runtime::Smol2).await;
+        test_managed_deadline(Runtime::Smol2).await;
+    });
+}
+
+#[derive(Debug)]
+struct TokioRuntime;
+
+impl CustomRuntime for TokioRuntime {
+    fn sleep(&self, duration: Duration) -> BoxFuture<()> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+
+    fn spawn(&self, future: BoxFuture<()>) {
+        drop(tokio::spawn(future));
+    }
+}
+
+static TOKIO_RUNTIME: TokioRuntime = TokioRuntime;
+
+#[tokio::test]
+async fn rt_custom() {
+    let runtime = Runtime::Custom(&TOKIO_RUNTIME);
+    test_managed_timeout(runtime).await;
+    test_managed_timeout_override(runtime).await;
+    test_managed_timeout_details(runtime).await;
+    test_managed_on_timeout(runtime).await;
+    test_managed_deadline(runtime).await;
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn rt_tokio_1_detected() {
+    // No runtime is specified but the Tokio runtime is detected.
+    let pool = managed::Pool::<Manager, Object<Manager>>::builder(Manager {})
+        .max_size(1)
+        .create_timeout(Some(Duration::from_millis(10)))
+        .build()
+        .unwrap();
+    assert!(matches!(
+        pool.get().await,
+        Err(PoolError::Timeout(TimeoutError {
+            timeout_type: TimeoutType::Create,
+            ..
+        }))
+    ));
 }
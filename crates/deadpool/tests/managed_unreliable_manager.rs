@@ -1,13 +1,16 @@
 #![cfg(feature = "managed")]
 
 use std::{
-    sync::atomic::{AtomicUsize, Ordering},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
     time::Duration,
 };
 
 use tokio::time;
 
-use deadpool::managed::{self, Metrics, RecycleError, RecycleResult};
+use deadpool::managed::{self, ErrorStage, Metrics, PoolErrorKind, RecycleError, RecycleResult};
 
 type Pool = managed::Pool<Manager>;
 
@@ -98,3 +101,262 @@ async fn recycle() {
     assert_eq!(status.available, 1);
     assert_eq!(status.size, 1);
 }
+
+#[tokio::test]
+async fn on_create_error() {
+    let manager = Manager {
+        create_fail: true,
+        recycle_fail: false,
+        detached: AtomicUsize::new(0),
+    };
+    let errors = Arc::new(AtomicUsize::new(0));
+    let pool = Pool::builder(manager)
+        .max_size(1)
+        .on_create_error({
+            let errors = errors.clone();
+            move |_| {
+                errors.fetch_add(1, Ordering::Relaxed);
+            }
+        })
+        .build()
+        .unwrap();
+    assert!(pool.get().await.is_err());
+    assert!(pool.get().await.is_err());
+    assert_eq!(errors.load(Ordering::Relaxed), 2);
+}
+
+#[tokio::test]
+async fn on_recycle_error() {
+    let manager = Manager {
+        create_fail: false,
+        recycle_fail: true,
+        detached: AtomicUsize::new(0),
+    };
+    let errors = Arc::new(AtomicUsize::new(0));
+    let pool = Pool::builder(manager)
+        .max_size(1)
+        .on_recycle_error({
+            let errors = errors.clone();
+            move |e, metrics| {
+                assert!(matches!(e, RecycleError::Backend(())));
+                assert_eq!(metrics.use_count, 1);
+                errors.fetch_add(1, Ordering::Relaxed);
+            }
+        })
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    assert_eq!(errors.load(Ordering::Relaxed), 0);
+    drop(pool.get().await.unwrap());
+    assert_eq!(errors.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test]
+async fn last_error() {
+    let manager = Manager {
+        create_fail: false,
+        recycle_fail: true,
+        detached: AtomicUsize::new(0),
+    };
+    let pool = Pool::builder(manager)
+        .max_size(1)
+        .describe_error(|_| "backend failed".into())
+        .build()
+        .unwrap();
+    assert!(pool.last_error().is_none());
+    drop(pool.get().await.unwrap());
+    assert!(pool.last_error().is_none());
+    // The failed recycle isn't reported to the caller.
+    drop(pool.get().await.unwrap());
+    let e = pool.last_error().unwrap();
+    assert_eq!(e.stage, ErrorStage::Recycle);
+    assert_eq!(e.kind, PoolErrorKind::Backend);
+    assert_eq!(e.message.as_deref(), Some("backend failed"));
+
+    let manager = Manager {
+        create_fail: true,
+        recycle_fail: false,
+        detached: AtomicUsize::new(0),
+    };
+    let pool = Pool::builder(manager).max_size(1).build().unwrap();
+    assert!(pool.get().await.is_err());
+    let e = pool.last_error().unwrap();
+    assert_eq!(e.stage, ErrorStage::Create);
+    assert_eq!(e.kind, PoolErrorKind::Backend);
+    assert!(e.message.is_none());
+}
+
+#[cfg(feature = "rt_tokio_1")]
+mod create_retry {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use deadpool::{
+        managed::{self, Metrics, PoolError, RecycleResult, RetryConfig},
+        Runtime,
+    };
+
+    type Pool = managed::Pool<FlakyManager>;
+
+    /// Manager failing to create the first `failures` objects.
+    struct FlakyManager {
+        failures: usize,
+        attempts: AtomicUsize,
+    }
+
+    impl managed::Manager for FlakyManager {
+        type Type = ();
+        type Error = ();
+
+        async fn create(&self) -> Result<(), ()> {
+            if self.attempts.fetch_add(1, Ordering::Relaxed) < self.failures {
+                Err(())
+            } else {
+                Ok(())
+            }
+        }
+
+        async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+            Ok(())
+        }
+    }
+
+    fn pool(failures: usize, max_attempts: usize) -> Pool {
+        let manager = FlakyManager {
+            failures,
+            attempts: AtomicUsize::new(0),
+        };
+        Pool::builder(manager)
+            .max_size(1)
+            .create_retry(Some(RetryConfig::new(
+                max_attempts,
+                Duration::from_millis(1),
+            )))
+            .runtime(Runtime::Tokio1)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn succeeds() {
+        let pool = pool(2, 3);
+        assert!(pool.get().await.is_ok());
+        assert_eq!(pool.manager().attempts.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up() {
+        let pool = pool(2, 2);
+        assert!(matches!(pool.get().await, Err(PoolError::Backend(()))));
+        assert_eq!(pool.manager().attempts.load(Ordering::Relaxed), 2);
+        assert_eq!(pool.status().size, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn max_delay() {
+        let mut retry = RetryConfig::new(100, Duration::from_millis(1));
+        retry.max_delay = Duration::from_millis(10);
+        retry.jitter = false;
+        let manager = FlakyManager {
+            failures: usize::MAX,
+            attempts: AtomicUsize::new(0),
+        };
+        let pool = Pool::builder(manager)
+            .max_size(1)
+            .create_retry(Some(retry))
+            .runtime(Runtime::Tokio1)
+            .build()
+            .unwrap();
+        let start = tokio::time::Instant::now();
+        assert!(matches!(pool.get().await, Err(PoolError::Backend(()))));
+        assert_eq!(pool.manager().attempts.load(Ordering::Relaxed), 100);
+        // The delay stops doubling once it reaches the `max_delay` instead
+        // of overflowing for large retry counts.
+        assert!(start.elapsed() <= Duration::from_millis(99 * 10));
+    }
+}
+
+mod circuit_breaker {
+    use std::{
+        sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use deadpool::managed::{self, CircuitBreakerConfig, Metrics, PoolError, RecycleResult};
+
+    type Pool = managed::Pool<DownManager>;
+
+    /// Manager failing to create objects while the backend is down.
+    struct DownManager {
+        down: AtomicBool,
+        attempts: AtomicUsize,
+    }
+
+    impl managed::Manager for DownManager {
+        type Type = ();
+        type Error = ();
+
+        async fn create(&self) -> Result<(), ()> {
+            let _ = self.attempts.fetch_add(1, Ordering::Relaxed);
+            if self.down.load(Ordering::Relaxed) {
+                Err(())
+            } else {
+                Ok(())
+            }
+        }
+
+        async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+            Ok(())
+        }
+    }
+
+    fn pool() -> Pool {
+        let manager = DownManager {
+            down: AtomicBool::new(true),
+            attempts: AtomicUsize::new(0),
+        };
+        Pool::builder(manager)
+            .max_size(1)
+            .circuit_breaker(Some(CircuitBreakerConfig::new(
+                2,
+                Duration::from_millis(50),
+            )))
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn opens_and_closes() {
+        let pool = pool();
+        let attempts = || pool.manager().attempts.load(Ordering::Relaxed);
+        assert!(matches!(pool.get().await, Err(PoolError::Backend(()))));
+        assert!(matches!(pool.get().await, Err(PoolError::Backend(()))));
+        assert_eq!(attempts(), 2);
+        // The breaker is open now and doesn't call the manager.
+        assert!(matches!(pool.get().await, Err(PoolError::CircuitOpen)));
+        assert_eq!(attempts(), 2);
+        pool.manager().down.store(false, Ordering::Relaxed);
+        assert!(matches!(pool.get().await, Err(PoolError::CircuitOpen)));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        // The probe succeeds and closes the breaker.
+        drop(pool.get().await.unwrap());
+        assert_eq!(attempts(), 3);
+        assert_eq!(pool.status().size, 1);
+    }
+
+    #[tokio::test]
+    async fn failed_probe() {
+        let pool = pool();
+        let attempts = || pool.manager().attempts.load(Ordering::Relaxed);
+        assert!(pool.get().await.is_err());
+        assert!(pool.get().await.is_err());
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(matches!(pool.get().await, Err(PoolError::Backend(()))));
+        assert_eq!(attempts(), 3);
+        // A failed probe opens the breaker again right away.
+        assert!(matches!(pool.get().await, Err(PoolError::CircuitOpen)));
+        assert_eq!(attempts(), 3);
+    }
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "managed")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use deadpool::managed::{self, Metrics, RecycleResult, Validation};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {
+    validation: Validation,
+    recycled: AtomicUsize,
+}
+
+impl Manager {
+    fn new(validation: Validation) -> Self {
+        Self {
+            validation,
+            recycled: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        Ok(0)
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<()> {
+        let _ = self.recycled.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn validate(&self, obj: &mut usize, _: &Metrics) -> Validation {
+        *obj += 1;
+        self.validation
+    }
+}
+
+#[tokio::test]
+async fn valid_skips_recycle() {
+    let pool = Pool::builder(Manager::new(Validation::Valid))
+        .max_size(1)
+        .build()
+        .unwrap();
+    assert_eq!(*pool.get().await.unwrap(), 0);
+    assert_eq!(*pool.get().await.unwrap(), 1);
+    assert_eq!(*pool.get().await.unwrap(), 2);
+    assert_eq!(pool.manager().recycled.load(Ordering::Relaxed), 0);
+}
+
+#[tokio::test]
+async fn invalid_discards() {
+    let pool = Pool::builder(Manager::new(Validation::Invalid))
+        .max_size(1)
+        .build()
+        .unwrap();
+    assert_eq!(*pool.get().await.unwrap(), 0);
+    assert_eq!(*pool.get().await.unwrap(), 0);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(pool.manager().recycled.load(Ordering::Relaxed), 0);
+}
+
+#[tokio::test]
+async fn recycle() {
+    let pool = Pool::builder(Manager::new(Validation::Recycle))
+        .max_size(1)
+        .build()
+        .unwrap();
+    assert_eq!(*pool.get().await.unwrap(), 0);
+    assert_eq!(*pool.get().await.unwrap(), 1);
+    assert_eq!(pool.manager().recycled.load(Ordering::Relaxed), 1);
+}
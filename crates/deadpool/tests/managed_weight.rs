@@ -0,0 +1,108 @@
+#![cfg(feature = "managed")]
+
+use std::{collections::VecDeque, convert::Infallible, sync::Mutex};
+
+use deadpool::managed::{
+    self, Metrics, PoolError, RecycleResult, TimeoutError, TimeoutType, Timeouts,
+};
+
+type Pool = managed::Pool<Manager>;
+
+/// Manager creating objects with the given weights. The objects are their
+/// own weight.
+struct Manager {
+    weights: Mutex<VecDeque<usize>>,
+}
+
+impl Manager {
+    fn new(weights: impl IntoIterator<Item = usize>) -> Self {
+        Self {
+            weights: Mutex::new(weights.into_iter().collect()),
+        }
+    }
+}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        Ok(self.weights.lock().unwrap().pop_front().unwrap_or(1))
+    }
+
+    async fn recycle(&self, _conn: &mut usize, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+
+    fn weight(&self, obj: &usize) -> usize {
+        *obj
+    }
+}
+
+#[tokio::test]
+async fn weighted() {
+    let pool = Pool::builder(Manager::new([2, 1]))
+        .max_size(3)
+        .build()
+        .unwrap();
+    let heavy = pool.get().await.unwrap();
+    let light = pool.get().await.unwrap();
+    assert_eq!(*heavy, 2);
+    assert_eq!(*light, 1);
+    // All units of the pool are in use.
+    let result = pool.timeout_get(&Timeouts::wait_millis(0)).await;
+    assert!(matches!(
+        result,
+        Err(PoolError::Timeout(TimeoutError {
+            timeout_type: TimeoutType::Wait,
+            ..
+        }))
+    ));
+    drop(heavy);
+    let heavy = pool.get().await.unwrap();
+    assert_eq!(*heavy, 2);
+    drop(light);
+    drop(heavy);
+    assert_eq!(pool.status().size, 2);
+    assert_eq!(pool.status().available, 2);
+}
+
+#[tokio::test]
+async fn waits_for_all_permits() {
+    let pool = Pool::builder(Manager::new([1, 2]))
+        .max_size(2)
+        .build()
+        .unwrap();
+    let light = pool.get().await.unwrap();
+    let join_handle = {
+        let pool = pool.clone();
+        tokio::spawn(async move { *pool.get().await.unwrap() })
+    };
+    // The heavy object has been created but can't be handed out while the
+    // light one is in use.
+    tokio::task::yield_now().await;
+    assert_eq!(pool.status().size, 2);
+    assert!(!join_handle.is_finished());
+    drop(light);
+    assert_eq!(join_handle.await.unwrap(), 2);
+    assert_eq!(pool.status().size, 2);
+    assert_eq!(pool.status().available, 2);
+}
+
+#[tokio::test]
+async fn limited_to_max_size() {
+    let pool = Pool::builder(Manager::new([5]))
+        .max_size(2)
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 5);
+    let result = pool.timeout_get(&Timeouts::wait_millis(0)).await;
+    assert!(matches!(
+        result,
+        Err(PoolError::Timeout(TimeoutError {
+            timeout_type: TimeoutType::Wait,
+            ..
+        }))
+    ));
+}
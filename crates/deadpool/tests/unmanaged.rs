@@ -1,10 +1,16 @@
 #![cfg(feature = "unmanaged")]
 
-use std::time::Duration;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use tokio::{task, time};
 
-use deadpool::unmanaged::{Pool, PoolError};
+use deadpool::unmanaged::{GrowthPolicy, Object, Pool, PoolConfig, PoolError, QueueMode};
 
 #[tokio::test]
 async fn basic() {
@@ -177,3 +183,358 @@ async fn add_timeout() {
 
     assert_eq!(pool.try_remove().unwrap(), 2);
 }
+
+#[tokio::test]
+async fn waiting() {
+    let pool = Pool::from(vec![()]);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(pool.status().waiting, 0);
+
+    let get = {
+        let pool = pool.clone();
+        tokio::spawn(async move { drop(pool.get().await.unwrap()) })
+    };
+    task::yield_now().await;
+    let status = pool.status();
+    assert_eq!(status.waiting, 1);
+    assert_eq!(status.available, 0);
+
+    let timeout = time::timeout(Duration::from_millis(10), pool.get()).await;
+    assert!(timeout.is_err());
+    assert_eq!(pool.status().waiting, 1);
+
+    drop(obj);
+    get.await.unwrap();
+    let status = pool.status();
+    assert_eq!(status.waiting, 0);
+    assert_eq!(status.available, 1);
+}
+
+#[tokio::test]
+async fn metrics() {
+    let pool = Pool::new(1);
+    pool.try_add(42).unwrap();
+
+    let obj = pool.get().await.unwrap();
+    let metrics = *Object::metrics(&obj);
+    assert_eq!(metrics.use_count, 1);
+    assert!(metrics.returned.is_none());
+    drop(obj);
+
+    let obj = pool.get().await.unwrap();
+    let metrics = *Object::metrics(&obj);
+    assert_eq!(metrics.use_count, 2);
+    assert!(metrics.returned.unwrap() >= metrics.added);
+    assert!(metrics.last_used() <= metrics.age());
+
+    // Taking an object out of the pool drops its metrics.
+    pool.try_add(Object::take(obj)).unwrap();
+    let obj = pool.get().await.unwrap();
+    assert_eq!(Object::metrics(&obj).use_count, 1);
+}
+
+#[tokio::test]
+async fn queue_mode() {
+    assert_eq!(PoolConfig::default().queue_mode, QueueMode::Lifo);
+    for (queue_mode, expected) in [(QueueMode::Fifo, [1, 2, 3]), (QueueMode::Lifo, [3, 2, 1])] {
+        let pool = Pool::from_config(&PoolConfig {
+            queue_mode,
+            ..PoolConfig::new(3)
+        });
+        for i in 1..=3 {
+            pool.try_add(i).unwrap();
+        }
+        let objs = [
+            pool.get().await.unwrap(),
+            pool.get().await.unwrap(),
+            pool.get().await.unwrap(),
+        ];
+        assert_eq!(objs.map(|obj| *obj), expected);
+    }
+}
+
+#[tokio::test]
+async fn remove_where() {
+    let pool = Pool::new(6);
+    for i in 1..=6 {
+        pool.try_add(i).unwrap();
+    }
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 6);
+
+    let mut removed = pool.remove_where(|i| i % 2 == 0);
+    removed.sort_unstable();
+    assert_eq!(removed, [2, 4]);
+    let status = pool.status();
+    assert_eq!(status.size, 4);
+    assert_eq!(status.available, 3);
+
+    // The removed objects free up their capacity.
+    pool.try_add(7).unwrap();
+    pool.try_add(8).unwrap();
+    assert!(pool.try_add(9).is_err());
+
+    // Objects in use are left alone.
+    assert!(pool.remove_where(|&i| i == 6).is_empty());
+    drop(obj);
+    assert_eq!(pool.remove_where(|&i| i == 6), [6]);
+
+    let mut remaining = pool.remove_where(|_| true);
+    remaining.sort_unstable();
+    assert_eq!(remaining, [1, 3, 5, 7, 8]);
+    let status = pool.status();
+    assert_eq!(status.size, 0);
+    assert_eq!(status.available, 0);
+    assert!(pool.try_get().is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn remove_where_concurrent() {
+    let pool = Pool::new(4);
+    for i in 0..4 {
+        pool.try_add(i).unwrap();
+    }
+    let tasks = (0..8)
+        .map(|_| {
+            let pool = pool.clone();
+            tokio::spawn(async move {
+                for _ in 0..100 {
+                    if let Ok(obj) = pool.try_get() {
+                        task::yield_now().await;
+                        drop(obj);
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut removed = Vec::new();
+    while removed.len() < 4 {
+        removed.extend(pool.remove_where(|_| true));
+        task::yield_now().await;
+    }
+    for task in tasks {
+        task.await.unwrap();
+    }
+    removed.sort_unstable();
+    assert_eq!(removed, [0, 1, 2, 3]);
+    assert_eq!(pool.status().size, 0);
+}
+
+#[tokio::test]
+async fn factory() {
+    let created = Arc::new(AtomicUsize::new(0));
+    let pool = {
+        let created = created.clone();
+        Pool::with_factory(&PoolConfig::new(2), move || {
+            let created = created.clone();
+            async move { created.fetch_add(1, Ordering::Relaxed) }
+        })
+    };
+    assert!(pool.try_get().is_err());
+
+    let obj0 = pool.get().await.unwrap();
+    assert_eq!(*obj0, 0);
+    assert_eq!(Object::metrics(&obj0).use_count, 1);
+    drop(obj0);
+
+    // Idle objects are used before creating new ones.
+    let obj0 = pool.get().await.unwrap();
+    assert_eq!(*obj0, 0);
+    let obj1 = pool.get().await.unwrap();
+    assert_eq!(*obj1, 1);
+    assert_eq!(pool.status().size, 2);
+
+    // No objects are created beyond the maximum size.
+    let get = time::timeout(Duration::from_millis(10), pool.get()).await;
+    assert!(get.is_err());
+    assert_eq!(created.load(Ordering::Relaxed), 2);
+
+    // Objects taken from the pool free up a slot for the factory.
+    let _ = Object::take(obj1);
+    assert_eq!(*pool.get().await.unwrap(), 2);
+    drop(obj0);
+}
+
+#[tokio::test]
+async fn factory_cancelled() {
+    let pool = Pool::with_factory(&PoolConfig::new(1), std::future::pending::<()>);
+    let get = time::timeout(Duration::from_millis(10), pool.get()).await;
+    assert!(get.is_err());
+    let status = pool.status();
+    assert_eq!(status.size, 0);
+    assert_eq!(status.creating, 0);
+    // The slot reserved by the cancelled factory call is free again.
+    pool.try_add(()).unwrap();
+    assert!(pool.try_get().is_ok());
+}
+
+#[tokio::test]
+async fn resize_grow() {
+    let pool = Pool::from(vec![1, 2]);
+    assert!(pool.try_add(3).is_err());
+    pool.resize(3);
+    assert_eq!(pool.status().max_size, 3);
+    pool.try_add(3).unwrap();
+    assert!(pool.try_add(4).is_err());
+    assert_eq!(pool.status().size, 3);
+}
+
+#[tokio::test]
+async fn resize_shrink_idle() {
+    let pool = Pool::new(4);
+    for i in 1..=4 {
+        pool.try_add(i).unwrap();
+    }
+    pool.resize(2);
+    let status = pool.status();
+    assert_eq!(status.max_size, 2);
+    assert_eq!(status.size, 2);
+    assert_eq!(status.available, 2);
+    // The least recently added objects are dropped first.
+    let mut remaining = pool.remove_where(|_| true);
+    remaining.sort_unstable();
+    assert_eq!(remaining, [3, 4]);
+}
+
+#[tokio::test]
+async fn resize_shrink_in_use() {
+    let pool = Pool::new(3);
+    for i in 1..=3 {
+        pool.try_add(i).unwrap();
+    }
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    pool.resize(1);
+    assert_eq!(pool.status().size, 2);
+
+    // Returned objects are dropped while the pool is too large.
+    drop(obj0);
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 0);
+    drop(obj1);
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 1);
+    assert!(pool.try_add(4).is_err());
+}
+
+#[tokio::test]
+async fn resize_shrink_grow() {
+    let pool = Pool::new(2);
+    pool.try_add(1).unwrap();
+    pool.try_add(2).unwrap();
+    let obj = Object::take(pool.get().await.unwrap());
+    let obj1 = pool.get().await.unwrap();
+    // Both slots are revoked, the free one right away.
+    pool.resize(0);
+    pool.resize(1);
+    assert!(pool.try_add(obj).is_err());
+    // Taking the object in use pays off the remaining revoked slot.
+    let _ = Object::take(obj1);
+    pool.try_add(3).unwrap();
+    assert!(pool.try_add(4).is_err());
+    assert_eq!(pool.status().size, 1);
+}
+
+#[tokio::test]
+async fn resize_factory() {
+    let pool = Pool::with_factory(&PoolConfig::new(0), || async { 42 });
+    assert!(time::timeout(Duration::from_millis(10), pool.get())
+        .await
+        .is_err());
+    pool.resize(1);
+    assert_eq!(*pool.get().await.unwrap(), 42);
+    assert_eq!(pool.status().size, 1);
+}
+
+#[tokio::test]
+async fn visit_idle() {
+    let pool = Pool::from(vec![1, 2, 3]);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 3);
+    drop(pool.get().await.unwrap());
+
+    let mut visited = Vec::new();
+    pool.visit_idle(|obj, metrics| visited.push((*obj, metrics.use_count)));
+    assert_eq!(visited, [(1, 0), (2, 1)]);
+    assert_eq!(pool.status().available, 2);
+    drop(obj);
+}
+
+#[tokio::test]
+async fn try_add_with_reject() {
+    let pool = Pool::from(vec![1, 2]);
+    assert!(matches!(
+        pool.try_add_with(3, GrowthPolicy::Reject),
+        Err((3, PoolError::Timeout))
+    ));
+    assert_eq!(pool.status().size, 2);
+}
+
+#[tokio::test]
+async fn try_add_with_grow() {
+    let pool = Pool::from(vec![1, 2]);
+    assert!(matches!(pool.try_add_with(3, GrowthPolicy::Grow), Ok(None)));
+    let status = pool.status();
+    assert_eq!(status.max_size, 3);
+    assert_eq!(status.size, 3);
+    assert_eq!(status.available, 3);
+    assert!(pool.try_add(4).is_err());
+}
+
+#[tokio::test]
+async fn try_add_with_replace_oldest() {
+    let pool = Pool::from(vec![1, 2]);
+    assert!(matches!(
+        pool.try_add_with(3, GrowthPolicy::ReplaceOldest),
+        Ok(Some(1))
+    ));
+    let status = pool.status();
+    assert_eq!(status.max_size, 2);
+    assert_eq!(status.size, 2);
+    assert_eq!(status.available, 2);
+    let mut remaining = pool.remove_where(|_| true);
+    remaining.sort_unstable();
+    assert_eq!(remaining, [2, 3]);
+}
+
+#[tokio::test]
+async fn try_add_with_replace_oldest_in_use() {
+    let pool = Pool::from(vec![1]);
+    let obj = pool.get().await.unwrap();
+    assert!(matches!(
+        pool.try_add_with(2, GrowthPolicy::ReplaceOldest),
+        Err((2, PoolError::Timeout))
+    ));
+    drop(obj);
+    assert_eq!(*pool.get().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn try_add_with_closed() {
+    let pool = Pool::from(vec![1]);
+    pool.close();
+    for policy in [
+        GrowthPolicy::Reject,
+        GrowthPolicy::Grow,
+        GrowthPolicy::ReplaceOldest,
+    ] {
+        assert!(matches!(
+            pool.try_add_with(2, policy),
+            Err((2, PoolError::Closed))
+        ));
+    }
+}
+
+#[tokio::test]
+async fn add_with_replace_oldest_waits() {
+    let pool = Pool::from(vec![1]);
+    let obj = pool.get().await.unwrap();
+    let _ = Object::take(obj);
+    assert!(matches!(
+        pool.add_with(2, GrowthPolicy::ReplaceOldest).await,
+        Ok(None)
+    ));
+    assert_eq!(*pool.get().await.unwrap(), 2);
+}
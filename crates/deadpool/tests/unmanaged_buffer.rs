@@ -0,0 +1,108 @@
+#![cfg(feature = "unmanaged")]
+
+use deadpool::unmanaged::buffer::{BufferPool, BufferPoolConfig, PooledBuffer};
+
+fn pool(max_retained: usize) -> BufferPool {
+    BufferPool::new(BufferPoolConfig {
+        min_capacity: 16,
+        max_capacity: 64,
+        max_retained,
+    })
+}
+
+#[test]
+fn capacity_classes() {
+    let pool = pool(1024);
+    assert_eq!(pool.get(0).capacity(), 16);
+    assert_eq!(pool.get(16).capacity(), 16);
+    assert_eq!(pool.get(17).capacity(), 32);
+    assert_eq!(pool.get(64).capacity(), 64);
+    // Buffers larger than the `max_capacity` aren't pooled.
+    assert!(pool.get(100).capacity() >= 100);
+    // Every buffer is returned before the next one is retrieved.
+    assert_eq!(pool.retained(), 16 + 32 + 64);
+}
+
+#[test]
+fn reuse() {
+    let pool = pool(1024);
+    let mut buf = pool.get(20);
+    buf.extend_from_slice(b"Hello world!");
+    let ptr = buf.as_ptr();
+    drop(buf);
+    assert_eq!(pool.retained(), 32);
+
+    let buf = pool.get(32);
+    assert!(buf.is_empty());
+    assert_eq!(buf.as_ptr(), ptr);
+    assert_eq!(pool.retained(), 0);
+
+    // Smaller classes don't take larger buffers.
+    drop(buf);
+    assert_eq!(pool.get(16).capacity(), 16);
+    assert_eq!(pool.retained(), 32 + 16);
+}
+
+#[test]
+fn grown_buffers() {
+    let pool = pool(1024);
+    let mut buf = pool.get(16);
+    buf.extend_from_slice(&[0; 40]);
+    let capacity = buf.capacity();
+    assert!(capacity >= 40);
+    drop(buf);
+    // The buffer is filed into the class it is large enough for.
+    assert_eq!(pool.retained(), capacity);
+    let buf = pool.get(32);
+    assert_eq!(buf.capacity(), capacity);
+
+    let mut large = pool.get(64);
+    large.extend_from_slice(&[0; 100]);
+    drop(large);
+    assert_eq!(pool.retained(), 0);
+    drop(buf);
+}
+
+#[test]
+fn max_retained() {
+    let pool = pool(100);
+    let bufs = (0..4).map(|_| pool.get(32)).collect::<Vec<_>>();
+    drop(bufs);
+    assert_eq!(pool.retained(), 96);
+    drop(pool.get(64));
+    drop(pool.get(16));
+    assert_eq!(pool.retained(), 96);
+
+    pool.clear();
+    assert_eq!(pool.retained(), 0);
+}
+
+#[test]
+fn take() {
+    let pool = pool(1024);
+    let buf = PooledBuffer::take(pool.get(16));
+    assert_eq!(buf.capacity(), 16);
+    drop(buf);
+    assert_eq!(pool.retained(), 0);
+}
+
+#[test]
+fn dropped_pool() {
+    let pool = pool(1024);
+    let buf = pool.get(16);
+    drop(pool);
+    drop(buf);
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn bytes_mut() {
+    use bytes::{BufMut, BytesMut};
+
+    let pool = BufferPool::<BytesMut>::default();
+    let mut buf = pool.get(1000);
+    buf.put_slice(b"Hello world!");
+    drop(buf);
+    assert_eq!(pool.retained(), 1024);
+    assert!(pool.get(1000).is_empty());
+}
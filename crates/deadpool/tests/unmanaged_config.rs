@@ -0,0 +1,26 @@
+#![cfg(all(feature = "unmanaged", feature = "serde"))]
+
+use std::time::Duration;
+
+use deadpool::unmanaged::{PoolConfig, QueueMode};
+
+#[test]
+fn from_json() {
+    let cfg = serde_json::from_str::<PoolConfig>(
+        r#"{
+            "max_size": 4,
+            "timeout": "30s",
+            "queue_mode": "Fifo"
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(cfg.max_size, 4);
+    assert_eq!(cfg.timeout, Some(Duration::from_secs(30)));
+    assert_eq!(cfg.queue_mode, QueueMode::Fifo);
+
+    let cfg = serde_json::from_str::<PoolConfig>(r#"{ "max_size": 4 }"#).unwrap();
+    assert_eq!(cfg.timeout, None);
+    assert_eq!(cfg.queue_mode, QueueMode::Lifo);
+
+    assert!(serde_json::from_str::<PoolConfig>(r#"{ "max_size": 4, "timeout": "soon" }"#).is_err());
+}
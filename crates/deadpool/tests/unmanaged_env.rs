@@ -0,0 +1,38 @@
+#![cfg(all(feature = "unmanaged", feature = "env"))]
+
+use std::{env, time::Duration};
+
+use deadpool::unmanaged::{FromEnvError, PoolConfig, QueueMode};
+
+// Every test uses its own prefix so they can run concurrently.
+
+#[test]
+fn from_env() {
+    env::set_var("UNMANAGED_ENV_TEST_A__MAX_SIZE", "42");
+    env::set_var("UNMANAGED_ENV_TEST_A__TIMEOUT", "500ms");
+    env::set_var("UNMANAGED_ENV_TEST_A__QUEUE_MODE", "fifo");
+
+    let cfg = PoolConfig::from_env("UNMANAGED_ENV_TEST_A").unwrap();
+    assert_eq!(cfg.max_size, 42);
+    assert_eq!(cfg.timeout, Some(Duration::from_millis(500)));
+    assert_eq!(cfg.queue_mode, QueueMode::Fifo);
+    assert!(cfg.runtime.is_none());
+}
+
+#[test]
+fn from_env_defaults() {
+    let cfg = PoolConfig::from_env("UNMANAGED_ENV_TEST_B").unwrap();
+    assert_eq!(cfg.max_size, PoolConfig::default().max_size);
+    assert_eq!(cfg.timeout, None);
+    assert_eq!(cfg.queue_mode, QueueMode::Lifo);
+}
+
+#[test]
+fn from_env_invalid() {
+    env::set_var("UNMANAGED_ENV_TEST_C__QUEUE_MODE", "random");
+    assert!(matches!(
+        PoolConfig::from_env("UNMANAGED_ENV_TEST_C"),
+        Err(FromEnvError::Invalid { name, value, .. })
+            if name == "UNMANAGED_ENV_TEST_C__QUEUE_MODE" && value == "random"
+    ));
+}
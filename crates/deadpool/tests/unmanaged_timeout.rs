@@ -24,9 +24,9 @@ async fn no_runtime() {
 
 async fn _test_get(runtime: Runtime) {
     let cfg = PoolConfig {
-        max_size: 16,
         timeout: None,
         runtime: Some(runtime),
+        ..PoolConfig::new(16)
     };
     let pool = Pool::from_config(&cfg);
     assert!(matches!(
@@ -37,19 +37,31 @@ async fn _test_get(runtime: Runtime) {
 
 async fn _test_config(runtime: Runtime) {
     let cfg = PoolConfig {
-        max_size: 16,
         timeout: Some(Duration::from_millis(1)),
         runtime: Some(runtime),
+        ..PoolConfig::new(16)
     };
     let pool = Pool::from_config(&cfg);
     assert!(matches!(pool.get().await, Err(PoolError::Timeout)));
 }
 
+async fn _test_factory(runtime: Runtime) {
+    let cfg = PoolConfig {
+        timeout: Some(Duration::from_millis(1)),
+        runtime: Some(runtime),
+        ..PoolConfig::new(16)
+    };
+    let pool = Pool::with_factory(&cfg, std::future::pending);
+    assert!(matches!(pool.get().await, Err(PoolError::Timeout)));
+    assert_eq!(pool.status().size, 0);
+}
+
 #[cfg(feature = "rt_tokio_1")]
 #[tokio::test]
 async fn rt_tokio_1() {
     _test_get(Runtime::Tokio1).await;
     _test_config(Runtime::Tokio1).await;
+    _test_factory(Runtime::Tokio1).await;
 }
 
 #[cfg(feature = "rt_async-std_1")]
@@ -57,4 +69,15 @@ async fn rt_tokio_1() {
 async fn rt_async_std_1() {
     _test_get(Runtime::AsyncStd1).await;
     _test_config(Runtime::AsyncStd1).await;
+    _test_factory(Runtime::AsyncStd1).await;
+}
+
+#[cfg(feature = "rt_smol_2")]
+#[test]
+fn rt_smol_2() {
+    smol::block_on(async {
+        _test_get(Runtime::Smol2).await;
+        _test_config(Runtime::Smol2).await;
+        _test_factory(Runtime::Smol2).await;
+    });
 }